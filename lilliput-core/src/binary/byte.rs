@@ -13,8 +13,8 @@ impl From<Byte> for u8 {
     }
 }
 
-impl std::fmt::Display for Byte {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Byte {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0x")?;
         }
@@ -22,8 +22,8 @@ impl std::fmt::Display for Byte {
     }
 }
 
-impl std::fmt::Debug for Byte {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for Byte {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0b")?;
         }
@@ -31,8 +31,8 @@ impl std::fmt::Debug for Byte {
     }
 }
 
-impl std::fmt::LowerHex for Byte {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::LowerHex for Byte {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0x")?;
         }
@@ -40,8 +40,8 @@ impl std::fmt::LowerHex for Byte {
     }
 }
 
-impl std::fmt::UpperHex for Byte {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::UpperHex for Byte {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0x")?;
         }
@@ -49,8 +49,8 @@ impl std::fmt::UpperHex for Byte {
     }
 }
 
-impl std::fmt::Octal for Byte {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Octal for Byte {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0o")?;
         }
@@ -58,8 +58,8 @@ impl std::fmt::Octal for Byte {
     }
 }
 
-impl std::fmt::Binary for Byte {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Binary for Byte {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0b")?;
         }
@@ -33,8 +33,8 @@ impl<'a> IntoIterator for BytesSlice<'a> {
     }
 }
 
-impl std::fmt::Display for BytesSlice<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for BytesSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "[")?;
         for (index, byte) in self.iter().enumerate() {
             if index > 0 {
@@ -47,8 +47,8 @@ impl std::fmt::Display for BytesSlice<'_> {
     }
 }
 
-impl std::fmt::Debug for BytesSlice<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for BytesSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "[")?;
         for (index, byte) in self.iter().enumerate() {
             if index > 0 {
@@ -65,8 +65,8 @@ impl std::fmt::Debug for BytesSlice<'_> {
     }
 }
 
-impl std::fmt::LowerHex for BytesSlice<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::LowerHex for BytesSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0x ")?;
         }
@@ -80,8 +80,8 @@ impl std::fmt::LowerHex for BytesSlice<'_> {
     }
 }
 
-impl std::fmt::UpperHex for BytesSlice<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::UpperHex for BytesSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0x ")?;
         }
@@ -95,8 +95,8 @@ impl std::fmt::UpperHex for BytesSlice<'_> {
     }
 }
 
-impl std::fmt::Octal for BytesSlice<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Octal for BytesSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0o ")?;
         }
@@ -110,8 +110,8 @@ impl std::fmt::Octal for BytesSlice<'_> {
     }
 }
 
-impl std::fmt::Binary for BytesSlice<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Binary for BytesSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "0b ")?;
         }
@@ -1,7 +1,7 @@
 //! Configurations for encoding/decoding.
 
-pub use float::FloatEncoderConfig;
-pub use int::IntEncoderConfig;
+pub use float::{FloatEncoderConfig, PackedFloatValidation};
+pub use int::{IntEncoderConfig, IntWidth};
 pub use length::LengthEncoderConfig;
 
 mod float;
@@ -41,6 +41,12 @@ pub struct EncoderConfig {
 }
 
 impl EncoderConfig {
+    /// Returns a builder for configuring multiple options at once,
+    /// starting from the default configuration.
+    pub fn builder() -> EncoderConfigBuilder {
+        EncoderConfigBuilder::default()
+    }
+
     /// Sets packing-modes to `packing`, returning `self`.
     pub fn with_packing(mut self, packing: PackingMode) -> Self {
         self.lengths = self.lengths.with_packing(packing);
@@ -48,9 +54,255 @@ impl EncoderConfig {
         self.floats = self.floats.with_packing(packing);
         self
     }
+
+    /// Sets integer width policy to `width`, returning `self`.
+    ///
+    /// See `IntWidth` for details.
+    pub fn with_int_width(mut self, width: IntWidth) -> Self {
+        self.ints = self.ints.with_width(width);
+        self
+    }
+
+    /// Sets float-normalization to `normalize`, returning `self`.
+    ///
+    /// See `FloatEncoderConfig::normalize` for details.
+    pub fn with_normalize_floats(mut self, normalize: bool) -> Self {
+        self.floats = self.floats.with_normalize(normalize);
+        self
+    }
+
+    /// Sets float-shrinking to `shrink`, returning `self`.
+    ///
+    /// See `FloatEncoderConfig::shrink` for details.
+    pub fn with_shrink_floats(mut self, shrink: bool) -> Self {
+        self.floats = self.floats.with_shrink(shrink);
+        self
+    }
+}
+
+/// A builder for `EncoderConfig`, obtained via `EncoderConfig::builder()`.
+#[derive(Default, Clone, Debug)]
+pub struct EncoderConfigBuilder {
+    config: EncoderConfig,
+}
+
+impl EncoderConfigBuilder {
+    /// Sets packing-modes to `packing`.
+    pub fn packing(mut self, packing: PackingMode) -> Self {
+        self.config = self.config.with_packing(packing);
+        self
+    }
+
+    /// Sets integer width policy to `width`.
+    ///
+    /// See `IntWidth` for details.
+    pub fn int_width(mut self, width: IntWidth) -> Self {
+        self.config = self.config.with_int_width(width);
+        self
+    }
+
+    /// Sets float-normalization to `normalize`.
+    ///
+    /// See `FloatEncoderConfig::normalize` for details.
+    pub fn normalize_floats(mut self, normalize: bool) -> Self {
+        self.config = self.config.with_normalize_floats(normalize);
+        self
+    }
+
+    /// Sets float-shrinking to `shrink`.
+    ///
+    /// See `FloatEncoderConfig::shrink` for details.
+    pub fn shrink_floats(mut self, shrink: bool) -> Self {
+        self.config = self.config.with_shrink_floats(shrink);
+        self
+    }
+
+    /// Sets float-validation to `validation`.
+    pub fn float_validation(mut self, validation: PackedFloatValidation) -> Self {
+        self.config.floats = self.config.floats.with_validation(validation);
+        self
+    }
+
+    /// Sets whether equal values should always get encoded into the same bytes.
+    ///
+    /// If `true`, then this sets optimal packing, minimal integer widths and float-normalization.
+    /// If `false`, then this turns float-normalization back off, leaving packing and widths as they are.
+    pub fn canonical(self, canonical: bool) -> Self {
+        if canonical {
+            self.packing(PackingMode::Optimal)
+                .int_width(IntWidth::Minimal)
+                .normalize_floats(true)
+        } else {
+            self.normalize_floats(false)
+        }
+    }
+
+    /// Returns the configuration.
+    pub fn build(self) -> EncoderConfig {
+        self.config
+    }
 }
 
 /// Configuration used for decoding values.
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
-pub struct DecoderConfig {}
+pub struct DecoderConfig {
+    /// Whether map keys not matching any of a struct's fields should be
+    /// rejected (`true`), or left to the struct's implementation (`false`),
+    /// when deserializing structs.
+    ///
+    /// Only applies to format-level integrations, such as `lilliput-serde`.
+    pub deny_unknown_fields: bool,
+    /// Whether strings containing invalid UTF-8 should be decoded lossily (`true`),
+    /// replacing invalid sequences with `U+FFFD`, or rejected (`false`).
+    ///
+    /// Only applies to decoding owned strings (e.g. via `Decoder::decode_string`),
+    /// as borrowed strings (e.g. via `Decoder::decode_str`) can't be repaired in place.
+    pub lossy_strings: bool,
+    /// Whether maps containing the same key more than once should be rejected (`true`),
+    /// or have later entries replace earlier ones (`false`), when decoding maps as values.
+    ///
+    /// Rejecting them protects against smuggling attacks, where different consumers
+    /// of the same input disagree on which of the duplicate entries applies.
+    pub deny_duplicate_keys: bool,
+    /// The maximum length of byte arrays and strings to borrow from the input,
+    /// or `None` for borrowing them regardless of their length.
+    ///
+    /// Longer ones get copied into the provided scratch buffer (i.e. returned
+    /// as `Reference::Copied`), even by readers capable of borrowing, such as `SliceReader`.
+    /// This prevents small references from keeping huge inputs (e.g. memory-mapped files) alive,
+    /// but also causes `Decoder::decode_bytes_borrowed` to fail for them.
+    pub max_borrow_len: Option<usize>,
+    /// Whether to collect statistics about the decoded values (see `Decoder::stats`).
+    pub collect_stats: bool,
+    /// Whether non-negative integers encoded as signed should be decoded as unsigned (`true`),
+    /// or retain the signedness they were encoded with (`false`), when decoding `IntValue`s.
+    ///
+    /// Negative integers always get decoded as signed.
+    pub normalize_int_sign: bool,
+    /// The maximum number of bytes to pre-allocate for a sequence or map,
+    /// based on the length found in its header, or `None` for the default of 1 MiB.
+    ///
+    /// Longer collections still get decoded, but grow incrementally beyond the cap,
+    /// as their elements get decoded. This prevents a single (untrusted) header from
+    /// triggering a huge upfront allocation, independently of any length limits.
+    pub max_prealloc: Option<usize>,
+}
+
+impl DecoderConfig {
+    /// Sets deny-unknown-fields to `deny_unknown_fields`, returning `self`.
+    pub fn with_deny_unknown_fields(mut self, deny_unknown_fields: bool) -> Self {
+        self.deny_unknown_fields = deny_unknown_fields;
+        self
+    }
+
+    /// Sets lossy-strings to `lossy_strings`, returning `self`.
+    pub fn with_lossy_strings(mut self, lossy_strings: bool) -> Self {
+        self.lossy_strings = lossy_strings;
+        self
+    }
+
+    /// Sets deny-duplicate-keys to `deny_duplicate_keys`, returning `self`.
+    pub fn with_deny_duplicate_keys(mut self, deny_duplicate_keys: bool) -> Self {
+        self.deny_duplicate_keys = deny_duplicate_keys;
+        self
+    }
+
+    /// Sets max-borrow-length to `max_borrow_len`, returning `self`.
+    pub fn with_max_borrow_len(mut self, max_borrow_len: Option<usize>) -> Self {
+        self.max_borrow_len = max_borrow_len;
+        self
+    }
+
+    /// Sets collect-stats to `collect_stats`, returning `self`.
+    pub fn with_collect_stats(mut self, collect_stats: bool) -> Self {
+        self.collect_stats = collect_stats;
+        self
+    }
+
+    /// Sets normalize-int-sign to `normalize_int_sign`, returning `self`.
+    pub fn with_normalize_int_sign(mut self, normalize_int_sign: bool) -> Self {
+        self.normalize_int_sign = normalize_int_sign;
+        self
+    }
+
+    /// Sets max-prealloc to `max_prealloc`, returning `self`.
+    pub fn with_max_prealloc(mut self, max_prealloc: Option<usize>) -> Self {
+        self.max_prealloc = max_prealloc;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use super::*;
+
+    fn assert_same(lhs: &EncoderConfig, rhs: &EncoderConfig) {
+        assert_eq!(lhs.lengths, rhs.lengths);
+        assert_eq!(lhs.ints, rhs.ints);
+        assert_eq!(lhs.floats.packing, rhs.floats.packing);
+        assert_eq!(lhs.floats.normalize, rhs.floats.normalize);
+        assert_eq!(lhs.floats.shrink, rhs.floats.shrink);
+    }
+
+    #[test]
+    fn builder_defaults() {
+        assert_same(&EncoderConfig::builder().build(), &EncoderConfig::default());
+    }
+
+    #[test]
+    fn builder_canonical() {
+        let built = EncoderConfig::builder()
+            .packing(PackingMode::None)
+            .int_width(IntWidth::Fixed64)
+            .canonical(true)
+            .build();
+
+        let direct = EncoderConfig {
+            lengths: LengthEncoderConfig {
+                packing: PackingMode::Optimal,
+            },
+            ints: IntEncoderConfig {
+                packing: PackingMode::Optimal,
+                width: IntWidth::Minimal,
+            },
+            floats: FloatEncoderConfig {
+                packing: PackingMode::Optimal,
+                normalize: true,
+                ..FloatEncoderConfig::default()
+            },
+        };
+
+        assert_same(&built, &direct);
+    }
+
+    proptest! {
+        #[test]
+        fn builder_matches_fields(
+            packing in PackingMode::arbitrary(),
+            width in IntWidth::arbitrary(),
+            normalize in any::<bool>(),
+        ) {
+            let built = EncoderConfig::builder()
+                .packing(packing)
+                .int_width(width)
+                .normalize_floats(normalize)
+                .build();
+
+            let direct = EncoderConfig {
+                lengths: LengthEncoderConfig { packing },
+                ints: IntEncoderConfig { packing, width },
+                floats: FloatEncoderConfig {
+                    packing,
+                    normalize,
+                    ..FloatEncoderConfig::default()
+                },
+            };
+
+            assert_same(&built, &direct);
+        }
+    }
+}
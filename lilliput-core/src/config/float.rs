@@ -49,6 +49,21 @@ pub struct FloatEncoderConfig {
     )]
     /// Validation for float-packing.
     pub validation: PackedFloatValidation,
+    /// Whether to normalize values before encoding.
+    ///
+    /// If `true`, then any NaN gets encoded as a canonical quiet NaN,
+    /// and `-0.0` gets encoded as `+0.0`, ensuring that equal values
+    /// always get encoded into the same bytes.
+    ///
+    /// Normalization is lossy with regards to NaN payloads,
+    /// as well as the sign of zero and NaN values.
+    #[cfg_attr(any(test, feature = "testing"), proptest(value = "false"))]
+    pub normalize: bool,
+    /// Whether to encode `f64` values as `f32`, if exactly representable as such.
+    ///
+    /// Unlike packing (which is subject to `validation`) this never narrows lossily,
+    /// preserving the exact bits of the value (including NaN payloads).
+    pub shrink: bool,
 }
 
 impl FloatEncoderConfig {
@@ -63,4 +78,58 @@ impl FloatEncoderConfig {
         self.validation = validation;
         self
     }
+
+    /// Sets float-normalization to `normalize`, returning `self`.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Sets float-shrinking to `shrink`, returning `self`.
+    pub fn with_shrink(mut self, shrink: bool) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// Returns `value`, normalized if float-normalization is enabled.
+    ///
+    /// Normalizing replaces any NaN with a canonical quiet NaN, and `-0.0` with `+0.0`.
+    pub(crate) fn normalized_f32(&self, value: f32) -> f32 {
+        if !self.normalize {
+            value
+        } else if value.is_nan() {
+            f32::NAN
+        } else if value == 0.0 {
+            0.0
+        } else {
+            value
+        }
+    }
+
+    /// Returns `value`, normalized if float-normalization is enabled.
+    ///
+    /// Normalizing replaces any NaN with a canonical quiet NaN, and `-0.0` with `+0.0`.
+    pub(crate) fn normalized_f64(&self, value: f64) -> f64 {
+        if !self.normalize {
+            value
+        } else if value.is_nan() {
+            f64::NAN
+        } else if value == 0.0 {
+            0.0
+        } else {
+            value
+        }
+    }
+
+    /// Returns `value` as `f32`, if float-shrinking is enabled
+    /// and it is exactly representable as such.
+    pub(crate) fn shrunk_f64(&self, value: f64) -> Option<f32> {
+        if !self.shrink {
+            return None;
+        }
+
+        let narrowed = value as f32;
+
+        (f64::from(narrowed).to_bits() == value.to_bits()).then_some(narrowed)
+    }
 }
@@ -2,12 +2,28 @@
 
 use super::PackingMode;
 
+/// Width policy for encoding integer values.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum IntWidth {
+    /// Encode values using the minimal width permitted by the packing mode.
+    #[default]
+    Minimal,
+    /// Encode values using a fixed width of 8 bytes (as `i64`/`u64`), regardless of packing mode.
+    ///
+    /// Every integer value then occupies exactly 9 bytes (header included),
+    /// which allows for patching encoded values in place, without shifting subsequent bytes.
+    Fixed64,
+}
+
 /// Configuration used for encoding integer values.
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 #[derive(Default, Clone, PartialEq, Debug)]
 pub struct IntEncoderConfig {
     /// Packing mode for encoding.
     pub packing: PackingMode,
+    /// Width policy for encoding.
+    pub width: IntWidth,
 }
 
 impl IntEncoderConfig {
@@ -16,4 +32,10 @@ impl IntEncoderConfig {
         self.packing = packing;
         self
     }
+
+    /// Sets width policy to `width`, returning `self`.
+    pub fn with_width(mut self, width: IntWidth) -> Self {
+        self.width = width;
+        self
+    }
 }
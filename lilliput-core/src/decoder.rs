@@ -1,34 +1,176 @@
 //! Decoders for decoding lilliput-encoded values.
 
+use alloc::vec::Vec;
+use core::ops::Range;
+
 use crate::{
-    error::{Error, Result},
-    header::Header,
-    io::{Read, Reference},
+    config::DecoderConfig,
+    error::{Error, ErrorCode, Result},
+    header::{Header, IntHeader, LenWidth, MapHeader, SeqHeader},
+    io::{Read, Reference, SliceReader},
     marker::Marker,
     value::Value,
 };
 
 mod bool;
 mod bytes;
+mod duration;
+mod ext;
 mod float;
+mod framed;
 mod int;
+mod limits;
 mod map;
 mod null;
+mod resumable;
+mod scan;
 mod seq;
+mod stats;
+mod strict;
 mod string;
 mod unit;
+mod values;
+mod visit;
+
+use self::{limits::StepCallback, stats::StatsCollector};
+
+pub use self::{
+    resumable::{Resumable, ResumableDecoder},
+    stats::DecodeStats,
+    values::{Values, ValuesIter},
+    visit::{Visit, Visitor},
+};
+
+#[cfg(target_has_atomic = "ptr")]
+mod interner;
+#[cfg(feature = "bytes")]
+mod shared_bytes;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod timestamp;
+#[cfg(feature = "tokio")]
+mod tokio;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(target_has_atomic = "ptr")]
+pub use self::interner::{Interner, StringInterner};
+#[cfg(feature = "tokio")]
+pub use self::tokio::AsyncDecoder;
+
+/// The maximum nesting depth of values accepted by `Decoder::validate()` and `Decoder::visit()`.
+const MAX_TRAVERSAL_DEPTH: usize = 128;
+
+/// The default maximum number of bytes to pre-allocate for a collection,
+/// based on the (untrusted) length found in its header (see `DecoderConfig::max_prealloc`).
+const MAX_PREALLOCATED_BYTES: usize = 1024 * 1024;
+
+/// The maximum number of bytes to pull at once, when streaming
+/// a value's contents to a writer (e.g. via `Decoder::decode_bytes_to`).
+#[cfg(feature = "std")]
+const STREAMING_CHUNK_LEN: usize = 64 * 1024;
+
+/// Returns a capacity to pre-allocate for `len` elements of type `T`,
+/// capped at `max_bytes`.
+///
+/// Collections of larger lengths still get decoded, but grow by reallocation
+/// beyond the cap, which prevents malicious headers from triggering huge allocations.
+fn cautious_capacity<T>(len: usize, max_bytes: usize) -> usize {
+    let max_len = max_bytes / core::mem::size_of::<T>().max(1);
+    len.min(max_len)
+}
+
+/// The settings a decoder passes on to the nested decoders it creates for
+/// sub-slices of its input (e.g. for framed values), which would otherwise bypass them.
+#[derive(Copy, Clone)]
+struct NestedSettings {
+    config: DecoderConfig,
+    is_strict: bool,
+    max_total_bytes: Option<usize>,
+    step_budget: Option<usize>,
+}
+
+impl NestedSettings {
+    /// Creates a decoder for `bytes`, found at position `pos` of the parent decoder's input.
+    fn decoder(self, bytes: &[u8], pos: usize) -> Decoder<SliceReader<'_>> {
+        let mut decoder = Decoder::new(SliceReader::new(bytes), self.config);
+        decoder.is_strict = self.is_strict;
+        decoder.max_total_bytes = self.max_total_bytes.map(|max| max.saturating_sub(pos));
+        decoder.step_budget = self.step_budget;
+        decoder
+    }
+}
+
+/// Reads the next `len` bytes into the cleared `scratch`, even if `reader` could borrow them.
+fn read_copied<'de, 's, R>(reader: &mut R, len: usize, scratch: &'s mut Vec<u8>) -> Result<&'s [u8]>
+where
+    R: Read<'de>,
+{
+    // Reading into a local buffer (which takes over the scratch buffer's capacity)
+    // decouples the borrowed bytes' lifetime from `scratch`:
+    let mut buf = core::mem::take(scratch);
+    buf.clear();
+
+    let result = match reader.read(len, &mut buf) {
+        Ok(Reference::Borrowed(bytes)) => Ok(Some(bytes)),
+        Ok(Reference::Copied(_)) => Ok(None),
+        Err(err) => Err(err),
+    };
+
+    if let Ok(Some(bytes)) = result {
+        buf.extend_from_slice(bytes);
+    }
+
+    *scratch = buf;
+
+    result.map(|_| scratch.as_slice())
+}
 
 /// A decoder for decoding lilliput-encoded values.
 #[derive(Debug)]
 pub struct Decoder<R> {
     reader: R,
     pos: usize,
+    scratch: Vec<u8>,
+    max_total_bytes: Option<usize>,
+    step_budget: Option<usize>,
+    step_callback: Option<StepCallback>,
+    is_strict: bool,
+    config: DecoderConfig,
+    stats: StatsCollector,
 }
 
 impl<R> Decoder<R> {
     /// Creates a decoder from a `reader`.
     pub fn from_reader(reader: R) -> Self {
-        Decoder { reader, pos: 0 }
+        Self::new(reader, DecoderConfig::default())
+    }
+
+    /// Creates a decoder from a `reader`, configured by `config`.
+    pub fn new(reader: R, config: DecoderConfig) -> Self {
+        Decoder {
+            reader,
+            pos: 0,
+            scratch: Vec::new(),
+            max_total_bytes: None,
+            step_budget: None,
+            step_callback: None,
+            is_strict: false,
+            config,
+            stats: StatsCollector::default(),
+        }
+    }
+
+    /// Returns the decoder's configuration.
+    pub fn config(&self) -> &DecoderConfig {
+        &self.config
+    }
+
+    /// Returns statistics about the values decoded so far,
+    /// or `None` if not enabled via `DecoderConfig::collect_stats`.
+    pub fn stats(&self) -> Option<DecodeStats> {
+        self.config
+            .collect_stats
+            .then(|| self.stats.stats(self.pos))
     }
 
     /// Returns the decoder's internal `reader`, consuming `self`.
@@ -40,6 +182,39 @@ impl<R> Decoder<R> {
     pub fn pos(&self) -> usize {
         self.pos
     }
+
+    /// Returns the settings to be inherited by nested decoders.
+    fn nested_settings(&self) -> NestedSettings {
+        NestedSettings {
+            config: self.config,
+            is_strict: self.is_strict,
+            max_total_bytes: self.max_total_bytes,
+            step_budget: self.step_budget,
+        }
+    }
+
+    /// Replaces the decoder's internal `reader`, resetting its read position
+    /// and collected statistics, returning the previous reader.
+    ///
+    /// This allows for reusing a decoder (and its internal scratch buffer)
+    /// for decoding many values from separate inputs.
+    /// Its configuration, strictness and total-bytes limit are retained,
+    /// while the remaining step budget is not replenished (see `Decoder::set_step_budget`).
+    pub fn reset(&mut self, reader: R) -> R {
+        self.pos = 0;
+        self.stats = StatsCollector::default();
+
+        core::mem::replace(&mut self.reader, reader)
+    }
+
+    /// Returns the number of remaining undecoded bytes,
+    /// or `None` if the decoder's reader is unable to tell (e.g. for streaming readers).
+    pub fn remaining<'de>(&self) -> Option<usize>
+    where
+        R: Read<'de>,
+    {
+        self.reader.remaining()
+    }
 }
 
 impl<'de, R> Decoder<R>
@@ -54,6 +229,14 @@ where
         self.decode_value_of(header)
     }
 
+    /// Decodes a `Value`, along with the range of positions (see `Decoder::pos`)
+    /// its encoding occupied, e.g. for building an index of offsets into the input.
+    pub fn decode_value_with_span(&mut self) -> Result<(Value, Range<usize>)> {
+        let start = self.pos;
+        let value = self.decode_value()?;
+        Ok((value, start..self.pos))
+    }
+
     // MARK: - Marker
 
     /// Decodes a value's type `Marker`.
@@ -61,6 +244,92 @@ where
         self.peek_byte().map(Marker::detect)
     }
 
+    /// Moves the decoder to the absolute byte offset `pos` of its input,
+    /// e.g. for decoding a value at a known offset of an indexed archive.
+    ///
+    /// Fails (without moving the decoder) if the reader is unable to seek
+    /// (e.g. for streaming readers), see `Read::seek_to`.
+    pub fn seek_to(&mut self, pos: usize) -> Result<()> {
+        let old_pos = self.pos;
+
+        self.reader
+            .seek_to(pos)
+            .map_err(|err| err.with_default_pos(old_pos))?;
+
+        if self.config.collect_stats {
+            self.stats.record_jump(old_pos);
+        }
+
+        self.pos = pos;
+
+        Ok(())
+    }
+
+    /// Returns `true` if the decoder's input is exhausted, otherwise `false`,
+    /// without advancing the decoder.
+    ///
+    /// This allows for decoding a stream of values until its end,
+    /// without having to tell an unexpected end-of-file error apart from a clean one.
+    pub fn is_at_end(&mut self) -> Result<bool> {
+        if let Some(remaining) = self.reader.remaining() {
+            return Ok(remaining == 0);
+        }
+
+        match self.peek_byte() {
+            Ok(_) => Ok(false),
+            Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => Ok(true),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Ensures the decoder's input is exhausted, e.g. after decoding a top-level value.
+    ///
+    /// Fails with `ErrorCode::TrailingData` otherwise,
+    /// pointing at the first leftover byte, without advancing the decoder.
+    pub fn finish(&mut self) -> Result<()> {
+        if !self.is_at_end()? {
+            return Err(Error::trailing_data(Some(self.pos)));
+        }
+
+        Ok(())
+    }
+
+    /// Decodes a sequence's `SeqHeader`, if the next value is a sequence.
+    ///
+    /// Fails with `ErrorCode::InvalidType` otherwise, without advancing the decoder.
+    pub fn expect_seq(&mut self) -> Result<SeqHeader> {
+        self.expect_marker(Marker::Seq)?;
+        self.decode_seq_header()
+    }
+
+    /// Decodes a map's `MapHeader`, if the next value is a map.
+    ///
+    /// Fails with `ErrorCode::InvalidType` otherwise, without advancing the decoder.
+    pub fn expect_map(&mut self) -> Result<MapHeader> {
+        self.expect_marker(Marker::Map)?;
+        self.decode_map_header()
+    }
+
+    /// Decodes an integer's `IntHeader`, if the next value is an integer.
+    ///
+    /// Fails with `ErrorCode::InvalidType` otherwise, without advancing the decoder.
+    pub fn expect_int(&mut self) -> Result<IntHeader> {
+        self.expect_marker(Marker::Int)?;
+        self.decode_int_header()
+    }
+
+    fn expect_marker(&mut self, expected: Marker) -> Result<()> {
+        let pos = self.pos;
+
+        let byte = self.peek_byte()?;
+
+        if Marker::detect(byte) != expected {
+            return Err(Error::invalid_marker(expected, byte, Some(pos)));
+        }
+
+        Ok(())
+    }
+
     // MARK: - Header
 
     /// Decodes a value's `Header`.
@@ -75,6 +344,32 @@ where
             Marker::Bool => self.decode_bool_header().map(From::from),
             Marker::Unit => self.decode_unit_header().map(From::from),
             Marker::Null => self.decode_null_header().map(From::from),
+            Marker::Ext => self.decode_ext_header().map(From::from),
+        }
+    }
+
+    /// Decodes a value's `Header`, without advancing the decoder's position.
+    pub fn peek_header(&mut self) -> Result<Header> {
+        let mut len = 1;
+
+        // Peeking doesn't count towards the step budget:
+        let settings = NestedSettings {
+            step_budget: None,
+            ..self.nested_settings()
+        };
+
+        loop {
+            let bytes = self
+                .reader
+                .peek(len)
+                .map_err(|err| err.with_default_pos(self.pos))?;
+            let mut decoder = settings.decoder(bytes, self.pos);
+
+            match decoder.decode_header() {
+                Ok(header) => return Ok(header),
+                Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => len += 1,
+                Err(err) => return Err(err.with_pos_offset(self.pos)),
+            }
         }
     }
 
@@ -100,9 +395,25 @@ where
             Header::Bool(header) => self.skip_bool_value_of(header),
             Header::Unit(header) => self.skip_unit_value_of(header),
             Header::Null(header) => self.skip_null_value_of(header),
+            Header::Ext(header) => self.skip_ext_value_of(header),
         }
     }
 
+    // MARK: - Validate
+
+    /// Validates the next to-be-decoded value's structure, without decoding it.
+    ///
+    /// Headers get decoded and checked recursively, while the values' contents
+    /// get skipped, without allocating any `Value`s.
+    /// As such the contents of strings do not get validated as UTF-8.
+    ///
+    /// Returns the first structural error (with its position) encountered,
+    /// or a depth-limit error for values nested deeper than 128 levels.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn validate(&mut self) -> Result<()> {
+        self.validate_value(MAX_TRAVERSAL_DEPTH)
+    }
+
     // MARK: - Body
 
     /// Decodes value for a given `header`.
@@ -117,6 +428,45 @@ where
             Header::Bool(header) => self.decode_bool_value_of(header).map(From::from),
             Header::Unit(header) => self.decode_unit_value_of(header).map(From::from),
             Header::Null(header) => self.decode_null_value_of(header).map(From::from),
+            Header::Ext(header) => self.decode_ext_value_of(header).map(From::from),
+        }
+    }
+}
+
+// MARK: - Private
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    fn validate_value(&mut self, remaining_depth: usize) -> Result<()> {
+        let pos = self.pos;
+
+        match self.decode_header()? {
+            Header::Seq(header) => {
+                let remaining_depth = remaining_depth
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::depth_limit_exceeded(Some(pos)))?;
+
+                for _ in 0..header.len() {
+                    self.validate_value(remaining_depth)?; // item
+                }
+
+                Ok(())
+            }
+            Header::Map(header) => {
+                let remaining_depth = remaining_depth
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::depth_limit_exceeded(Some(pos)))?;
+
+                for _ in 0..header.len() {
+                    self.validate_value(remaining_depth)?; // key
+                    self.validate_value(remaining_depth)?; // value
+                }
+
+                Ok(())
+            }
+            header => self.skip_value_of(header),
         }
     }
 }
@@ -127,31 +477,47 @@ impl<'de, R> Decoder<R>
 where
     R: Read<'de>,
 {
+    /// Returns the next byte, without consuming it.
     #[inline]
     fn peek_byte(&mut self) -> Result<u8> {
-        self.reader.peek_one()
+        let pos = self.pos;
+
+        self.reader
+            .peek_one()
+            .map_err(|err| err.with_default_pos(pos))
     }
 
     #[inline]
     fn pull_byte_expecting(&mut self, marker: Marker) -> Result<u8> {
         let pos = self.pos;
 
+        self.check_step_budget(pos)?;
+
         let byte = self.pull_byte()?;
 
-        marker.validate(byte).map_err(|exp| {
-            Error::invalid_type(
-                exp.unexpected.to_string(),
-                exp.expected.to_string(),
-                Some(pos),
-            )
-        })?;
+        marker
+            .validate(byte)
+            .map_err(|_| Error::invalid_marker(marker, byte, Some(pos)))?;
+
+        self.consume_step();
+
+        if self.config.collect_stats {
+            self.stats.record_header(marker, pos);
+        }
 
         Ok(byte)
     }
 
     #[inline]
     fn pull_byte(&mut self) -> Result<u8> {
-        let byte = self.reader.read_one()?;
+        self.check_total_bytes(1)?;
+
+        let pos = self.pos;
+
+        let byte = self
+            .reader
+            .read_one()
+            .map_err(|err| err.with_default_pos(pos))?;
 
         self.pos += 1;
 
@@ -166,7 +532,13 @@ where
             return Ok(());
         }
 
-        self.reader.read_into(buf)?;
+        self.check_total_bytes(len)?;
+
+        let pos = self.pos;
+
+        self.reader
+            .read_into(buf)
+            .map_err(|err| err.with_default_pos(pos))?;
 
         self.pos += len;
 
@@ -179,7 +551,54 @@ where
         len: usize,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'de, 's, [u8]>> {
-        let bytes = self.reader.read(len, scratch)?;
+        self.check_total_bytes(len)?;
+
+        let pos = self.pos;
+
+        let bytes = if self.exceeds_max_borrow_len(len) {
+            read_copied(&mut self.reader, len, scratch).map(Reference::Copied)
+        } else {
+            self.reader.read(len, scratch)
+        }
+        .map_err(|err| err.with_default_pos(pos))?;
+
+        debug_assert_eq!(bytes.len(), len);
+
+        self.pos += len;
+
+        Ok(bytes)
+    }
+
+    /// Returns a capacity to pre-allocate for `len` elements of type `T`,
+    /// capped at `DecoderConfig::max_prealloc` bytes.
+    #[inline]
+    fn cautious_capacity<T>(&self, len: usize) -> usize {
+        let max_bytes = self.config.max_prealloc.unwrap_or(MAX_PREALLOCATED_BYTES);
+        cautious_capacity::<T>(len, max_bytes)
+    }
+
+    #[inline]
+    fn exceeds_max_borrow_len(&self, len: usize) -> bool {
+        self.config
+            .max_borrow_len
+            .is_some_and(|max_len| len > max_len)
+    }
+
+    #[inline]
+    fn pull_bytes_into_scratch(&mut self, len: usize) -> Result<Reference<'de, '_, [u8]>> {
+        self.check_total_bytes(len)?;
+
+        // Clearing the scratch buffer retains its capacity:
+        self.scratch.clear();
+
+        let pos = self.pos;
+
+        let bytes = if self.exceeds_max_borrow_len(len) {
+            read_copied(&mut self.reader, len, &mut self.scratch).map(Reference::Copied)
+        } else {
+            self.reader.read(len, &mut self.scratch)
+        }
+        .map_err(|err| err.with_default_pos(pos))?;
 
         debug_assert_eq!(bytes.len(), len);
 
@@ -188,28 +607,106 @@ where
         Ok(bytes)
     }
 
+    /// Pulls the next `len` bytes in chunks of at most `STREAMING_CHUNK_LEN` bytes,
+    /// passing each chunk (along with its position) to `f`.
+    #[cfg(feature = "std")]
+    fn pull_bytes_chunked<F>(&mut self, len: usize, mut f: F) -> Result<()>
+    where
+        F: FnMut(&[u8], usize) -> Result<()>,
+    {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        let mut remaining = len;
+        let mut result = Ok(());
+
+        while remaining > 0 {
+            let chunk_len = remaining.min(STREAMING_CHUNK_LEN);
+            let pos = self.pos;
+
+            scratch.clear();
+
+            result = self
+                .pull_bytes(chunk_len, &mut scratch)
+                .and_then(|bytes| f(bytes.as_ref(), pos));
+
+            if result.is_err() {
+                break;
+            }
+
+            remaining -= chunk_len;
+        }
+
+        self.scratch = scratch;
+
+        result
+    }
+
+    #[inline]
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        self.check_total_bytes(len)?;
+
+        let pos = self.pos;
+
+        self.reader
+            .skip(len)
+            .map_err(|err| err.with_default_pos(pos))?;
+
+        self.pos += len;
+
+        Ok(())
+    }
+
     #[inline]
-    fn pull_len_bytes(&mut self, width: u8) -> Result<usize> {
+    fn pull_len_bytes(&mut self, width: LenWidth) -> Result<usize> {
         let pos = self.pos;
 
-        const MAX_WIDTH: usize = 8;
+        const MAX_WIDTH: usize = LenWidth::MAX.get() as usize;
         let mut padded_be_bytes: [u8; MAX_WIDTH] = [0b0; MAX_WIDTH];
-        self.pull_bytes_into(&mut padded_be_bytes[(MAX_WIDTH - (width as usize))..])?;
+        self.pull_bytes_into(&mut padded_be_bytes[(MAX_WIDTH - usize::from(width))..])?;
 
-        u64::from_be_bytes(padded_be_bytes)
-            .try_into()
-            .map_err(|_| Error::number_out_of_range(Some(pos)))
+        len_from_u64(u64::from_be_bytes(padded_be_bytes), pos)
     }
 }
 
+/// Converts a decoded length `value` into `T` (usually `usize`),
+/// failing with a `RangeOverflow` detailing `value` and `T`'s width otherwise.
+pub(crate) fn len_from_u64<T>(value: u64, pos: usize) -> Result<T>
+where
+    T: TryFrom<u64>,
+{
+    value.try_into().map_err(|_| {
+        let target_bits = (core::mem::size_of::<T>() * 8) as u32;
+        Error::length_out_of_range(value, target_bits, Some(pos))
+    })
+}
+
 // MARK: - Tests
 
 #[cfg(test)]
 mod test {
-    use crate::{error::ErrorCode, io::SliceReader};
+    use proptest::prelude::*;
+
+    use crate::{
+        config::{EncoderConfig, PackingMode},
+        encoder::Encoder,
+        error::{ErrorKind, MarkerMismatch, RangeOverflow},
+        header::StringHeader,
+        io::{StdIoReader, VecWriter},
+        value::IntValue,
+    };
 
     use super::*;
 
+    /// Returns the bytes encoded by `f`, using a default-configured encoder.
+    ///
+    /// Shared by the tests of the decoder's submodules.
+    pub(super) fn encode_with(f: impl FnOnce(&mut Encoder<VecWriter<'_>>)) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        f(&mut encoder);
+        encoded
+    }
+
     #[test]
     fn new() {
         let bytes = SliceReader::new(&[1, 2, 3]);
@@ -295,4 +792,634 @@ mod test {
         assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
         assert_eq!(decoder.pos, 3);
     }
+
+    #[test]
+    fn peek_header() {
+        let bytes = SliceReader::new(&[0b0001_0001, 0b0000_0001, 0b0000_0010]);
+        let mut decoder = Decoder::from_reader(bytes);
+
+        let peeked = decoder.peek_header().unwrap();
+        assert_eq!(decoder.pos, 0);
+
+        let decoded = decoder.decode_header().unwrap();
+        assert_eq!(peeked, decoded);
+    }
+
+    #[test]
+    fn peek_header_truncated() {
+        let bytes = SliceReader::new(&[0b0001_0001, 0b0000_0001]);
+        let mut decoder = Decoder::from_reader(bytes);
+
+        let error_code = decoder.peek_header().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(decoder.pos, 0);
+    }
+
+    proptest! {
+        #[test]
+        fn peek_header_roundtrip(header in Header::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_header(&header).unwrap();
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+
+            let peeked = decoder.peek_header().unwrap();
+            prop_assert_eq!(&peeked, &header);
+            prop_assert_eq!(decoder.pos(), 0);
+
+            let decoded = decoder.decode_header().unwrap();
+            prop_assert_eq!(&decoded, &header);
+            prop_assert_eq!(decoder.pos(), encoded.len());
+        }
+
+        #[test]
+        fn skip_and_validate_roundtrip(value in Value::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::from_writer(writer);
+            encoder.encode_value(&value).unwrap();
+            let len = encoded.len();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            decoder.skip_value().unwrap();
+            prop_assert_eq!(decoder.pos(), len);
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            decoder.validate().unwrap();
+            prop_assert_eq!(decoder.pos(), len);
+        }
+    }
+
+    #[test]
+    fn cautious_capacity() {
+        const MAX: usize = MAX_PREALLOCATED_BYTES;
+
+        assert_eq!(super::cautious_capacity::<u8>(0, MAX), 0);
+        assert_eq!(super::cautious_capacity::<u8>(42, MAX), 42);
+        assert_eq!(super::cautious_capacity::<u8>(usize::MAX, MAX), MAX);
+        assert_eq!(super::cautious_capacity::<()>(usize::MAX, MAX), MAX);
+
+        let max_len = MAX / core::mem::size_of::<Value>();
+        assert_eq!(super::cautious_capacity::<Value>(max_len, MAX), max_len);
+        assert_eq!(super::cautious_capacity::<Value>(max_len + 1, MAX), max_len);
+
+        assert_eq!(super::cautious_capacity::<u8>(42, 0), 0);
+        assert_eq!(super::cautious_capacity::<u16>(42, 10), 5);
+    }
+
+    #[test]
+    fn max_prealloc() {
+        let len = 100_000;
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder
+            .encode_seq_header(&encoder.header_for_seq_len(len))
+            .unwrap();
+        for _ in 0..len {
+            encoder.encode_null().unwrap();
+        }
+
+        let decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.cautious_capacity::<Value>(1000), 1000);
+
+        for max_prealloc in [0, 1, 4 * core::mem::size_of::<Value>()] {
+            let config = DecoderConfig::default().with_max_prealloc(Some(max_prealloc));
+            let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+
+            let max_len = max_prealloc / core::mem::size_of::<Value>();
+            assert_eq!(decoder.cautious_capacity::<Value>(len), max_len);
+
+            // Large (but legitimate) lengths still get decoded, by growing incrementally:
+            let seq = decoder.decode_seq().unwrap();
+            assert_eq!(seq.len(), len);
+        }
+
+        // A huge declared length only allocates up to the cap, before running out of input:
+        let truncated = &encoded[..(encoded.len() / 2)];
+        let config = DecoderConfig::default().with_max_prealloc(Some(64));
+        let mut decoder = Decoder::new(SliceReader::new(truncated), config);
+        let err = decoder.decode_seq().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn validate_nested() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder
+            .encode_map_header(&encoder.header_for_map_len(2))
+            .unwrap();
+        encoder.encode_str("seq").unwrap();
+        encoder
+            .encode_seq_header(&encoder.header_for_seq_len(3))
+            .unwrap();
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_bytes(&[1, 2, 3]).unwrap();
+        encoder.encode_null().unwrap();
+        encoder.encode_str("f64").unwrap();
+        let f64_pos = encoder.pos();
+        encoder.encode_f64(4.2).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.validate().unwrap();
+        assert_eq!(decoder.pos(), encoded.len());
+
+        // Truncated:
+        let truncated = &encoded[..(encoded.len() - 1)];
+        let reader = SliceReader::new(truncated);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.validate().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(err.pos(), Some(f64_pos + 1));
+    }
+
+    #[test]
+    fn validate_invalid_marker() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder
+            .encode_seq_header(&encoder.header_for_seq_len(2))
+            .unwrap();
+        encoder.encode_u8(1).unwrap();
+        let ext_pos = encoder.pos();
+        encoder.encode_ext(42, &[1, 2]).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.validate().unwrap();
+
+        // Corrupt the extension's tag (expected to be an integer) into a string marker,
+        // skipping the extension's header byte and its single-byte length:
+        let tag_pos = ext_pos + 2;
+        encoded[tag_pos] = StringHeader::TYPE_BITS;
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.validate().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert_eq!(err.pos(), Some(tag_pos));
+    }
+
+    #[test]
+    fn validate_depth_limit() {
+        let encode_nested = |depth: usize| {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::from_writer(writer);
+            for _ in 0..(depth - 1) {
+                encoder
+                    .encode_seq_header(&encoder.header_for_seq_len(1))
+                    .unwrap();
+            }
+            encoder
+                .encode_seq_header(&encoder.header_for_seq_len(0))
+                .unwrap();
+            encoded
+        };
+
+        let encoded = encode_nested(MAX_TRAVERSAL_DEPTH);
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.validate().unwrap();
+
+        let encoded = encode_nested(MAX_TRAVERSAL_DEPTH + 1);
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.validate().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+        assert_eq!(err.pos(), Some(MAX_TRAVERSAL_DEPTH));
+    }
+
+    #[test]
+    fn remaining() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_str("lilliput").unwrap();
+        encoder.encode_bytes(&[1, 2, 3]).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.remaining(), Some(encoded.len()));
+
+        while decoder.remaining() != Some(0) {
+            decoder.skip_value().unwrap();
+            assert_eq!(decoder.remaining(), Some(encoded.len() - decoder.pos()));
+        }
+
+        assert_eq!(decoder.pos(), encoded.len());
+
+        let reader = StdIoReader::new(encoded.as_slice());
+        let decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.remaining(), None);
+    }
+
+    #[test]
+    fn is_at_end() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_str("lilliput").unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&[]));
+        assert!(decoder.is_at_end().unwrap());
+        assert_eq!(
+            decoder.decode_value().unwrap_err().code(),
+            ErrorCode::UnexpectedEndOfFile
+        );
+
+        // Readers able to tell their remaining bytes, as well as streaming ones:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut values = Vec::new();
+        while !decoder.is_at_end().unwrap() {
+            values.push(decoder.decode_value().unwrap());
+        }
+        assert_eq!(values, [Value::from(1_u8), Value::from("lilliput")]);
+        assert!(decoder.is_at_end().unwrap());
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(encoded.as_slice()));
+        assert!(!decoder.is_at_end().unwrap());
+        assert_eq!(decoder.pos(), 0);
+        let mut values = Vec::new();
+        while !decoder.is_at_end().unwrap() {
+            values.push(decoder.decode_value().unwrap());
+        }
+        assert_eq!(values, [Value::from(1_u8), Value::from("lilliput")]);
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn finish() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_str("lilliput").unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_value().unwrap(), Value::from(1_u8));
+        let pos = decoder.pos();
+        let err = decoder.finish().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TrailingData);
+        assert_eq!(err.pos(), Some(pos));
+        assert_eq!(decoder.pos(), pos);
+
+        assert_eq!(decoder.decode_value().unwrap(), Value::from("lilliput"));
+        decoder.finish().unwrap();
+
+        // Streaming readers:
+        let mut decoder = Decoder::from_reader(StdIoReader::new(encoded.as_slice()));
+        decoder.decode_value().unwrap();
+        let err = decoder.finish().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TrailingData);
+        assert_eq!(err.pos(), Some(pos));
+        decoder.decode_value().unwrap();
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn seek_to() {
+        let values = [
+            Value::from("lilliput"),
+            Value::from(42_u8),
+            Value::from_iter([Value::from(1_u8), Value::from(vec![1_u8, 2, 3])]),
+        ];
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+
+        let mut offsets = Vec::new();
+        for value in &values {
+            offsets.push(encoder.pos());
+            encoder.encode_value(value).unwrap();
+        }
+
+        let config = DecoderConfig::default().with_collect_stats(true);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+
+        for index in [2, 0, 1, 2, 0] {
+            decoder.seek_to(offsets[index]).unwrap();
+            assert_eq!(decoder.pos(), offsets[index]);
+            assert_eq!(decoder.decode_value().unwrap(), values[index]);
+        }
+
+        // Seeking to the very end is fine, seeking past it is not:
+        decoder.seek_to(encoded.len()).unwrap();
+        assert!(decoder.is_at_end().unwrap());
+
+        let err = decoder.seek_to(encoded.len() + 1).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(decoder.pos(), encoded.len());
+
+        let stats = decoder.stats().unwrap();
+        assert_eq!(stats.count(Marker::String), 2);
+        assert_eq!(stats.count(Marker::Seq), 2);
+
+        // Streaming readers are unable to seek:
+        let mut decoder = Decoder::from_reader(StdIoReader::new(encoded.as_slice()));
+        let err = decoder.seek_to(offsets[1]).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Uncategorized);
+        assert_eq!(decoder.pos(), 0);
+        assert_eq!(decoder.decode_value().unwrap(), values[0]);
+    }
+
+    #[test]
+    fn decode_value_with_span() {
+        let elements = [
+            Value::from(42_u8),
+            Value::from("lilliput"),
+            Value::from_iter([Value::from(true), Value::from(vec![1_u8, 2, 3])]),
+            Value::from(u64::MAX),
+        ];
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_seq(&elements).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let len = decoder.expect_seq().unwrap().len();
+
+        let mut end = decoder.pos();
+
+        for expected in &elements[..len] {
+            let (value, span) = decoder.decode_value_with_span().unwrap();
+            assert_eq!(&value, expected);
+            assert_eq!(span.start, end);
+            end = span.end;
+
+            // Each span contains exactly the element's encoding:
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded[span]));
+            assert_eq!(&decoder.decode_value().unwrap(), expected);
+            assert!(decoder.is_at_end().unwrap());
+        }
+
+        assert_eq!(end, encoded.len());
+
+        // Spans are relative to the decoder's position:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let (value, span) = decoder.decode_value_with_span().unwrap();
+        assert_eq!(value, Value::from_iter(elements));
+        assert_eq!(span, 0..encoded.len());
+    }
+
+    #[cfg(feature = "allocator_api")]
+    #[test]
+    fn decode_in_allocator() {
+        use core::{
+            alloc::{AllocError, Allocator, Layout},
+            cell::{Cell, UnsafeCell},
+            ptr::NonNull,
+        };
+
+        /// A bump allocator, never freeing its arena's memory.
+        struct Bump {
+            arena: UnsafeCell<[u8; 4096]>,
+            offset: Cell<usize>,
+        }
+
+        impl Bump {
+            fn new() -> Self {
+                Self {
+                    arena: UnsafeCell::new([0; 4096]),
+                    offset: Cell::new(0),
+                }
+            }
+
+            fn contains(&self, ptr: *const u8) -> bool {
+                let start = self.arena.get() as *const u8 as usize;
+                (start..(start + 4096)).contains(&(ptr as usize))
+            }
+        }
+
+        unsafe impl Allocator for &Bump {
+            fn allocate(&self, layout: Layout) -> core::result::Result<NonNull<[u8]>, AllocError> {
+                let base = self.arena.get() as *mut u8;
+                let start = (base as usize + self.offset.get()).next_multiple_of(layout.align());
+                let offset = start - base as usize;
+
+                if offset + layout.size() > 4096 {
+                    return Err(AllocError);
+                }
+
+                self.offset.set(offset + layout.size());
+
+                let ptr = NonNull::new(base.wrapping_add(offset)).ok_or(AllocError)?;
+                Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+            }
+
+            unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+        }
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_bytes(&[1, 2, 3]).unwrap();
+        encoder
+            .encode_seq(&[Value::from(1_u8), Value::from("a")])
+            .unwrap();
+        encoder
+            .encode_map(&crate::value::Map::from_iter([(
+                Value::from("a"),
+                Value::from(true),
+            )]))
+            .unwrap();
+
+        let bump = Bump::new();
+
+        fn check<'de>(mut decoder: Decoder<impl Read<'de>>, bump: &Bump) {
+            let bytes = decoder.decode_bytes_buf_in(bump).unwrap();
+            assert_eq!(bytes.as_slice(), &[1, 2, 3]);
+            assert!(bump.contains(bytes.as_ptr()));
+
+            let seq = decoder.decode_seq_in(bump).unwrap();
+            assert_eq!(seq.as_slice(), &[Value::from(1_u8), Value::from("a")]);
+            assert!(bump.contains(seq.as_ptr().cast()));
+
+            let entries = decoder.decode_map_entries_in(bump).unwrap();
+            assert_eq!(entries.as_slice(), &[(Value::from("a"), Value::from(true))]);
+            assert!(bump.contains(entries.as_ptr().cast()));
+        }
+
+        check(Decoder::from_reader(SliceReader::new(&encoded)), &bump);
+        check(
+            Decoder::from_reader(StdIoReader::new(encoded.as_slice())),
+            &bump,
+        );
+
+        assert!(bump.offset.get() > 0);
+    }
+
+    #[test]
+    fn expect_header() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder
+            .encode_seq_header(&SeqHeader::for_len(1, PackingMode::Optimal))
+            .unwrap();
+        encoder
+            .encode_map_header(&MapHeader::for_len(1, PackingMode::Optimal))
+            .unwrap();
+        encoder.encode_u64(42).unwrap();
+        encoder.encode_str("lilliput").unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.expect_seq().unwrap().len(), 1);
+        assert_eq!(decoder.expect_map().unwrap().len(), 1);
+        let header = decoder.expect_int().unwrap();
+        assert_eq!(
+            decoder.decode_int_value_of(header).unwrap(),
+            IntValue::from(42_u64)
+        );
+
+        // A mismatch is reported at the value's offset, without consuming it:
+        let pos = decoder.pos();
+        let err = decoder.expect_map().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert_eq!(err.pos(), Some(pos));
+        let ErrorKind::InvalidType(expectation) = err.kind() else {
+            panic!("expected invalid type error");
+        };
+        assert_eq!(expectation.unexpected, Marker::String.to_string());
+        assert_eq!(expectation.expected, Marker::Map.to_string());
+        let mismatch = err.marker_mismatch().unwrap();
+        assert_eq!(mismatch.expected, Marker::Map);
+        assert_eq!(mismatch.found, Marker::String);
+        assert_eq!(Marker::detect(mismatch.byte), Marker::String);
+
+        assert_eq!(decoder.pos(), pos);
+        assert_eq!(decoder.decode_string().unwrap(), "lilliput");
+    }
+
+    #[test]
+    fn len_out_of_range() {
+        const FIVE_GIB: u64 = 5 << 30;
+
+        assert_eq!(len_from_u64::<u64>(FIVE_GIB, 0).unwrap(), FIVE_GIB);
+
+        // Simulating a 32-bit `usize`:
+        let err = len_from_u64::<u32>(FIVE_GIB, 42).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NumberOutOfRange);
+        assert_eq!(err.pos(), Some(42));
+        assert_eq!(
+            err.range_overflow(),
+            Some(RangeOverflow {
+                value: FIVE_GIB,
+                target_bits: 32,
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "\"number out of range: 5368709120 does not fit into 32 bits\", at position: 42"
+        );
+
+        assert_eq!(Error::number_out_of_range(None).range_overflow(), None);
+    }
+
+    #[test]
+    fn marker_mismatch() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_u8(42).unwrap();
+        encoder.encode_bytes(&[1, 2, 3]).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder.decode_bytes_buf().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert_eq!(err.pos(), Some(0));
+        assert_eq!(
+            err.marker_mismatch(),
+            Some(MarkerMismatch {
+                expected: Marker::Bytes,
+                found: Marker::Int,
+                byte: encoded[0],
+            })
+        );
+
+        // The message remains the same:
+        let ErrorKind::InvalidType(expectation) = err.kind() else {
+            panic!("expected invalid type error");
+        };
+        assert_eq!(expectation.unexpected, Marker::Int.to_string());
+        assert_eq!(expectation.expected, Marker::Bytes.to_string());
+
+        // Errors unrelated to headers have none:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded[..0]));
+        let err = decoder.decode_bytes_buf().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(err.marker_mismatch(), None);
+    }
+
+    #[test]
+    fn error_pos_for_corrupted_marker() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_bool(true).unwrap();
+        encoder.encode_bool(false).unwrap();
+        encoder.encode_bool(true).unwrap();
+
+        // Corrupt the second value's marker into that of an empty string:
+        encoded[1] = 0b0100_0000;
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert!(decoder.decode_bool().unwrap());
+
+        let error = decoder.decode_bool().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidType);
+        assert_eq!(error.pos(), Some(1));
+    }
+
+    #[test]
+    fn error_pos_for_truncated_len() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_bool(true).unwrap();
+        encoder
+            .encode_seq_header(&SeqHeader::extended(300))
+            .unwrap();
+
+        // Drop the last byte of the seq's length:
+        encoded.pop();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert!(decoder.decode_bool().unwrap());
+
+        let error = decoder.decode_seq_header().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(error.pos(), Some(2));
+    }
+
+    #[test]
+    fn error_pos_for_truncated_skip() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_str("lilliput").unwrap();
+
+        let truncated = &encoded[..(encoded.len() - 1)];
+
+        let reader = SliceReader::new(truncated);
+        let mut decoder = Decoder::from_reader(reader);
+
+        let error = decoder.skip_value().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(error.pos(), Some(1));
+    }
 }
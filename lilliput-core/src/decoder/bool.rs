@@ -48,8 +48,10 @@ where
     where
         R: Read<'de>,
     {
+        // The value is stored in the header byte itself, so there's nothing left to skip:
         let _ = header;
-        self.reader.skip_one()
+
+        Ok(())
     }
 
     // MARK: - Body
@@ -68,3 +70,59 @@ where
         Ok(header.value())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{decoder::test::encode_with, error::ErrorCode, io::SliceReader, value::Value};
+
+    use super::*;
+
+    fn seq_of_bool_and_int_then_u8() -> Vec<u8> {
+        encode_with(|encoder| {
+            let seq: Value = [Value::from(true), Value::from(1_u8)].into_iter().collect();
+            encoder.encode_value(&seq).unwrap();
+            encoder.encode_u8(7).unwrap();
+        })
+    }
+
+    #[test]
+    fn skip() {
+        let encoded = encode_with(|encoder| {
+            encoder.encode_bool(true).unwrap();
+            encoder.encode_bool(false).unwrap();
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.pos(), 1);
+        assert!(!decoder.decode_bool().unwrap());
+        decoder.finish().unwrap();
+
+        let encoded = seq_of_bool_and_int_then_u8();
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.decode_u8().unwrap(), 7);
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn validate() {
+        let encoded = seq_of_bool_and_int_then_u8();
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.validate().unwrap();
+        assert_eq!(decoder.decode_u8().unwrap(), 7);
+
+        // A sequence of two elements, truncated after its leading bool:
+        let encoded = encode_with(|encoder| {
+            encoder
+                .encode_seq_header(&encoder.header_for_seq_len(2))
+                .unwrap();
+            encoder.encode_bool(true).unwrap();
+        });
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder.validate().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+}
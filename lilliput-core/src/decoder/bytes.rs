@@ -1,7 +1,10 @@
+use alloc::{string::ToString as _, vec::Vec};
+
 use crate::{
-    error::Result,
-    header::BytesHeader,
-    io::{Read, Reference},
+    config::PackingMode,
+    error::{Error, Result},
+    header::{BytesHeader, LenWidth},
+    io::{BorrowingRead, Read, Reference},
     marker::Marker,
     value::BytesValue,
 };
@@ -25,6 +28,43 @@ where
         self.decode_bytes_of(header, scratch)
     }
 
+    /// Decodes a byte array value, as a slice reference,
+    /// using the decoder's internal scratch buffer (if necessary).
+    ///
+    /// The scratch buffer gets cleared (retaining its capacity) on every call.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_owned_scratch(&mut self) -> Result<Reference<'de, '_, [u8]>> {
+        let header = self.decode_bytes_header()?;
+
+        self.pull_bytes_into_scratch(header.len())
+    }
+
+    /// Decodes a byte array value, as a slice borrowed from the input.
+    ///
+    /// Unlike `decode_bytes`, the returned slice's lifetime is tied to the input only,
+    /// allowing it to outlive subsequent calls on the decoder.
+    ///
+    /// Returns an error if the reader fails to borrow from its input.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_borrowed(&mut self) -> Result<&'de [u8]>
+    where
+        R: BorrowingRead<'de>,
+    {
+        let pos = self.pos;
+
+        let header = self.decode_bytes_header()?;
+
+        let mut scratch = Vec::new();
+
+        match self.decode_bytes_of(header, &mut scratch)? {
+            Reference::Borrowed(bytes) => Ok(bytes),
+            Reference::Copied(_) => Err(Error::uncategorized(
+                "reader failed to borrow from its input",
+                Some(pos),
+            )),
+        }
+    }
+
     /// Decodes a byte array value, as an owned buffer.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_bytes_buf(&mut self) -> Result<Vec<u8>> {
@@ -33,6 +73,82 @@ where
         self.decode_bytes_buf_of(header)
     }
 
+    /// Decodes a byte array value, as an owned buffer allocated by `alloc`.
+    ///
+    /// The bytes may pass through the decoder's internal scratch buffer
+    /// (allocated by the global allocator), depending on the reader.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"allocator_api"` feature.*
+    #[cfg(feature = "allocator_api")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_buf_in<A>(&mut self, alloc: A) -> Result<Vec<u8, A>>
+    where
+        A: core::alloc::Allocator,
+    {
+        let header = self.decode_bytes_header()?;
+        let len = header.len();
+
+        let mut buf = Vec::with_capacity_in(self.cautious_capacity::<u8>(len), alloc);
+        buf.extend_from_slice(&self.pull_bytes_into_scratch(len)?);
+
+        Ok(buf)
+    }
+
+    /// Decodes a byte array value into `buf`, replacing its contents.
+    ///
+    /// The buffer gets cleared (retaining its capacity), allowing it to be reused across calls.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_into(&mut self, buf: &mut Vec<u8>) -> Result<()> {
+        let header = self.decode_bytes_header()?;
+
+        self.decode_bytes_into_of(header, buf)
+    }
+
+    /// Decodes a byte array value, writing its bytes to `out`,
+    /// returning the number of bytes written.
+    ///
+    /// The bytes get streamed to the writer in chunks, without buffering
+    /// the whole byte array, making this suitable for large blobs.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"std"` feature.*
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_to(&mut self, out: &mut dyn std::io::Write) -> Result<usize> {
+        let header = self.decode_bytes_header()?;
+        let len = header.len();
+
+        self.pull_bytes_chunked(len, |bytes, pos| {
+            out.write_all(bytes)
+                .map_err(|err| Error::io(err).with_default_pos(pos))
+        })?;
+
+        Ok(len)
+    }
+
+    /// Decodes a byte array value of exactly `N` bytes, as an array.
+    ///
+    /// Returns an error if the byte array's length is not exactly `N` bytes,
+    /// e.g. for fixed-size fields, such as keys or hashes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let pos = self.pos;
+
+        let header = self.decode_bytes_header()?;
+
+        if header.len() != N {
+            return Err(Error::invalid_length(
+                header.len().to_string(),
+                N.to_string(),
+                Some(pos),
+            ));
+        }
+
+        let mut bytes = [0b0; N];
+        self.pull_bytes_into(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
     /// Decodes a byte array value, as a `BytesValue`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_bytes_value(&mut self) -> Result<BytesValue> {
@@ -44,17 +160,23 @@ where
     /// Decodes a byte array value's header.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_bytes_header(&mut self) -> Result<BytesHeader> {
-        let byte = self.pull_byte_expecting(Marker::Bytes)?;
+        let pos = self.pos;
 
-        let len_width_exponent = byte & BytesHeader::LEN_WIDTH_EXPONENT_BITS;
+        let byte = self.pull_byte_expecting(Marker::Bytes)?;
 
-        let len_width: u8 = 1 << len_width_exponent;
+        let len_width = LenWidth::from_exponent_bits(byte & BytesHeader::LEN_WIDTH_EXPONENT_BITS);
         let len = self.pull_len_bytes(len_width)?;
 
         #[cfg(feature = "tracing")]
         tracing::debug!(byte = crate::binary::fmt_byte(byte), len = len);
 
-        Ok(BytesHeader::for_len(len))
+        let header = BytesHeader::for_len(len);
+
+        self.check_canonical(pos, || {
+            (self.pos - pos) == header.encoded_len(PackingMode::Optimal)
+        })?;
+
+        Ok(header)
     }
 
     // MARK: - Skip
@@ -65,7 +187,7 @@ where
     where
         R: Read<'de>,
     {
-        self.reader.skip(header.len())
+        self.skip_bytes(header.len())
     }
 
     // MARK: - Body
@@ -93,7 +215,17 @@ where
     fn decode_bytes_buf_of(&mut self, header: BytesHeader) -> Result<Vec<u8>> {
         let mut buf = Vec::new();
 
-        match self.decode_bytes_of(header, &mut buf)? {
+        self.decode_bytes_into_of(header, &mut buf)?;
+
+        Ok(buf)
+    }
+
+    /// Decodes byte array value for a given `header`, into a cleared `buf`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn decode_bytes_into_of(&mut self, header: BytesHeader, buf: &mut Vec<u8>) -> Result<()> {
+        buf.clear();
+
+        match self.decode_bytes_of(header, buf)? {
             Reference::Borrowed(slice) => {
                 debug_assert_eq!(buf.len(), 0);
                 buf.extend_from_slice(slice);
@@ -103,6 +235,215 @@ where
             }
         }
 
-        Ok(buf)
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::DecoderConfig,
+        decoder::test::encode_with,
+        error::ErrorCode,
+        io::{Reference, SliceReader, StdIoReader},
+    };
+
+    use super::*;
+
+    #[test]
+    fn decode_bytes_borrowed() {
+        let encoded = encode_with(|encoder| {
+            encoder.encode_bytes(&[1, 2, 3]).unwrap();
+            encoder.encode_bytes(&[4, 5]).unwrap();
+            encoder.encode_u8(6).unwrap();
+        });
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+
+        let first = decoder.decode_bytes_borrowed().unwrap();
+        let second = decoder.decode_bytes_borrowed().unwrap();
+        let third = decoder.decode_u8().unwrap();
+
+        // Both slices outlive subsequent decode calls:
+        assert_eq!(first, &[1, 2, 3]);
+        assert_eq!(second, &[4, 5]);
+        assert_eq!(third, 6);
+
+        // The slices point into the input:
+        assert_eq!(first.as_ptr(), encoded[2..].as_ptr());
+    }
+
+    #[test]
+    fn decode_bytes_owned_scratch() {
+        let values: Vec<Vec<u8>> = vec![vec![42; 64], vec![1, 2, 3], vec![], vec![7; 64]];
+
+        let encoded = encode_with(|encoder| {
+            for value in &values {
+                encoder.encode_bytes(value).unwrap();
+            }
+        });
+
+        let reader = StdIoReader::new(encoded.as_slice());
+        let mut decoder = Decoder::from_reader(reader);
+
+        let decoded = decoder.decode_bytes_owned_scratch().unwrap();
+        assert_eq!(decoded.as_ref(), values[0].as_slice());
+
+        // After warm-up the scratch buffer should no longer get reallocated:
+        let ptr = decoder.scratch.as_ptr();
+        let capacity = decoder.scratch.capacity();
+
+        for value in &values[1..] {
+            let decoded = decoder.decode_bytes_owned_scratch().unwrap();
+            assert_eq!(decoded.as_ref(), value.as_slice());
+
+            assert_eq!(decoder.scratch.as_ptr(), ptr);
+            assert_eq!(decoder.scratch.capacity(), capacity);
+        }
+    }
+
+    #[test]
+    fn decode_bytes_into() {
+        let encoded = encode_with(|encoder| {
+            for len in [100, 50, 10, 0] {
+                encoder.encode_bytes(&vec![len as u8; len]).unwrap();
+            }
+        });
+
+        fn check<'de>(mut decoder: Decoder<impl Read<'de>>) {
+            let mut buf = Vec::new();
+
+            decoder.decode_bytes_into(&mut buf).unwrap();
+            assert_eq!(buf, vec![100; 100]);
+
+            let capacity = buf.capacity();
+            let ptr = buf.as_ptr();
+
+            for len in [50, 10, 0] {
+                decoder.decode_bytes_into(&mut buf).unwrap();
+                assert_eq!(buf, vec![len as u8; len]);
+                assert_eq!(buf.capacity(), capacity);
+                assert_eq!(buf.as_ptr(), ptr);
+            }
+        }
+
+        check(Decoder::from_reader(SliceReader::new(&encoded)));
+        check(Decoder::from_reader(StdIoReader::new(encoded.as_slice())));
+    }
+
+    #[test]
+    fn decode_bytes_array() {
+        let encoded = encode_with(|encoder| {
+            for len in [32, 31, 33, 0] {
+                encoder.encode_bytes(&vec![len as u8; len]).unwrap();
+            }
+        });
+
+        fn check<'de>(mut decoder: Decoder<impl Read<'de>>) {
+            // Exact match:
+            assert_eq!(decoder.decode_bytes_array::<32>().unwrap(), [32; 32]);
+
+            // Too short:
+            let pos = decoder.pos();
+            let err = decoder.decode_bytes_array::<32>().unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidLength);
+            assert_eq!(err.pos(), Some(pos));
+            decoder.skip_bytes(31).unwrap();
+
+            // Too long:
+            let pos = decoder.pos();
+            let err = decoder.decode_bytes_array::<32>().unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidLength);
+            assert_eq!(err.pos(), Some(pos));
+            decoder.skip_bytes(33).unwrap();
+
+            assert_eq!(decoder.decode_bytes_array::<0>().unwrap(), []);
+        }
+
+        check(Decoder::from_reader(SliceReader::new(&encoded)));
+        check(Decoder::from_reader(StdIoReader::new(encoded.as_slice())));
+
+        // Truncated input:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded[..16]));
+        let err = decoder.decode_bytes_array::<32>().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn decode_bytes_to() {
+        let bytes: Vec<u8> = (0..(1024 * 1024)).map(|i| (i % 251) as u8).collect();
+
+        let encoded = encode_with(|encoder| {
+            encoder.encode_bytes(&bytes).unwrap();
+            encoder.encode_bytes(&[]).unwrap();
+            encoder.encode_u8(42).unwrap();
+        });
+
+        fn check<'de>(mut decoder: Decoder<impl Read<'de>>, bytes: &[u8]) {
+            let mut out: Vec<u8> = Vec::new();
+            assert_eq!(decoder.decode_bytes_to(&mut out).unwrap(), bytes.len());
+            assert_eq!(out, bytes);
+
+            assert_eq!(decoder.decode_bytes_to(&mut out).unwrap(), 0);
+            assert_eq!(out.len(), bytes.len());
+
+            let err = decoder.decode_bytes_to(&mut out).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidType);
+        }
+
+        check(Decoder::from_reader(SliceReader::new(&encoded)), &bytes);
+        check(
+            Decoder::from_reader(StdIoReader::new(encoded.as_slice())),
+            &bytes,
+        );
+
+        // Truncated input fails:
+        let truncated = &encoded[..(encoded.len() / 2)];
+        let mut decoder = Decoder::from_reader(SliceReader::new(truncated));
+        let err = decoder.decode_bytes_to(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+
+        // Writer errors get propagated:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder
+            .decode_bytes_to(&mut [0_u8; 16].as_mut_slice())
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::StdIo);
+    }
+
+    #[test]
+    fn max_borrow_len() {
+        let small = b"lilliput".as_slice();
+        let large = [42_u8; 64];
+
+        let encoded = encode_with(|encoder| {
+            encoder.encode_bytes(small).unwrap();
+            encoder.encode_bytes(&large).unwrap();
+            encoder.encode_str("lilliput".repeat(8).as_str()).unwrap();
+        });
+
+        let config = DecoderConfig::default().with_max_borrow_len(Some(small.len()));
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let mut scratch = Vec::new();
+
+        // Payloads up to the threshold still get borrowed:
+        let bytes = decoder.decode_bytes(&mut scratch).unwrap();
+        assert_eq!(bytes, Reference::Borrowed(small));
+
+        // Payloads above it get copied, even from a slice:
+        let bytes = decoder.decode_bytes(&mut scratch).unwrap();
+        assert_eq!(bytes, Reference::Copied(large.as_slice()));
+
+        let string = decoder.decode_str_owned_scratch().unwrap();
+        assert!(matches!(string, Reference::Copied(_)));
+        assert_eq!(&*string, "lilliput".repeat(8));
+
+        // Which prevents borrowing them explicitly:
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        assert_eq!(decoder.decode_bytes_borrowed().unwrap(), small);
+        assert!(decoder.decode_bytes_borrowed().is_err());
     }
 }
@@ -0,0 +1,119 @@
+use alloc::string::ToString as _;
+use core::time::Duration;
+
+use crate::{
+    error::{Error, Result},
+    io::Read,
+};
+
+use super::Decoder;
+
+/// The number of nanoseconds per second.
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a `Duration` value, from a `[seconds, nanoseconds]` sequence.
+    ///
+    /// Returns an error if the nanoseconds are `1_000_000_000` or more.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_duration(&mut self) -> Result<Duration> {
+        let pos = self.pos;
+
+        let header = self.decode_seq_header()?;
+
+        if header.len() != 2 {
+            return Err(Error::invalid_length(
+                header.len().to_string(),
+                "2".to_string(),
+                Some(pos),
+            ));
+        }
+
+        let secs = self.decode_u64()?;
+
+        let nanos_pos = self.pos;
+        let nanos = self.decode_u32()?;
+
+        if nanos >= NANOS_PER_SEC {
+            return Err(Error::invalid_value(
+                nanos.to_string(),
+                "nanoseconds less than 1_000_000_000".to_string(),
+                Some(nanos_pos),
+            ));
+        }
+
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::EncoderConfig,
+        decoder::test::encode_with,
+        encoder::Encoder,
+        error::ErrorCode,
+        header::SeqHeader,
+        io::{SliceReader, VecWriter},
+    };
+
+    use super::*;
+
+    fn arbitrary_duration() -> impl Strategy<Value = Duration> {
+        prop_oneof![
+            Just(Duration::ZERO),
+            Just(Duration::MAX),
+            (u64::arbitrary(), 0..NANOS_PER_SEC)
+                .prop_map(|(secs, nanos)| Duration::new(secs, nanos)),
+        ]
+    }
+
+    #[test]
+    fn decode_invalid_nanos() {
+        let encoded = encode_with(|encoder| {
+            encoder.encode_seq_header(&SeqHeader::compact(2)).unwrap();
+            encoder.encode_u64(1).unwrap();
+            encoder.encode_u32(NANOS_PER_SEC).unwrap();
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error = decoder.decode_duration().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidValue);
+    }
+
+    #[test]
+    fn decode_invalid_len() {
+        let encoded = encode_with(|encoder| {
+            encoder.encode_seq_header(&SeqHeader::compact(1)).unwrap();
+            encoder.encode_u64(1).unwrap();
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error = decoder.decode_duration().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidLength);
+    }
+
+    proptest! {
+        #[test]
+        fn encode_decode_roundtrip(value in arbitrary_duration(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_duration(value).unwrap();
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_duration().unwrap();
+            prop_assert_eq!(decoded, value);
+            prop_assert_eq!(decoder.pos(), encoded.len());
+        }
+    }
+}
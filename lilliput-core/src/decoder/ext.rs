@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+
+use crate::{
+    config::PackingMode,
+    error::Result,
+    header::{ExtHeader, LenWidth},
+    io::{Read, Reference},
+    marker::Marker,
+    value::ExtValue,
+};
+
+use super::Decoder;
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes an extension value, as an `ExtValue`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_ext_value(&mut self) -> Result<ExtValue> {
+        let header = self.decode_ext_header()?;
+        self.decode_ext_value_of(header)
+    }
+
+    // MARK: - Header
+
+    /// Decodes an extension value's header.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_ext_header(&mut self) -> Result<ExtHeader> {
+        let pos = self.pos;
+
+        let byte = self.pull_byte_expecting(Marker::Ext)?;
+
+        let len_width = LenWidth::from_width_bits(byte & ExtHeader::LEN_WIDTH_BITS);
+        let len = self.pull_len_bytes(len_width)?;
+
+        let tag = self.decode_u64()?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(byte = crate::binary::fmt_byte(byte), len = len, tag = tag);
+
+        let header = ExtHeader::new(tag, len);
+
+        self.check_canonical(pos, || {
+            (self.pos - pos) == header.encoded_len(PackingMode::Optimal)
+        })?;
+
+        Ok(header)
+    }
+
+    // MARK: - Skip
+
+    /// Skips the extension value for a given `header`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn skip_ext_value_of(&mut self, header: ExtHeader) -> Result<()> {
+        self.skip_bytes(header.len())
+    }
+
+    // MARK: - Body
+
+    /// Decodes extension value for a given `header`, as an `ExtValue`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_ext_value_of(&mut self, header: ExtHeader) -> Result<ExtValue> {
+        let mut data = Vec::new();
+
+        match self.pull_bytes(header.len(), &mut data)? {
+            Reference::Borrowed(slice) => {
+                debug_assert_eq!(data.len(), 0);
+                data.extend_from_slice(slice);
+            }
+            Reference::Copied(slice) => {
+                debug_assert_eq!(slice.len(), data.len());
+            }
+        }
+
+        Ok(ExtValue::new(header.tag(), data))
+    }
+}
@@ -52,12 +52,15 @@ where
     /// Skips the floating-point value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn skip_float_value_of(&mut self, header: FloatHeader) -> Result<()> {
-        self.reader.skip(header.width().into())
+        self.skip_bytes(header.width().into())
     }
 
     // MARK: - Body
 
     /// Decodes floating-point value for a given `header`, as a `FloatValue`.
+    ///
+    /// Values of up to 4 bytes decode as `FloatValue::F32`, wider ones as `FloatValue::F64`,
+    /// so re-encoding them with the same configuration reproduces the same bytes.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_float_value_of(&mut self, header: FloatHeader) -> Result<FloatValue> {
         match header.width() {
@@ -0,0 +1,251 @@
+use alloc::format;
+
+use crate::{
+    error::{Error, ErrorCode, Result},
+    io::Read,
+    value::Value,
+};
+
+use super::{len_from_u64, Decoder};
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a `Value`, prefixed with its encoded length in bytes.
+    ///
+    /// The value gets decoded from within the bounds of its frame,
+    /// returning an error if it either overruns or underruns the frame,
+    /// which protects against getting out of sync with the stream's frames.
+    ///
+    /// Use `Encoder::encode_framed()` for encoding framed values.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_framed(&mut self) -> Result<Value> {
+        let pos = self.pos;
+
+        // The length prefix is fixed-width by design (see `Encoder::encode_framed()`),
+        // so it's exempt from strict mode's check for canonical encodings:
+        let is_strict = core::mem::replace(&mut self.is_strict, false);
+        let len = self.decode_u64();
+        self.is_strict = is_strict;
+
+        let len: usize = len_from_u64(len?, pos)?;
+
+        let start = self.pos;
+        let settings = self.nested_settings();
+
+        // The nested decoder borrows the callback, as it can't be shared:
+        let step_callback = self.step_callback.take();
+
+        let body = match self.pull_bytes_into_scratch(len) {
+            Ok(body) => body,
+            Err(err) => {
+                self.step_callback = step_callback;
+                return Err(err);
+            }
+        };
+
+        let mut decoder = settings.decoder(&body, start);
+        decoder.step_callback = step_callback;
+
+        let value = decoder.decode_value();
+
+        let decoded_len = decoder.pos();
+        let step_budget = decoder.step_budget;
+        let step_callback = decoder.step_callback.take();
+
+        self.step_budget = step_budget;
+        self.step_callback = step_callback;
+
+        let value = value.map_err(|err| {
+            if err.code() == ErrorCode::UnexpectedEndOfFile {
+                Error::invalid_length(
+                    "value overrunning frame".into(),
+                    format!("frame of {len} bytes"),
+                    Some(start),
+                )
+            } else {
+                err.with_pos_offset(start)
+            }
+        })?;
+
+        if decoded_len != len {
+            return Err(Error::invalid_length(
+                format!("value of {decoded_len} bytes"),
+                format!("frame of {len} bytes"),
+                Some(start),
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::{DecoderConfig, EncoderConfig, PackingMode},
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+        value::{IntValue, SeqValue, StringValue},
+    };
+
+    use super::*;
+
+    fn encode_framed(values: &[Value]) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+
+        for value in values {
+            encoder.encode_framed(value).unwrap();
+        }
+
+        encoded
+    }
+
+    #[test]
+    fn back_to_back() {
+        let first = Value::from(StringValue::from("foo".to_owned()));
+        let second = Value::from(SeqValue::from(vec![
+            Value::from(IntValue::from(1_u8)),
+            Value::from(IntValue::from(-2_i8)),
+        ]));
+
+        let encoded = encode_framed(&[first.clone(), second.clone()]);
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.decode_framed().unwrap(), first);
+        assert_eq!(decoder.decode_framed().unwrap(), second);
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn corrupted_len() {
+        let value = Value::from(StringValue::from("foo".to_owned()));
+
+        let encoded = encode_framed(&[value.clone(), value]);
+
+        // The length prefix is an extended 8-byte integer,
+        // so its least significant byte is the prefix's last one:
+        const LEN_INDEX: usize = 8;
+
+        // Frame too short for its value:
+        let mut corrupted = encoded.clone();
+        corrupted[LEN_INDEX] -= 1;
+
+        let reader = SliceReader::new(&corrupted);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.decode_framed().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidLength);
+        assert_eq!(err.pos(), Some(LEN_INDEX + 1));
+
+        // Frame too long for its value:
+        let mut corrupted = encoded.clone();
+        corrupted[LEN_INDEX] += 1;
+
+        let reader = SliceReader::new(&corrupted);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.decode_framed().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidLength);
+        assert_eq!(err.pos(), Some(LEN_INDEX + 1));
+    }
+
+    #[test]
+    fn strict_roundtrip() {
+        let values = [
+            Value::from("foo"),
+            Value::from(42_u8),
+            [Value::from(1_u8), Value::from(-2_i8)]
+                .into_iter()
+                .collect(),
+        ];
+
+        let encoded = encode_framed(&values);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_strict(true);
+        for value in values {
+            assert_eq!(decoder.decode_framed().unwrap(), value);
+        }
+        assert!(decoder.is_strict());
+
+        // The framed values themselves are still checked:
+        let mut body: Vec<u8> = Vec::new();
+        let config = EncoderConfig::default().with_packing(PackingMode::None);
+        let mut encoder = Encoder::new(VecWriter::new(&mut body), config);
+        encoder.encode_u64(42).unwrap();
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_u64(body.len() as u64).unwrap();
+        encoder.encode_raw(&body).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_strict(true);
+        let err = decoder.decode_framed().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NonCanonicalEncoding);
+    }
+
+    #[test]
+    fn inherits_settings() {
+        // A map with a duplicate key, smuggled within a frame:
+        let mut map: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut map));
+        encoder
+            .encode_map_header(&encoder.header_for_map_len(2))
+            .unwrap();
+        for value in [1_u8, 2] {
+            encoder.encode_str("a").unwrap();
+            encoder.encode_u8(value).unwrap();
+        }
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_u64(map.len() as u64).unwrap();
+        encoder.encode_raw(&map).unwrap();
+
+        let config = DecoderConfig::default().with_deny_duplicate_keys(true);
+
+        let mut decoder = Decoder::new(SliceReader::new(&map), config);
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DuplicateKey);
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let err = decoder.decode_framed().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DuplicateKey);
+
+        // The frame's contents count towards the step budget:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_step_budget(4);
+        let err = decoder.decode_framed().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::StepBudgetExhausted);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_step_budget(6);
+        decoder.decode_framed().unwrap();
+        assert_eq!(decoder.step_budget(), Some(0));
+    }
+
+    proptest! {
+        #[test]
+        fn encode_decode_roundtrip(value in Value::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_framed(&value).unwrap();
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_framed().unwrap();
+            prop_assert_eq!(&decoded, &value);
+            prop_assert_eq!(decoder.pos(), encoded.len());
+        }
+    }
+}
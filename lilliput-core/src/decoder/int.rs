@@ -3,10 +3,11 @@ use core::num::TryFromIntError;
 use num_traits::{Signed, Unsigned};
 
 use crate::{
+    config::PackingMode,
     error::{Error, Result},
     header::{CompactIntHeader, ExtendedIntHeader, IntHeader},
     marker::Marker,
-    num::FromZigZag,
+    num::{FromZigZag, WithPackedBeBytes as _},
     value::{IntValue, SignedIntValue, UnsignedIntValue},
 };
 
@@ -113,6 +114,8 @@ where
     }
 
     /// Decodes a integer value, as an `IntValue`.
+    ///
+    /// The value's variant reflects its encoded signedness (see `IntValue::wire_signedness`).
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_int_value(&mut self) -> Result<IntValue> {
         let header = self.decode_int_header()?;
@@ -165,7 +168,7 @@ where
             IntHeader::Extended(header) => header,
         };
 
-        self.reader.skip(header.width().into())
+        self.skip_bytes(header.width().into())
     }
 
     // MARK: - Body
@@ -173,6 +176,27 @@ where
     /// Decodes integer value for a given `header`, as an `IntValue`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_int_value_of(&mut self, header: IntHeader) -> Result<IntValue> {
+        let pos = self.pos.saturating_sub(header.encoded_len());
+
+        let value = self.decode_int_body_of(header)?;
+
+        self.check_canonical(pos, || header == canonical_int_header(&value))?;
+
+        if self.config.normalize_int_sign {
+            if let IntValue::Signed(signed) = value {
+                if let Ok(unsigned) = signed.to_unsigned() {
+                    return Ok(IntValue::Unsigned(unsigned));
+                }
+            }
+        }
+
+        Ok(value)
+    }
+
+    // MARK: - Private
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn decode_int_body_of(&mut self, header: IntHeader) -> Result<IntValue> {
         let (is_signed, width): (bool, usize) = match header {
             IntHeader::Compact(CompactIntHeader { is_signed, bits }) => {
                 if is_signed {
@@ -297,3 +321,59 @@ where
         }
     }
 }
+
+/// Returns the canonical (i.e. optimally packed) header for `value`.
+fn canonical_int_header(value: &IntValue) -> IntHeader {
+    let packing_mode = PackingMode::Optimal;
+
+    match value {
+        IntValue::Signed(value) => value
+            .canonicalized()
+            .with_packed_be_bytes(packing_mode, |bytes| {
+                IntHeader::for_int_be_bytes(true, bytes, packing_mode)
+            }),
+        IntValue::Unsigned(value) => value
+            .canonicalized()
+            .with_packed_be_bytes(packing_mode, |bytes| {
+                IntHeader::for_int_be_bytes(false, bytes, packing_mode)
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{decoder::test::encode_with, error::ErrorCode, io::SliceReader};
+
+    use super::*;
+
+    #[test]
+    fn decode_scalars_in_order() {
+        let encoded = encode_with(|encoder| {
+            encoder.encode_u32(0xDEAD_BEEF).unwrap();
+            encoder.encode_i64(-42).unwrap();
+            encoder.encode_f32(1.5).unwrap();
+            encoder.encode_bool(true).unwrap();
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_u32().unwrap(), 0xDEAD_BEEF);
+        assert_eq!(decoder.decode_i64().unwrap(), -42);
+        assert_eq!(decoder.decode_f32().unwrap(), 1.5);
+        assert!(decoder.decode_bool().unwrap());
+        decoder.finish().unwrap();
+
+        // Decoding the scalars out of order fails on the first mismatch:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_u32().unwrap(), 0xDEAD_BEEF);
+        let pos = decoder.pos();
+        let err = decoder.decode_bool().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert_eq!(err.pos(), Some(pos));
+        assert_eq!(
+            err.marker_mismatch().map(|mismatch| mismatch.found),
+            Some(Marker::Int)
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::{collections::BTreeSet, sync::Arc};
+
+use crate::{error::Result, io::Read};
+
+use super::Decoder;
+
+/// A string interner, deduplicating equal strings into shared `Arc<str>`s.
+///
+/// Used by `Decoder::decode_str_interned`, e.g. for decoding the keys of
+/// homogeneous records, without allocating each occurrence separately.
+pub trait Interner {
+    /// Returns a shared string equal to `value`.
+    fn intern(&mut self, value: &str) -> Arc<str>;
+}
+
+/// A string interner, retaining every string it has interned.
+#[derive(Default, Clone, Debug)]
+pub struct StringInterner {
+    strings: BTreeSet<Arc<str>>,
+}
+
+impl StringInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct strings interned so far.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true`, if no strings have been interned so far, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl Interner for StringInterner {
+    fn intern(&mut self, value: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(value) {
+            return Arc::clone(interned);
+        }
+
+        let interned: Arc<str> = Arc::from(value);
+        self.strings.insert(Arc::clone(&interned));
+        interned
+    }
+}
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a string value, as a shared string obtained from `interner`.
+    ///
+    /// The string gets decoded using the decoder's internal scratch buffer (if necessary),
+    /// only allocating if `interner` hasn't encountered an equal string before.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_str_interned(&mut self, interner: &mut dyn Interner) -> Result<Arc<str>> {
+        let str_ref = self.decode_str_owned_scratch()?;
+
+        Ok(interner.intern(&str_ref))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use test_log::test;
+
+    use crate::{
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{SliceReader, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    #[test]
+    fn intern() {
+        let mut interner = StringInterner::new();
+        assert!(interner.is_empty());
+
+        let a = interner.intern("a");
+        let b = interner.intern("b");
+        assert!(Arc::ptr_eq(&a, &interner.intern("a")));
+        assert!(!Arc::ptr_eq(&a, &b));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn decode_records() {
+        const RECORDS: usize = 1000;
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder
+            .encode_seq_header(&encoder.header_for_seq_len(RECORDS))
+            .unwrap();
+        for id in 0..RECORDS {
+            encoder
+                .encode_map_header(&encoder.header_for_map_len(2))
+                .unwrap();
+            encoder.encode_str("id").unwrap();
+            encoder.encode_u64(id as u64).unwrap();
+            encoder.encode_str("name").unwrap();
+            encoder.encode_str(&id.to_string()).unwrap();
+        }
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut interner = StringInterner::new();
+
+        let mut records: Vec<Vec<(Arc<str>, Value)>> = Vec::new();
+        for _ in 0..decoder.expect_seq().unwrap().len() {
+            let len = decoder.expect_map().unwrap().len();
+            let record = (0..len)
+                .map(|_| {
+                    let key = decoder.decode_str_interned(&mut interner).unwrap();
+                    (key, decoder.decode_value().unwrap())
+                })
+                .collect();
+            records.push(record);
+        }
+
+        assert_eq!(records.len(), RECORDS);
+        assert_eq!(interner.len(), 2);
+
+        let (id_key, name_key) = (&records[0][0].0, &records[0][1].0);
+        assert_eq!((&**id_key, &**name_key), ("id", "name"));
+
+        for (id, record) in records.iter().enumerate() {
+            assert!(Arc::ptr_eq(&record[0].0, id_key));
+            assert!(Arc::ptr_eq(&record[1].0, name_key));
+            assert_eq!(record[0].1, Value::from(id as u64));
+        }
+
+        // Non-strings don't get interned:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder.decode_str_interned(&mut interner).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert_eq!(interner.len(), 2);
+    }
+}
@@ -0,0 +1,294 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::{
+    error::{Error, Result},
+    io::Read,
+};
+
+use super::Decoder;
+
+/// A callback replenishing a decoder's exhausted step budget (see `Decoder::set_step_callback`).
+pub(super) struct StepCallback(Box<dyn FnMut() -> Option<usize> + Send + Sync>);
+
+impl fmt::Debug for StepCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StepCallback").finish_non_exhaustive()
+    }
+}
+
+impl<R> Decoder<R> {
+    /// Returns the maximum total number of bytes the decoder is allowed to decode, if any.
+    pub fn max_total_bytes(&self) -> Option<usize> {
+        self.max_total_bytes
+    }
+
+    /// Limits the total number of bytes the decoder is allowed to decode to `max_total_bytes`.
+    ///
+    /// Decoding (or skipping) any bytes beyond the limit fails with
+    /// `ErrorCode::LengthLimitExceeded`, before reading or allocating for them,
+    /// regardless of how the bytes are distributed among the document's values.
+    pub fn set_max_total_bytes(&mut self, max_total_bytes: usize) {
+        self.max_total_bytes = Some(max_total_bytes);
+    }
+
+    /// Returns the number of headers the decoder is still allowed to decode, if limited.
+    pub fn step_budget(&self) -> Option<usize> {
+        self.step_budget
+    }
+
+    /// Limits the number of headers (i.e. values, including nested ones)
+    /// the decoder is allowed to decode (or skip) to `step_budget`.
+    ///
+    /// Decoding a header beyond the budget fails with `ErrorCode::StepBudgetExhausted`,
+    /// without advancing the decoder, allowing for cooperative cancellation of large documents.
+    ///
+    /// If the budget ran out at the start of a value (e.g. while decoding the elements
+    /// of a sequence one by one), decoding can be resumed by replenishing the budget
+    /// and retrying. Values the budget ran out within are left partially decoded,
+    /// unless the budget gets replenished via `Decoder::set_step_callback` instead.
+    pub fn set_step_budget(&mut self, step_budget: usize) {
+        self.step_budget = Some(step_budget);
+    }
+
+    /// Sets a `callback` to be called whenever the decoder's step budget
+    /// (see `Decoder::set_step_budget`) runs out.
+    ///
+    /// Returning `Some(step_budget)` replenishes the budget and continues decoding
+    /// where it left off, even within a value, while returning `None` cancels decoding,
+    /// failing with `ErrorCode::StepBudgetExhausted` as if no callback had been set.
+    ///
+    /// This allows for periodically checking for cancellation (e.g. of a timed-out request)
+    /// while decoding large documents, without losing any partially decoded values.
+    pub fn set_step_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> Option<usize> + Send + Sync + 'static,
+    {
+        self.step_callback = Some(StepCallback(Box::new(callback)));
+    }
+}
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    #[inline]
+    pub(super) fn check_total_bytes(&self, pending: usize) -> Result<()> {
+        let Some(max_total_bytes) = self.max_total_bytes else {
+            return Ok(());
+        };
+
+        if self.pos.saturating_add(pending) > max_total_bytes {
+            return Err(Error::length_limit_exceeded(Some(self.pos)));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the step budget (if any) allows for decoding another header at `pos`,
+    /// calling the step callback (if any) for replenishing it, once exhausted.
+    #[inline]
+    pub(super) fn check_step_budget(&mut self, pos: usize) -> Result<()> {
+        if self.step_budget != Some(0) {
+            return Ok(());
+        }
+
+        let step_budget = match &mut self.step_callback {
+            Some(StepCallback(callback)) => callback(),
+            None => None,
+        };
+
+        match step_budget {
+            Some(step_budget) if step_budget > 0 => {
+                self.step_budget = Some(step_budget);
+                Ok(())
+            }
+            _ => Err(Error::step_budget_exhausted(Some(pos))),
+        }
+    }
+
+    #[inline]
+    pub(super) fn consume_step(&mut self) {
+        if let Some(step_budget) = &mut self.step_budget {
+            *step_budget -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{decoder::test::encode_with, error::ErrorCode, io::SliceReader, value::Value};
+
+    use super::*;
+
+    #[test]
+    fn max_total_bytes() {
+        let value: Value = [
+            (Value::from("name"), Value::from("lilliput")),
+            (Value::from("bytes"), Value::from(vec![0_u8; 100])),
+            (
+                Value::from("seq"),
+                (0..10_u8).map(Value::from).collect::<Value>(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let encoded = encode_with(|encoder| encoder.encode_value(&value).unwrap());
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.max_total_bytes(), None);
+        decoder.set_max_total_bytes(encoded.len());
+        assert_eq!(decoder.max_total_bytes(), Some(encoded.len()));
+        assert_eq!(decoder.decode_value().unwrap(), value);
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.set_max_total_bytes(encoded.len() - 1);
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LengthLimitExceeded);
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.set_max_total_bytes(encoded.len() - 1);
+        let err = decoder.skip_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LengthLimitExceeded);
+    }
+
+    #[test]
+    fn max_total_bytes_before_allocating() {
+        let encoded = encode_with(|encoder| encoder.encode_bytes(&[0; 1000]).unwrap());
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.set_max_total_bytes(100);
+        let header = decoder.decode_bytes_header().unwrap();
+        let pos = decoder.pos();
+        let err = decoder.decode_bytes_value_of(header).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LengthLimitExceeded);
+        assert_eq!(err.pos(), Some(pos));
+    }
+
+    #[test]
+    fn step_budget() {
+        const STEPS: usize = 64;
+
+        /// Retries `step` until it succeeds, replenishing the budget whenever it runs out.
+        fn resume<'de, T>(
+            decoder: &mut Decoder<SliceReader<'de>>,
+            yields: &mut usize,
+            mut step: impl FnMut(&mut Decoder<SliceReader<'de>>) -> Result<T>,
+        ) -> T {
+            loop {
+                match step(decoder) {
+                    Err(err) if err.code() == ErrorCode::StepBudgetExhausted => {
+                        assert_eq!(err.pos(), Some(decoder.pos()));
+                        *yields += 1;
+                        decoder.set_step_budget(STEPS);
+                    }
+                    result => return result.unwrap(),
+                }
+            }
+        }
+
+        let value: Value = (0..1000_u32)
+            .map(|i| match i % 2 {
+                0 => Value::from(i),
+                _ => Value::from(i.to_string()),
+            })
+            .collect();
+
+        let encoded = encode_with(|encoder| encoder.encode_value(&value).unwrap());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.step_budget(), None);
+        let one_shot = decoder.decode_value().unwrap();
+        assert_eq!(one_shot, value);
+
+        // Decoding element by element, yielding whenever the budget runs out:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_step_budget(STEPS);
+        assert_eq!(decoder.step_budget(), Some(STEPS));
+
+        let mut yields = 0;
+        let header = resume(&mut decoder, &mut yields, Decoder::decode_seq_header);
+        let stepwise: Value = (0..header.len())
+            .map(|_| resume(&mut decoder, &mut yields, Decoder::decode_value))
+            .collect();
+
+        assert_eq!(stepwise, one_shot);
+        assert_eq!(decoder.pos(), encoded.len());
+
+        // One header for the sequence, plus one per element:
+        assert_eq!(yields, (1 + 1000) / STEPS);
+
+        // Values the budget runs out within fail, both when decoding and skipping:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_step_budget(STEPS);
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::StepBudgetExhausted);
+        assert_eq!(decoder.step_budget(), Some(0));
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_step_budget(STEPS);
+        let err = decoder.skip_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::StepBudgetExhausted);
+    }
+
+    #[test]
+    fn step_callback() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        const STEPS: usize = 64;
+
+        let value: Value = (0..1000_u32)
+            .map(|i| Value::from_iter([Value::from(i), Value::from(i.to_string())]))
+            .collect();
+
+        let encoded = encode_with(|encoder| {
+            encoder.encode_value(&value).unwrap();
+            encoder.encode_framed(&value).unwrap();
+        });
+
+        // Replenishing the budget resumes decoding within values:
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_step_budget(STEPS);
+        decoder.set_step_callback({
+            let calls = Arc::clone(&calls);
+            move || {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Some(STEPS)
+            }
+        });
+
+        assert_eq!(decoder.decode_value().unwrap(), value);
+        assert_eq!(decoder.decode_framed().unwrap(), value);
+        decoder.finish().unwrap();
+
+        // One header for the sequence, plus three per element, per copy:
+        assert_eq!(calls.load(Ordering::Relaxed), (2 * (1 + 3 * 1000)) / STEPS);
+
+        // Returning `None` cancels decoding:
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.set_step_budget(STEPS);
+        decoder.set_step_callback({
+            let calls = Arc::clone(&calls);
+            move || (calls.fetch_add(1, Ordering::Relaxed) < 2).then_some(STEPS)
+        });
+
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::StepBudgetExhausted);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(decoder.step_budget(), Some(0));
+    }
+}
@@ -1,8 +1,11 @@
+use alloc::vec::Vec;
+
 use crate::{
-    error::Result,
-    header::MapHeader,
+    config::PackingMode,
+    error::{Error, Result},
+    header::{LenWidth, MapHeader},
     marker::Marker,
-    value::{Map, MapValue},
+    value::{Map, MapValue, Value},
 };
 
 use super::{Decoder, Read};
@@ -26,16 +29,98 @@ where
         self.decode_map().map(From::from)
     }
 
+    /// Decodes a map value's entries, in the order of their encoding.
+    ///
+    /// Unlike `decode_map`, this preserves the entries' on-wire order
+    /// (including any duplicate keys), regardless of the `"preserve_order"` feature.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_map_entries(&mut self) -> Result<Vec<(Value, Value)>> {
+        let header = self.decode_map_header()?;
+
+        let mut entries =
+            Vec::with_capacity(self.cautious_capacity::<(Value, Value)>(header.len()));
+
+        for _ in 0..header.len() {
+            let key = self.decode_value()?;
+            let value = self.decode_value()?;
+            entries.push((key, value));
+        }
+
+        Ok(entries)
+    }
+
+    /// Decodes a map value's entries, in the order of their encoding,
+    /// with the entries allocated by `alloc`.
+    ///
+    /// Only the entries' own buffer gets allocated by `alloc`,
+    /// while their keys' and values' contents (e.g. nested strings) use the global allocator.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"allocator_api"` feature.*
+    #[cfg(feature = "allocator_api")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_map_entries_in<A>(&mut self, alloc: A) -> Result<Vec<(Value, Value), A>>
+    where
+        A: core::alloc::Allocator,
+    {
+        let header = self.decode_map_header()?;
+
+        let mut entries = Vec::with_capacity_in(
+            self.cautious_capacity::<(Value, Value)>(header.len()),
+            alloc,
+        );
+
+        for _ in 0..header.len() {
+            let key = self.decode_value()?;
+            let value = self.decode_value()?;
+            entries.push((key, value));
+        }
+
+        Ok(entries)
+    }
+
+    /// Decodes the value for `key` from a map value, if it contains an entry for `key`.
+    ///
+    /// Only the map's keys (and the matching value) get decoded,
+    /// all other values get skipped, without decoding them.
+    /// The map's remaining entries get skipped as well, after a match,
+    /// leaving the decoder positioned after the map.
+    ///
+    /// Returns the value for the first matching entry, or `None` if there is no match.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn find_map_value(&mut self, key: &Value) -> Result<Option<Value>> {
+        let header = self.decode_map_header()?;
+
+        let mut found = None;
+
+        for _ in 0..header.len() {
+            if found.is_some() {
+                self.skip_value()?; // key
+                self.skip_value()?; // value
+                continue;
+            }
+
+            if self.decode_value()? == *key {
+                found = Some(self.decode_value()?);
+            } else {
+                self.skip_value()?;
+            }
+        }
+
+        Ok(found)
+    }
+
     // MARK: - Header
 
     /// Decodes a map value's header.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_map_header(&mut self) -> Result<MapHeader> {
+        let pos = self.pos;
+
         let byte = self.pull_byte_expecting(Marker::Map)?;
 
         let is_compact = (byte & MapHeader::COMPACT_VARIANT_BIT) != 0b0;
 
-        if is_compact {
+        let header = if is_compact {
             let len = byte & MapHeader::COMPACT_LEN_BITS;
 
             #[cfg(feature = "tracing")]
@@ -45,9 +130,9 @@ where
                 len = len
             );
 
-            Ok(MapHeader::compact(len))
+            MapHeader::compact(len)
         } else {
-            let len_width = 1 + (byte & MapHeader::EXTENDED_LEN_WIDTH_BITS);
+            let len_width = LenWidth::from_width_bits(byte & MapHeader::EXTENDED_LEN_WIDTH_BITS);
             let len = self.pull_len_bytes(len_width)?;
 
             #[cfg(feature = "tracing")]
@@ -57,8 +142,15 @@ where
                 len = len
             );
 
-            Ok(MapHeader::extended(len))
-        }
+            MapHeader::extended(len)
+        };
+
+        self.check_canonical(pos, || {
+            let canonical = MapHeader::for_len(header.len(), PackingMode::Optimal);
+            header == canonical && (self.pos - pos) == canonical.encoded_len(PackingMode::Optimal)
+        })?;
+
+        Ok(header)
     }
 
     // MARK: - Skip
@@ -71,6 +163,10 @@ where
             MapHeader::Extended(header) => header.len(),
         };
 
+        if self.skip_values_at_once(len.saturating_mul(2))? {
+            return Ok(());
+        }
+
         for _ in 0..len {
             self.skip_value()?; // key
             self.skip_value()?; // value
@@ -92,10 +188,20 @@ where
     /// Decodes map value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn decode_map_of(&mut self, header: MapHeader) -> Result<Map> {
+        #[cfg(feature = "preserve_order")]
+        let mut map = Map::with_capacity(self.cautious_capacity::<(Value, Value)>(header.len()));
+        // B-tree maps don't support pre-allocation:
+        #[cfg(not(feature = "preserve_order"))]
         let mut map = Map::default();
 
         for _ in 0..header.len() {
+            let pos = self.pos;
             let key = self.decode_value()?;
+
+            if self.config.deny_duplicate_keys && map.contains_key(&key) {
+                return Err(Error::duplicate_key(format_args!("{key:?}"), Some(pos)));
+            }
+
             let value = self.decode_value()?;
             map.insert(key, value);
         }
@@ -103,3 +209,174 @@ where
         Ok(map)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::DecoderConfig,
+        decoder::test::encode_with,
+        encoder::Encoder,
+        error::{ErrorCode, ErrorKind},
+        io::{SliceReader, VecWriter},
+        value::{IntValue, StringValue},
+    };
+
+    use super::*;
+
+    #[test]
+    fn map_entries_roundtrip() {
+        let entries: Vec<(Value, Value)> = vec![
+            (Value::from(42_u8), Value::from("a")),
+            (Value::from(true), Value::from("b")),
+            (Value::from(-7_i8), Value::from("c")),
+            (Value::from("key"), Value::from("d")),
+            (Value::from(1.5_f64), Value::from("e")),
+        ];
+
+        let encoded = encode_with(|encoder| encoder.encode_map_entries(&entries).unwrap());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_map_entries().unwrap(), entries);
+        decoder.finish().unwrap();
+
+        // Decoding into a `Map` instead gives up on the wire order:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_map().unwrap().len(), entries.len());
+    }
+
+    #[test]
+    fn decode_map_entries() {
+        let keys: Vec<u8> = vec![3, 1, 2, 1];
+
+        let encoded = encode_with(|encoder| {
+            encoder
+                .encode_map_header(&encoder.header_for_map_len(keys.len()))
+                .unwrap();
+            for (index, key) in keys.iter().enumerate() {
+                encoder.encode_u8(*key).unwrap();
+                encoder.encode_u8(index as u8).unwrap();
+            }
+        });
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let entries = decoder.decode_map_entries().unwrap();
+        assert_eq!(decoder.pos(), encoded.len());
+
+        let expected: Vec<(Value, Value)> = keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| {
+                (
+                    Value::from(IntValue::from(*key)),
+                    Value::from(IntValue::from(index as u8)),
+                )
+            })
+            .collect();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn find_map_value() {
+        let len = 100;
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder
+            .encode_map_header(&encoder.header_for_map_len(len))
+            .unwrap();
+
+        for index in 0..len {
+            let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+            encoder.encode_u8(index as u8).unwrap();
+
+            if index < 89 {
+                // Strings with invalid UTF-8 fail to decode, but skip just fine:
+                encoder
+                    .encode_string_header(&encoder.header_for_str_len(2))
+                    .unwrap();
+                encoded.extend_from_slice(&[0xff, 0xfe]);
+            } else {
+                encoder.encode_str(&format!("value {index}")).unwrap();
+            }
+        }
+
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_bool(true).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let value = decoder
+            .find_map_value(&Value::from(IntValue::from(89_u8)))
+            .unwrap();
+        assert_eq!(
+            value,
+            Some(Value::from(StringValue::from("value 89".to_owned())))
+        );
+        // The decoder should be positioned after the map:
+        assert!(decoder.decode_bool().unwrap());
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let value = decoder
+            .find_map_value(&Value::from(IntValue::from(100_u8)))
+            .unwrap();
+        assert_eq!(value, None);
+        assert!(decoder.decode_bool().unwrap());
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Utf8);
+    }
+
+    #[test]
+    fn deny_duplicate_keys() {
+        fn encode_map(keys: &[&str]) -> Vec<u8> {
+            encode_with(|encoder| {
+                encoder
+                    .encode_map_header(&MapHeader::for_len(keys.len(), PackingMode::Optimal))
+                    .unwrap();
+                for (index, key) in keys.iter().enumerate() {
+                    encoder.encode_str(key).unwrap();
+                    encoder.encode_u64(index as u64).unwrap();
+                }
+            })
+        }
+
+        let config = DecoderConfig::default().with_deny_duplicate_keys(true);
+
+        // Distinct keys decode fine either way:
+        let encoded = encode_map(&["a", "b", "c"]);
+        for config in [DecoderConfig::default(), config] {
+            let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+            let Value::Map(map) = decoder.decode_value().unwrap() else {
+                panic!("expected map");
+            };
+            assert_eq!(map.len(), 3);
+        }
+
+        let encoded = encode_map(&["a", "b", "a"]);
+
+        // By default the later entry wins:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let Value::Map(map) = decoder.decode_value().unwrap() else {
+            panic!("expected map");
+        };
+        assert_eq!(map.len(), 2);
+        let key = Value::String("a".to_owned().into());
+        assert_eq!(map.as_map_ref()[&key], Value::Int(2_u64.into()));
+
+        // If denied, the duplicate is reported at its key's offset:
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DuplicateKey);
+        assert_eq!(err.pos(), Some(1 + 2 + 1 + 2 + 1));
+        let ErrorKind::DuplicateKey(key) = err.kind() else {
+            panic!("expected duplicate key error");
+        };
+        assert_eq!(key, "\"a\"");
+    }
+}
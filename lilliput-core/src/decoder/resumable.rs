@@ -0,0 +1,242 @@
+use alloc::vec::Vec;
+
+use crate::{config::DecoderConfig, error::Result, io::SliceReader, value::Value};
+
+use super::{
+    scan::{Scanned, ValueScanner},
+    Decoder,
+};
+
+/// The outcome of a resumable decoding attempt.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Resumable<T> {
+    /// The value was decoded completely.
+    Complete(T),
+    /// The value is incomplete, and requires more bytes to be fed.
+    Pending {
+        /// The number of bytes of the incomplete value that have been buffered so far.
+        buffered: usize,
+    },
+}
+
+/// A decoder for decoding values that may be split across multiple chunks of input
+/// (e.g. when reading from a socket), allowing decoding to be resumed once more bytes arrive.
+///
+/// Bytes get fed into the decoder via [`ResumableDecoder::feed`], and get buffered
+/// until a value can be decoded completely. Only the headers of incomplete values
+/// get scanned (picking up where the previous attempt left off), with the value
+/// itself getting decoded exactly once, after all of its bytes have been fed.
+#[derive(Debug)]
+pub struct ResumableDecoder {
+    buffer: Vec<u8>,
+    pos: usize,
+    scanner: ValueScanner,
+    config: DecoderConfig,
+}
+
+impl Default for ResumableDecoder {
+    fn default() -> Self {
+        Self::with_config(DecoderConfig::default())
+    }
+}
+
+impl ResumableDecoder {
+    /// Creates an empty decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty decoder, configured by `config`.
+    pub fn with_config(config: DecoderConfig) -> Self {
+        Self {
+            buffer: Vec::new(),
+            pos: 0,
+            scanner: ValueScanner::new(config),
+            config,
+        }
+    }
+
+    /// Returns the decoder's configuration.
+    pub fn config(&self) -> &DecoderConfig {
+        &self.config
+    }
+
+    /// Appends `bytes` to the decoder's buffered input.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the number of bytes buffered, but not yet decoded.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the decoder's current read position, i.e. the total number of decoded bytes.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Decodes a `Value` from the buffered input, if it is complete.
+    ///
+    /// Returns `Resumable::Pending` if the buffered input ends before the value does,
+    /// in which case the call can be repeated after feeding more bytes.
+    /// Any other error is returned as is, with its position relative to
+    /// the start of the decoder's input.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_value_resumable(&mut self) -> Result<Resumable<Value>> {
+        let scanned = self
+            .scanner
+            .scan(&self.buffer)
+            .map_err(|err| err.with_pos_offset(self.pos))?;
+
+        let len = match scanned {
+            Scanned::Complete(len) => len,
+            Scanned::Incomplete { .. } => {
+                return Ok(Resumable::Pending {
+                    buffered: self.buffer.len(),
+                })
+            }
+        };
+
+        self.scanner = ValueScanner::new(self.config);
+
+        let mut decoder = Decoder::new(SliceReader::new(&self.buffer[..len]), self.config);
+        let value = decoder
+            .decode_value()
+            .map_err(|err| err.with_pos_offset(self.pos))?;
+
+        self.buffer.drain(..len);
+        self.pos += len;
+
+        Ok(Resumable::Complete(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::EncoderConfig,
+        encoder::Encoder,
+        error::ErrorCode,
+        header::MapHeader,
+        io::VecWriter,
+        value::{IntValue, SeqValue, StringValue},
+    };
+
+    use super::*;
+
+    fn encode(values: &[Value], config: EncoderConfig) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::new(writer, config);
+
+        for value in values {
+            encoder.encode_value(value).unwrap();
+        }
+
+        encoded
+    }
+
+    #[test]
+    fn byte_by_byte() {
+        let values = vec![
+            Value::from(StringValue::from("lilliput".to_owned())),
+            Value::from(SeqValue::from(vec![
+                Value::from(IntValue::from(1_u8)),
+                Value::from(IntValue::from(-1234_i16)),
+            ])),
+        ];
+
+        let encoded = encode(&values, EncoderConfig::default());
+
+        let mut decoder = ResumableDecoder::new();
+        let mut decoded = vec![];
+
+        for byte in &encoded {
+            decoder.feed(&[*byte]);
+
+            if let Resumable::Complete(value) = decoder.decode_value_resumable().unwrap() {
+                decoded.push(value);
+            }
+        }
+
+        assert_eq!(decoded, values);
+        assert_eq!(decoder.pos(), encoded.len());
+        assert_eq!(decoder.buffered(), 0);
+    }
+
+    #[test]
+    fn with_config() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_map_header(&MapHeader::compact(2)).unwrap();
+        for _ in 0..2 {
+            encoder.encode_str("key").unwrap();
+            encoder.encode_u8(42).unwrap();
+        }
+
+        let config = DecoderConfig::default().with_deny_duplicate_keys(true);
+        let mut decoder = ResumableDecoder::with_config(config);
+
+        for byte in &encoded[..(encoded.len() - 1)] {
+            decoder.feed(&[*byte]);
+            assert!(matches!(
+                decoder.decode_value_resumable().unwrap(),
+                Resumable::Pending { .. }
+            ));
+        }
+
+        decoder.feed(&encoded[(encoded.len() - 1)..]);
+        let err = decoder.decode_value_resumable().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DuplicateKey);
+    }
+
+    #[test]
+    fn error_pos() {
+        let mut decoder = ResumableDecoder::new();
+
+        let mut encoded = encode(
+            &[Value::from(IntValue::from(1_u8))],
+            EncoderConfig::default(),
+        );
+        decoder.feed(&encoded);
+        decoder.decode_value_resumable().unwrap();
+
+        // An extension value, whose tag has a non-integer marker:
+        encoded = vec![0b00101000, 0b00000000, 0b00000000];
+        decoder.feed(&encoded);
+        let err = decoder.decode_value_resumable().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert_eq!(err.pos(), Some(1 + 2));
+    }
+
+    proptest! {
+        #[test]
+        fn split_at_every_boundary(value in Value::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let encoded = encode(core::slice::from_ref(&value), config);
+
+            for split in 0..encoded.len() {
+                let (head, tail) = encoded.split_at(split);
+
+                let mut decoder = ResumableDecoder::new();
+
+                decoder.feed(head);
+                prop_assert_eq!(
+                    decoder.decode_value_resumable().unwrap(),
+                    Resumable::Pending { buffered: split }
+                );
+
+                decoder.feed(tail);
+                prop_assert_eq!(
+                    decoder.decode_value_resumable().unwrap(),
+                    Resumable::Complete(value.clone())
+                );
+                prop_assert_eq!(decoder.pos(), encoded.len());
+            }
+        }
+    }
+}
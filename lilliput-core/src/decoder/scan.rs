@@ -0,0 +1,310 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    config::DecoderConfig,
+    error::{ErrorCode, Result},
+    header::{Header, IntHeader},
+    io::{Read, SliceReader},
+};
+
+use super::Decoder;
+
+/// The outcome of scanning a buffer for the next value.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Scanned {
+    /// The buffer contains the complete value, occupying its first `len` bytes.
+    Complete(usize),
+    /// The buffer ends before the value does, requiring at least `needed` more bytes.
+    Incomplete { needed: usize },
+}
+
+/// Incrementally determines the encoded length of the next value
+/// at the start of a growing buffer, only looking at its headers.
+///
+/// Each scan resumes where the previous one left off, so feeding the buffer
+/// in many small chunks doesn't result in it getting re-scanned from its start,
+/// and the lengths declared by headers allow for reading their bodies in one go.
+#[derive(Clone, Debug)]
+pub(crate) struct ValueScanner {
+    config: DecoderConfig,
+    /// The length of the scanned prefix, which may exceed the buffer's length
+    /// if the buffer ends within a value's body.
+    offset: usize,
+    /// The number of values left to scan, per level of nesting.
+    remaining: Vec<usize>,
+}
+
+impl ValueScanner {
+    /// Creates a scanner for a single value, decoding its headers as configured by `config`.
+    pub(crate) fn new(config: DecoderConfig) -> Self {
+        Self::for_values(config, 1)
+    }
+
+    /// Creates a scanner for `count` consecutive values (e.g. a sequence's elements),
+    /// decoding their headers as configured by `config`.
+    pub(crate) fn for_values(config: DecoderConfig, count: usize) -> Self {
+        Self {
+            config,
+            offset: 0,
+            remaining: vec![count],
+        }
+    }
+
+    /// Scans `buf` for the end of its leading value, resuming any previous scans.
+    ///
+    /// `buf` is expected to only grow between scans, retaining any previously scanned bytes.
+    pub(crate) fn scan(&mut self, buf: &[u8]) -> Result<Scanned> {
+        loop {
+            while self.remaining.last() == Some(&0) {
+                self.remaining.pop();
+            }
+
+            if self.offset > buf.len() {
+                let needed = self.offset - buf.len();
+                return Ok(Scanned::Incomplete { needed });
+            }
+
+            let Some(remaining) = self.remaining.last_mut() else {
+                return Ok(Scanned::Complete(self.offset));
+            };
+
+            if self.offset == buf.len() {
+                return Ok(Scanned::Incomplete { needed: 1 });
+            }
+
+            let mut decoder = Decoder::new(SliceReader::new(&buf[self.offset..]), self.config);
+
+            let header = match decoder.decode_header() {
+                Ok(header) => header,
+                Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => {
+                    return Ok(Scanned::Incomplete { needed: 1 });
+                }
+                Err(err) => return Err(err.with_pos_offset(self.offset)),
+            };
+
+            *remaining -= 1;
+            self.offset += decoder.pos();
+
+            let body_len = match header {
+                Header::Int(IntHeader::Compact(_)) => 0,
+                Header::Int(IntHeader::Extended(header)) => header.width().into(),
+                Header::String(header) => header.len(),
+                Header::Seq(header) => {
+                    self.remaining.push(header.len());
+                    0
+                }
+                Header::Map(header) => {
+                    self.remaining.push(header.len().saturating_mul(2));
+                    0
+                }
+                Header::Float(header) => header.width().into(),
+                Header::Bytes(header) => header.len(),
+                Header::Bool(_) | Header::Unit(_) | Header::Null(_) => 0,
+                Header::Ext(header) => header.len(),
+            };
+
+            self.offset = self.offset.saturating_add(body_len);
+        }
+    }
+}
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    /// Skips the next `count` values (e.g. a sequence's elements) in a single jump,
+    /// returning `false` (without advancing the decoder) if unable to.
+    ///
+    /// Sequences and maps don't declare their encoded byte length, so the values' headers
+    /// still get scanned, but directly within the reader's in-memory bytes
+    /// (see `Read::remaining_slice`), jumping over their bodies,
+    /// before skipping the reader past all of them at once.
+    ///
+    /// As this bypasses any per-header bookkeeping it is only available if neither
+    /// strict mode, nor a step budget, nor a total-bytes limit, nor stats collection are enabled.
+    /// Malformed (or truncated) values get left to the regular value-by-value skipping,
+    /// for reporting their errors.
+    pub(super) fn skip_values_at_once(&mut self, count: usize) -> Result<bool> {
+        let has_bookkeeping = self.is_strict
+            || self.step_budget.is_some()
+            || self.max_total_bytes.is_some()
+            || self.config.collect_stats;
+
+        if has_bookkeeping {
+            return Ok(false);
+        }
+
+        let Some(bytes) = self.reader.remaining_slice() else {
+            return Ok(false);
+        };
+
+        let mut scanner = ValueScanner::for_values(self.config, count);
+
+        let Ok(Scanned::Complete(len)) = scanner.scan(bytes) else {
+            return Ok(false);
+        };
+
+        self.skip_bytes(len)?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::EncoderConfig,
+        encoder::Encoder,
+        io::{Reference, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    fn encode(value: &Value, config: EncoderConfig) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::new(writer, config);
+        encoder.encode_value(value).unwrap();
+        encoded
+    }
+
+    #[test]
+    fn declared_body_len() {
+        let encoded = encode(&Value::from("x".repeat(1000)), EncoderConfig::default());
+        let header_len = encoded.len() - 1000;
+
+        let mut scanner = ValueScanner::new(DecoderConfig::default());
+
+        // The string's header declares the number of missing bytes:
+        assert_eq!(
+            scanner.scan(&encoded[..(header_len + 10)]).unwrap(),
+            Scanned::Incomplete { needed: 990 }
+        );
+        assert_eq!(
+            scanner.scan(&encoded).unwrap(),
+            Scanned::Complete(encoded.len())
+        );
+    }
+
+    #[test]
+    fn skip_at_once() {
+        /// A reader recording the calls made to it.
+        struct RecordingReader<'r> {
+            reader: SliceReader<'r>,
+            calls: usize,
+            skips: Vec<usize>,
+        }
+
+        impl<'r> Read<'r> for RecordingReader<'r> {
+            fn peek_one(&mut self) -> Result<u8> {
+                self.calls += 1;
+                self.reader.peek_one()
+            }
+
+            fn peek(&mut self, len: usize) -> Result<&[u8]> {
+                self.calls += 1;
+                self.reader.peek(len)
+            }
+
+            fn skip(&mut self, len: usize) -> Result<()> {
+                self.calls += 1;
+                self.skips.push(len);
+                self.reader.skip(len)
+            }
+
+            fn read<'s>(
+                &'s mut self,
+                len: usize,
+                scratch: &'s mut Vec<u8>,
+            ) -> Result<Reference<'r, 's, [u8]>> {
+                self.calls += 1;
+                self.reader.read(len, scratch)
+            }
+
+            fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+                self.calls += 1;
+                self.reader.read_into(buf)
+            }
+
+            fn remaining(&self) -> Option<usize> {
+                self.reader.remaining()
+            }
+
+            fn remaining_slice(&self) -> Option<&[u8]> {
+                self.reader.remaining_slice()
+            }
+        }
+
+        let value: Value = [
+            (0..10_000_u32).map(Value::from).collect::<Value>(),
+            Value::from_iter([(Value::from("nested"), Value::from(vec![0_u8; 100]))]),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut encoded = encode(&value, EncoderConfig::default());
+        let len = encoded.len();
+        encoded.extend(encode(&Value::from(42_u8), EncoderConfig::default()));
+
+        let skip = |config: DecoderConfig| {
+            let reader = RecordingReader {
+                reader: SliceReader::new(&encoded),
+                calls: 0,
+                skips: Vec::new(),
+            };
+            let mut decoder = Decoder::new(reader, config);
+            decoder.skip_value().unwrap();
+            assert_eq!(decoder.pos(), len);
+            assert_eq!(decoder.decode_value().unwrap(), Value::from(42_u8));
+            decoder.into_reader()
+        };
+
+        // The outer sequence's header gets read, followed by a single jump past its elements:
+        let reader = skip(DecoderConfig::default());
+        assert_eq!(reader.skips, [len - 1]);
+        assert!(reader.calls < 10, "{}", reader.calls);
+
+        // Collecting stats requires visiting each header individually:
+        let reader = skip(DecoderConfig::default().with_collect_stats(true));
+        assert!(reader.calls > 10_000, "{}", reader.calls);
+
+        // Truncated values fail at the same position either way:
+        let truncated = &encoded[..(len - 1)];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(truncated));
+        let err = decoder.skip_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+
+        let config = DecoderConfig::default().with_collect_stats(true);
+        let mut decoder = Decoder::new(SliceReader::new(truncated), config);
+        assert_eq!(decoder.skip_value().unwrap_err().pos(), err.pos());
+    }
+
+    proptest! {
+        #[test]
+        fn scan_matches_encoded_len(value in Value::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded = encode(&value, config);
+            let len = encoded.len();
+
+            // Trailing bytes don't count towards the value's length:
+            encoded.push(0b0);
+
+            let mut scanner = ValueScanner::new(DecoderConfig::default());
+            prop_assert_eq!(scanner.scan(&encoded).unwrap(), Scanned::Complete(len));
+
+            // Resuming after every single byte:
+            let mut scanner = ValueScanner::new(DecoderConfig::default());
+            for end in 0..len {
+                let Scanned::Incomplete { needed } = scanner.scan(&encoded[..end]).unwrap() else {
+                    panic!("expected incomplete scan of {end} out of {len} bytes");
+                };
+                prop_assert!(end + needed <= len);
+            }
+            prop_assert_eq!(scanner.scan(&encoded[..len]).unwrap(), Scanned::Complete(len));
+        }
+    }
+}
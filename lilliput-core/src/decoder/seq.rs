@@ -1,9 +1,10 @@
 use crate::{
+    config::PackingMode,
     error::Result,
-    header::SeqHeader,
+    header::{LenWidth, SeqHeader},
     io::Read,
     marker::Marker,
-    value::{Seq, SeqValue},
+    value::{Seq, SeqValue, Value},
 };
 
 use super::Decoder;
@@ -22,6 +23,31 @@ where
         self.decode_seq_of(header)
     }
 
+    /// Decodes a sequence value, with its elements allocated by `alloc`.
+    ///
+    /// Only the sequence's own buffer gets allocated by `alloc`,
+    /// while its elements' contents (e.g. nested strings) use the global allocator.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"allocator_api"` feature.*
+    #[cfg(feature = "allocator_api")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_seq_in<A>(&mut self, alloc: A) -> Result<alloc::vec::Vec<Value, A>>
+    where
+        A: core::alloc::Allocator,
+    {
+        let header = self.decode_seq_header()?;
+
+        let mut seq =
+            alloc::vec::Vec::with_capacity_in(self.cautious_capacity::<Value>(header.len()), alloc);
+
+        for _ in 0..header.len() {
+            let value = self.decode_value()?;
+            seq.push(value);
+        }
+
+        Ok(seq)
+    }
+
     /// Decodes a sequence value, as a `SeqValue`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_seq_value(&mut self) -> Result<SeqValue> {
@@ -35,11 +61,13 @@ where
     /// Decodes a sequence value's header.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_seq_header(&mut self) -> Result<SeqHeader> {
+        let pos = self.pos;
+
         let byte = self.pull_byte_expecting(Marker::Seq)?;
 
         let is_compact = (byte & SeqHeader::COMPACT_VARIANT_BIT) != 0b0;
 
-        if is_compact {
+        let header = if is_compact {
             let len = byte & SeqHeader::COMPACT_LEN_BITS;
 
             #[cfg(feature = "tracing")]
@@ -49,9 +77,9 @@ where
                 len = len
             );
 
-            Ok(SeqHeader::compact(len))
+            SeqHeader::compact(len)
         } else {
-            let len_width = 1 + (byte & SeqHeader::EXTENDED_LEN_WIDTH_BITS);
+            let len_width = LenWidth::from_width_bits(byte & SeqHeader::EXTENDED_LEN_WIDTH_BITS);
             let len = self.pull_len_bytes(len_width)?;
 
             #[cfg(feature = "tracing")]
@@ -61,8 +89,15 @@ where
                 len = len
             );
 
-            Ok(SeqHeader::extended(len))
-        }
+            SeqHeader::extended(len)
+        };
+
+        self.check_canonical(pos, || {
+            let canonical = SeqHeader::for_len(header.len(), PackingMode::Optimal);
+            header == canonical && (self.pos - pos) == canonical.encoded_len(PackingMode::Optimal)
+        })?;
+
+        Ok(header)
     }
 
     // MARK: - Skip
@@ -75,6 +110,10 @@ where
             SeqHeader::Extended(header) => header.len(),
         };
 
+        if self.skip_values_at_once(len)? {
+            return Ok(());
+        }
+
         for _ in 0..len {
             self.skip_value()?; // item
         }
@@ -95,7 +134,7 @@ where
     /// Decodes sequence value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn decode_seq_of(&mut self, header: SeqHeader) -> Result<Seq> {
-        let mut seq = Seq::default();
+        let mut seq = Seq::with_capacity(self.cautious_capacity::<Value>(header.len()));
 
         for _ in 0..header.len() {
             let value = self.decode_value()?;
@@ -105,3 +144,49 @@ where
         Ok(seq)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{decoder::test::encode_with, error::ErrorCode, io::SliceReader};
+
+    use super::*;
+
+    #[test]
+    fn decode_seq_preallocates() {
+        let len = 1000;
+
+        let encoded = encode_with(|encoder| {
+            encoder
+                .encode_seq_header(&encoder.header_for_seq_len(len))
+                .unwrap();
+            for _ in 0..len {
+                encoder.encode_null().unwrap();
+            }
+        });
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let seq = decoder.decode_seq().unwrap();
+
+        assert_eq!(seq.len(), len);
+        // Pre-allocating the exact length results in a single allocation:
+        assert_eq!(seq.capacity(), len);
+    }
+
+    #[test]
+    fn decode_seq_with_huge_len() {
+        let encoded = encode_with(|encoder| {
+            encoder
+                .encode_seq_header(&SeqHeader::extended(usize::MAX))
+                .unwrap();
+        });
+
+        // Would abort due to allocation failure, if the length wasn't capped:
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.decode_seq().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+}
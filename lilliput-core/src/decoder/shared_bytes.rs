@@ -0,0 +1,79 @@
+use ::bytes::Bytes;
+
+use crate::{error::Result, io::BytesReader};
+
+use super::Decoder;
+
+impl Decoder<BytesReader> {
+    // MARK: - Value
+
+    /// Decodes a byte array value, as a `Bytes` buffer
+    /// sharing memory with the reader's underlying buffer, without copying.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"bytes"` feature.*
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_shared(&mut self) -> Result<Bytes> {
+        let header = self.decode_bytes_header()?;
+
+        let start = self.reader.pos();
+        self.skip_bytes(header.len())?;
+        let end = self.reader.pos();
+
+        Ok(self.reader.slice(start..end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{encoder::Encoder, error::ErrorCode, io::VecWriter};
+
+    use super::*;
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let data = Bytes::from_static(&[1, 2, 3, 4]);
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_bytes_from(&data).unwrap();
+        encoder.encode_bytes_from(&Bytes::new()).unwrap();
+
+        let reader = BytesReader::new(Bytes::from(encoded.clone()));
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.decode_bytes_shared().unwrap(), data);
+        assert_eq!(decoder.decode_bytes_shared().unwrap(), Bytes::new());
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn shares_allocation() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_bytes(&data).unwrap();
+
+        let source = Bytes::from(encoded);
+        let header_len = source.len() - data.len();
+
+        let reader = BytesReader::new(source.clone());
+        let mut decoder = Decoder::from_reader(reader);
+        let decoded = decoder.decode_bytes_shared().unwrap();
+
+        assert_eq!(&decoded[..], &data[..]);
+        assert_eq!(decoded.as_ptr(), source[header_len..].as_ptr());
+    }
+
+    #[test]
+    fn truncated() {
+        let reader = BytesReader::new(Bytes::from_static(&[0b00000100, 3, 1, 2]));
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.decode_bytes_shared().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(err.pos(), Some(2));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::marker::Marker;
+
+/// Statistics about the values decoded by a `Decoder`, per type.
+///
+/// Each value counts towards its own type, with its header's bytes (including lengths)
+/// and its contents' bytes (e.g. a string's UTF-8 bytes), but excluding the bytes of
+/// any values nested within it (e.g. a sequence's elements).
+/// Extension values' tags get counted as integers.
+///
+/// Skipped values get counted the same as decoded ones.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DecodeStats {
+    counts: [usize; Marker::ALL.len()],
+    bytes: [usize; Marker::ALL.len()],
+}
+
+impl DecodeStats {
+    /// Returns the number of values of type `marker`.
+    pub fn count(&self, marker: Marker) -> usize {
+        self.counts[index(marker)]
+    }
+
+    /// Returns the number of bytes of values of type `marker`.
+    pub fn bytes(&self, marker: Marker) -> usize {
+        self.bytes[index(marker)]
+    }
+
+    /// Returns the number of values, across all types.
+    pub fn total_count(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the number of bytes, across all types.
+    pub fn total_bytes(&self) -> usize {
+        self.bytes.iter().sum()
+    }
+}
+
+/// Accumulates `DecodeStats`, attributing the bytes between
+/// consecutive headers to the former header's type.
+#[derive(Default, Debug)]
+pub(super) struct StatsCollector {
+    stats: DecodeStats,
+    current: Option<(Marker, usize)>,
+}
+
+impl StatsCollector {
+    /// Records a header of type `marker`, starting at `pos`.
+    #[inline]
+    pub(super) fn record_header(&mut self, marker: Marker, pos: usize) {
+        if let Some((current, start)) = self.current {
+            self.stats.bytes[index(current)] += pos - start;
+        }
+
+        self.stats.counts[index(marker)] += 1;
+        self.current = Some((marker, pos));
+    }
+
+    /// Records the decoder leaving its current position `pos` non-sequentially (e.g. by seeking),
+    /// attributing the bytes so far to the current header's type.
+    pub(super) fn record_jump(&mut self, pos: usize) {
+        if let Some((current, start)) = self.current.take() {
+            self.stats.bytes[index(current)] += pos - start;
+        }
+    }
+
+    /// Returns the stats, as of `pos`.
+    pub(super) fn stats(&self, pos: usize) -> DecodeStats {
+        let mut stats = self.stats;
+
+        if let Some((current, start)) = self.current {
+            stats.bytes[index(current)] += pos - start;
+        }
+
+        stats
+    }
+}
+
+fn index(marker: Marker) -> usize {
+    match marker {
+        Marker::Int => 0,
+        Marker::String => 1,
+        Marker::Seq => 2,
+        Marker::Map => 3,
+        Marker::Float => 4,
+        Marker::Bytes => 5,
+        Marker::Bool => 6,
+        Marker::Unit => 7,
+        Marker::Null => 8,
+        Marker::Ext => 9,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::DecoderConfig,
+        decoder::{test::encode_with, Decoder},
+        io::SliceReader,
+        value::Value,
+    };
+
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let value = Value::from_iter([
+            (Value::from("name"), Value::from("lilliput")),
+            (
+                Value::from("tags"),
+                Value::from_iter([Value::from("a"), Value::from("b")]),
+            ),
+            (Value::from("id"), Value::from(42_u8)),
+            (Value::from("data"), Value::from(vec![1_u8, 2, 3])),
+            (Value::from("ok"), Value::from(true)),
+            (Value::from("none"), Value::from(None::<bool>)),
+        ]);
+
+        let encoded = encode_with(|encoder| encoder.encode_value(&value).unwrap());
+
+        // Stats are opt-in:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.decode_value().unwrap();
+        assert_eq!(decoder.stats(), None);
+
+        let config = DecoderConfig::default().with_collect_stats(true);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        assert_eq!(decoder.stats(), Some(DecodeStats::default()));
+        assert_eq!(decoder.decode_value().unwrap(), value);
+
+        let stats = decoder.stats().unwrap();
+
+        assert_eq!(stats.count(Marker::Map), 1);
+        assert_eq!(stats.count(Marker::Seq), 1);
+        assert_eq!(stats.count(Marker::String), 6 + 1 + 2);
+        assert_eq!(stats.count(Marker::Int), 1);
+        assert_eq!(stats.count(Marker::Bytes), 1);
+        assert_eq!(stats.count(Marker::Bool), 1);
+        assert_eq!(stats.count(Marker::Null), 1);
+        assert_eq!(stats.count(Marker::Float), 0);
+        assert_eq!(stats.total_count(), 15);
+
+        // Containers only account for their headers, but not their contents:
+        assert_eq!(stats.bytes(Marker::Map), 1);
+        assert_eq!(stats.bytes(Marker::Seq), 1);
+        assert_eq!(stats.bytes(Marker::String), (6 + 20) + (1 + 8) + (2 + 2));
+        assert_eq!(stats.bytes(Marker::Bytes), 1 + 1 + 3);
+        assert_eq!(stats.bytes(Marker::Float), 0);
+        assert_eq!(stats.total_bytes(), encoded.len());
+    }
+}
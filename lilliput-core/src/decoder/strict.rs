@@ -0,0 +1,137 @@
+use crate::{
+    error::{Error, Result},
+    io::Read,
+};
+
+use super::Decoder;
+
+impl<R> Decoder<R> {
+    /// Returns `true`, if the decoder is in strict mode, otherwise `false`.
+    pub fn is_strict(&self) -> bool {
+        self.is_strict
+    }
+
+    /// Enables (or disables) strict mode.
+    ///
+    /// In strict mode integers and lengths have to be encoded in their canonical
+    /// (i.e. smallest possible) form, as produced by an encoder using `PackingMode::Optimal`,
+    /// failing with `ErrorCode::NonCanonicalEncoding` otherwise.
+    ///
+    /// This protects against malleability, i.e. a single value having multiple valid encodings.
+    ///
+    /// The fixed-width length prefixes of framed values (see `Decoder::decode_framed`)
+    /// are exempt from this, as they are fixed-width by design, while the placeholder lengths
+    /// of deferred sequences (see `Encoder::begin_seq_deferred`) are not.
+    pub fn set_strict(&mut self, is_strict: bool) {
+        self.is_strict = is_strict;
+    }
+}
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    /// Returns an error if in strict mode and `is_canonical` is `false`,
+    /// for a value encoded at `pos`.
+    #[inline]
+    pub(super) fn check_canonical(
+        &self,
+        pos: usize,
+        is_canonical: impl FnOnce() -> bool,
+    ) -> Result<()> {
+        if self.is_strict && !is_canonical() {
+            return Err(Error::non_canonical_encoding(Some(pos)));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::{EncoderConfig, IntWidth, PackingMode},
+        decoder::test::encode_with,
+        encoder::Encoder,
+        error::ErrorCode,
+        header::{MapHeader, SeqHeader},
+        io::{SliceReader, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    #[test]
+    fn strict_rejects_non_canonical() {
+        fn encode_configured(
+            config: EncoderConfig,
+            f: impl FnOnce(&mut Encoder<VecWriter<'_>>) -> Result<()>,
+        ) -> Vec<u8> {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            f(&mut encoder).unwrap();
+            encoded
+        }
+
+        let unpacked = EncoderConfig::default().with_packing(PackingMode::None);
+
+        let cases = [
+            encode_configured(unpacked.clone(), |encoder| encoder.encode_u8(5)),
+            encode_configured(unpacked.clone(), |encoder| encoder.encode_str("foo")),
+            encode_configured(unpacked.clone(), |encoder| encoder.encode_bytes(&[1, 2])),
+            encode_configured(
+                EncoderConfig::default().with_int_width(IntWidth::Fixed64),
+                |encoder| encoder.encode_i64(-1),
+            ),
+            encode_configured(EncoderConfig::default(), |encoder| {
+                encoder.encode_seq_header(&SeqHeader::extended(2))?;
+                encoder.encode_u8(1)?;
+                encoder.encode_u8(2)
+            }),
+            encode_configured(EncoderConfig::default(), |encoder| {
+                encoder.encode_map_header(&MapHeader::extended(0))
+            }),
+        ];
+
+        for encoded in &cases {
+            let reader = SliceReader::new(encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            assert!(!decoder.is_strict());
+            assert!(decoder.decode_value().is_ok());
+
+            let reader = SliceReader::new(encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            decoder.set_strict(true);
+            let err = decoder.decode_value().unwrap_err();
+            assert_eq!(err.code(), ErrorCode::NonCanonicalEncoding);
+            assert_eq!(err.pos(), Some(0));
+        }
+
+        // Canonically encoded values pass:
+        let encoded = encode_configured(EncoderConfig::default(), |encoder| {
+            encoder.encode_ext(u64::from(u8::MAX), &[])?;
+            encoder.encode_u8(1)
+        });
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.set_strict(true);
+        decoder.decode_value().unwrap();
+        decoder.decode_value().unwrap();
+    }
+
+    proptest! {
+        #[test]
+        fn strict_accepts_canonical(value in Value::arbitrary()) {
+            let encoded = encode_with(|encoder| encoder.encode_value(&value).unwrap());
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            decoder.set_strict(true);
+            prop_assert_eq!(decoder.decode_value().unwrap(), value);
+        }
+    }
+}
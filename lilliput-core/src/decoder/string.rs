@@ -1,8 +1,10 @@
-use std::ops::Range;
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
 
 use crate::{
+    config::PackingMode,
     error::{Error, Result},
-    header::StringHeader,
+    header::{LenWidth, StringHeader},
     io::{Read, Reference},
     marker::Marker,
     value::StringValue,
@@ -26,6 +28,29 @@ where
         self.decode_str_of(header, scratch)
     }
 
+    /// Decodes a string value, as a reference,
+    /// using the decoder's internal scratch buffer (if necessary).
+    ///
+    /// The scratch buffer gets cleared (retaining its capacity) on every call.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_str_owned_scratch(&mut self) -> Result<Reference<'de, '_, str>> {
+        let header = self.decode_string_header()?;
+
+        let start = self.pos;
+        let bytes = self.pull_bytes_into_scratch(header.len())?;
+
+        let str_ref = match bytes {
+            Reference::Borrowed(bytes) => core::str::from_utf8(bytes).map(Reference::Borrowed),
+            Reference::Copied(bytes) => core::str::from_utf8(bytes).map(Reference::Copied),
+        }
+        .map_err(|err| {
+            let pos = start + err.valid_up_to() + 1;
+            Error::utf8(err, Some(pos))
+        })?;
+
+        Ok(str_ref)
+    }
+
     /// Decodes a string value's raw-bytes, as a reference.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_str_bytes<'s>(
@@ -37,12 +62,136 @@ where
     }
 
     /// Decodes a string value, as an owned string.
+    ///
+    /// If `DecoderConfig::lossy_strings` is set, then invalid UTF-8 sequences
+    /// get replaced with `U+FFFD`, rather than failing.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_string(&mut self) -> Result<String> {
         let header = self.decode_string_header()?;
         self.decode_string_of(header)
     }
 
+    /// Decodes a string value into `buf`, replacing its contents.
+    ///
+    /// The buffer gets cleared (retaining its capacity), allowing it to be reused across calls.
+    /// If decoding fails the buffer is left empty.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_string_into(&mut self, buf: &mut String) -> Result<()> {
+        let header = self.decode_string_header()?;
+
+        let mut bytes = core::mem::take(buf).into_bytes();
+
+        let range = match self.decode_string_bytes_into_of(header, &mut bytes) {
+            Ok(range) => range,
+            Err(err) => {
+                *buf = cleared_string(bytes);
+                return Err(err);
+            }
+        };
+
+        match String::from_utf8(bytes) {
+            Ok(string) => {
+                *buf = string;
+                Ok(())
+            }
+            Err(err) if self.config.lossy_strings => {
+                *buf = String::from_utf8_lossy(err.as_bytes()).into_owned();
+                Ok(())
+            }
+            Err(err) => {
+                let utf8_err = err.utf8_error();
+                let pos = range.start + utf8_err.valid_up_to() + 1;
+                *buf = cleared_string(err.into_bytes());
+                Err(Error::utf8(utf8_err, Some(pos)))
+            }
+        }
+    }
+
+    /// Decodes a string value, writing its UTF-8 bytes to `out`,
+    /// returning the number of bytes written.
+    ///
+    /// The bytes get validated and streamed to the writer in chunks, without buffering
+    /// the whole string, making this suitable for large strings.
+    /// As such the bytes preceding an invalid UTF-8 sequence may have been written already,
+    /// when failing.
+    ///
+    /// If `DecoderConfig::lossy_strings` is set, then invalid UTF-8 sequences
+    /// get replaced with `U+FFFD`, rather than failing.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"std"` feature.*
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_str_to(&mut self, out: &mut dyn std::io::Write) -> Result<usize> {
+        const REPLACEMENT: &[u8] = "\u{FFFD}".as_bytes();
+
+        let header = self.decode_string_header()?;
+        let lossy = self.config.lossy_strings;
+
+        // The bytes of a chunk's trailing incomplete UTF-8 sequence, carried over into the next:
+        let mut carry: Vec<u8> = Vec::new();
+        let mut written = 0;
+
+        let mut write = |bytes: &[u8], pos: usize| -> Result<()> {
+            written += bytes.len();
+            out.write_all(bytes)
+                .map_err(|err| Error::io(err).with_default_pos(pos))
+        };
+
+        self.pull_bytes_chunked(header.len(), |bytes, pos| {
+            let mut pos = pos - carry.len();
+            carry.extend_from_slice(bytes);
+
+            let mut valid = carry.as_slice();
+
+            loop {
+                match core::str::from_utf8(valid) {
+                    Ok(_) => {
+                        write(valid, pos)?;
+                        valid = &[];
+                        break;
+                    }
+                    Err(err) => {
+                        let (head, tail) = valid.split_at(err.valid_up_to());
+                        write(head, pos)?;
+                        pos += head.len();
+
+                        match err.error_len() {
+                            // Incomplete (but so far valid) sequence, to be completed by the next chunk:
+                            None => {
+                                valid = tail;
+                                break;
+                            }
+                            Some(len) if lossy => {
+                                write(REPLACEMENT, pos)?;
+                                pos += len;
+                                valid = &tail[len..];
+                            }
+                            Some(_) => return Err(Error::utf8(err, Some(pos + 1))),
+                        }
+                    }
+                }
+            }
+
+            let consumed = carry.len() - valid.len();
+            carry.drain(..consumed);
+
+            Ok(())
+        })?;
+
+        // Sequences still incomplete at the end of the string are invalid:
+        if let Err(err) = core::str::from_utf8(&carry) {
+            let pos = self.pos - carry.len();
+
+            if lossy {
+                write(REPLACEMENT, pos)?;
+            } else {
+                return Err(Error::utf8(err, Some(pos + 1)));
+            }
+        }
+
+        Ok(written)
+    }
+
     /// Decodes a string value's raw-bytes, as an owned buffer.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_string_bytes_buf(&mut self) -> Result<Vec<u8>> {
@@ -62,11 +211,13 @@ where
     /// Decodes a string value's header.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_string_header(&mut self) -> Result<StringHeader> {
+        let pos = self.pos;
+
         let byte = self.pull_byte_expecting(Marker::String)?;
 
         let is_compact = (byte & StringHeader::COMPACT_VARIANT_BIT) != 0b0;
 
-        if is_compact {
+        let header = if is_compact {
             let len = byte & StringHeader::COMPACT_LEN_BITS;
 
             #[cfg(feature = "tracing")]
@@ -76,9 +227,9 @@ where
                 len = len
             );
 
-            Ok(StringHeader::compact(len))
+            StringHeader::compact(len)
         } else {
-            let len_width = 1 + (byte & StringHeader::EXTENDED_LEN_WIDTH_BITS);
+            let len_width = LenWidth::from_width_bits(byte & StringHeader::EXTENDED_LEN_WIDTH_BITS);
             let len = self.pull_len_bytes(len_width)?;
 
             #[cfg(feature = "tracing")]
@@ -88,8 +239,15 @@ where
                 len = len
             );
 
-            Ok(StringHeader::extended(len))
-        }
+            StringHeader::extended(len)
+        };
+
+        self.check_canonical(pos, || {
+            let canonical = StringHeader::for_len(header.len(), PackingMode::Optimal);
+            header == canonical && (self.pos - pos) == canonical.encoded_len(PackingMode::Optimal)
+        })?;
+
+        Ok(header)
     }
 
     // MARK: - Skip
@@ -102,7 +260,7 @@ where
             StringHeader::Extended(header) => header.len(),
         };
 
-        self.reader.skip(len)
+        self.skip_bytes(len)
     }
 
     // MARK: - Body
@@ -124,8 +282,8 @@ where
         let (bytes, range) = self.decode_str_bytes_and_range_of(header, scratch)?;
 
         let str_ref = match bytes {
-            Reference::Borrowed(bytes) => std::str::from_utf8(bytes).map(Reference::Borrowed),
-            Reference::Copied(bytes) => std::str::from_utf8(bytes).map(Reference::Copied),
+            Reference::Borrowed(bytes) => core::str::from_utf8(bytes).map(Reference::Borrowed),
+            Reference::Copied(bytes) => core::str::from_utf8(bytes).map(Reference::Copied),
         }
         .map_err(|err| {
             let pos = range.start + err.valid_up_to() + 1;
@@ -148,13 +306,17 @@ where
     fn decode_string_of(&mut self, header: StringHeader) -> Result<String> {
         let (bytes_buf, range) = self.decode_string_bytes_buf_and_range_of(header)?;
 
-        let string = String::from_utf8(bytes_buf).map_err(|err| {
-            let err = err.utf8_error();
-            let pos = range.start + err.valid_up_to() + 1;
-            Error::utf8(err, Some(pos))
-        })?;
-
-        Ok(string)
+        match String::from_utf8(bytes_buf) {
+            Ok(string) => Ok(string),
+            Err(err) if self.config.lossy_strings => {
+                Ok(String::from_utf8_lossy(err.as_bytes()).into_owned())
+            }
+            Err(err) => {
+                let err = err.utf8_error();
+                let pos = range.start + err.valid_up_to() + 1;
+                Err(Error::utf8(err, Some(pos)))
+            }
+        }
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
@@ -169,7 +331,18 @@ where
     ) -> Result<(Vec<u8>, Range<usize>)> {
         let mut buf = Vec::new();
 
-        let (bytes, range) = self.decode_str_bytes_and_range_of(header, &mut buf)?;
+        let range = self.decode_string_bytes_into_of(header, &mut buf)?;
+
+        Ok((buf, range))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn decode_string_bytes_into_of(
+        &mut self,
+        header: StringHeader,
+        buf: &mut Vec<u8>,
+    ) -> Result<Range<usize>> {
+        let (bytes, range) = self.decode_str_bytes_and_range_of(header, buf)?;
 
         match bytes {
             Reference::Borrowed(slice) => {
@@ -181,7 +354,7 @@ where
             }
         }
 
-        Ok((buf, range))
+        Ok(range)
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
@@ -199,3 +372,183 @@ where
         Ok((bytes, range))
     }
 }
+
+/// Returns an empty string, reusing the allocation of `bytes`.
+fn cleared_string(mut bytes: Vec<u8>) -> String {
+    bytes.clear();
+
+    // Empty buffers are always valid UTF-8:
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::DecoderConfig,
+        decoder::{test::encode_with, STREAMING_CHUNK_LEN},
+        error::ErrorCode,
+        header::StringHeader,
+        io::{SliceReader, StdIoReader},
+        value::Value,
+    };
+
+    use super::*;
+
+    #[test]
+    fn decode_string_into() {
+        let mut encoded = encode_with(|encoder| {
+            for len in [100, 50, 10, 0] {
+                encoder.encode_str(&"x".repeat(len)).unwrap();
+            }
+            encoder
+                .encode_string_header(&StringHeader::compact(1))
+                .unwrap();
+        });
+        encoded.push(0xff);
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let mut buf = String::new();
+
+        decoder.decode_string_into(&mut buf).unwrap();
+        assert_eq!(buf, "x".repeat(100));
+
+        let capacity = buf.capacity();
+        let ptr = buf.as_ptr();
+
+        for len in [50, 10, 0] {
+            decoder.decode_string_into(&mut buf).unwrap();
+            assert_eq!(buf, "x".repeat(len));
+            assert_eq!(buf.capacity(), capacity);
+            assert_eq!(buf.as_ptr(), ptr);
+        }
+
+        // Invalid UTF-8 leaves the buffer empty, retaining its capacity:
+        let err = decoder.decode_string_into(&mut buf).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Utf8);
+        assert_eq!(buf, "");
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    #[test]
+    fn lossy_strings() {
+        let mut encoded = encode_with(|encoder| {
+            encoder.encode_str("lilliput").unwrap();
+            encoder
+                .encode_string_header(&StringHeader::compact(4))
+                .unwrap();
+        });
+        encoded.extend_from_slice(b"a\xffb\xfe");
+
+        // Valid strings decode the same in either mode:
+        for lossy_strings in [false, true] {
+            let config = DecoderConfig::default().with_lossy_strings(lossy_strings);
+            let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+            assert_eq!(decoder.decode_string().unwrap(), "lilliput");
+        }
+
+        // Strict mode (the default) rejects invalid UTF-8:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.decode_string().unwrap();
+        let err = decoder.decode_string().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Utf8);
+
+        // Lossy mode replaces invalid sequences:
+        let config = DecoderConfig::default().with_lossy_strings(true);
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        decoder.decode_string().unwrap();
+        assert_eq!(decoder.decode_string().unwrap(), "a\u{fffd}b\u{fffd}");
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        decoder.decode_value().unwrap();
+        assert_eq!(
+            decoder.decode_value().unwrap(),
+            Value::String("a\u{fffd}b\u{fffd}".to_owned().into())
+        );
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let mut buf = String::new();
+        decoder.decode_string_into(&mut buf).unwrap();
+        decoder.decode_string_into(&mut buf).unwrap();
+        assert_eq!(buf, "a\u{fffd}b\u{fffd}");
+    }
+
+    #[test]
+    fn decode_str_to() {
+        fn encode_raw_str(bytes: &[u8]) -> Vec<u8> {
+            let mut encoded = encode_with(|encoder| {
+                encoder
+                    .encode_string_header(&encoder.header_for_str_len(bytes.len()))
+                    .unwrap();
+            });
+            encoded.extend_from_slice(bytes);
+            encoded
+        }
+
+        // Multi-byte characters straddling chunk boundaries:
+        let string = "lilliput \u{1f41c} ä".repeat(20_000);
+        assert!(string.len() > 2 * STREAMING_CHUNK_LEN);
+
+        for bytes in [string.as_bytes(), b"", b"a\xffb\xfe", b"ab\xf0\x9f\x90"] {
+            let encoded = encode_raw_str(bytes);
+            let expected = core::str::from_utf8(bytes);
+
+            for lossy_strings in [false, true] {
+                let config = DecoderConfig::default().with_lossy_strings(lossy_strings);
+
+                let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+                let mut out: Vec<u8> = Vec::new();
+                let result = decoder.decode_str_to(&mut out);
+
+                match (expected, lossy_strings) {
+                    (Ok(expected), _) => {
+                        assert_eq!(result.unwrap(), expected.len());
+                        assert_eq!(out, expected.as_bytes());
+                    }
+                    (Err(_), true) => {
+                        let expected = String::from_utf8_lossy(bytes);
+                        assert_eq!(result.unwrap(), expected.len());
+                        assert_eq!(out, expected.as_bytes());
+                    }
+                    (Err(_), false) => {
+                        assert_eq!(result.unwrap_err().code(), ErrorCode::Utf8);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decode_str_owned_scratch() {
+        let values: Vec<String> = vec!["lilliput".repeat(8), "foo".to_owned(), "".to_owned()];
+
+        let encoded = encode_with(|encoder| {
+            for value in &values {
+                encoder.encode_str(value).unwrap();
+            }
+        });
+
+        let reader = StdIoReader::new(encoded.as_slice());
+        let mut decoder = Decoder::from_reader(reader);
+
+        let decoded = decoder.decode_str_owned_scratch().unwrap();
+        assert_eq!(&*decoded, values[0].as_str());
+
+        // After warm-up the scratch buffer should no longer get reallocated:
+        let ptr = decoder.scratch.as_ptr();
+        let capacity = decoder.scratch.capacity();
+
+        for value in &values[1..] {
+            let decoded = decoder.decode_str_owned_scratch().unwrap();
+            assert_eq!(&*decoded, value.as_str());
+
+            assert_eq!(decoder.scratch.as_ptr(), ptr);
+            assert_eq!(decoder.scratch.capacity(), capacity);
+        }
+
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+}
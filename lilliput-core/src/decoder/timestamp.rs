@@ -0,0 +1,223 @@
+use alloc::string::ToString as _;
+
+use crate::{
+    error::{Error, Result},
+    io::Read,
+    marker::Marker,
+    value::ExtValue,
+};
+
+use super::Decoder;
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a `chrono::DateTime<Utc>` value, from either a `[seconds, nanoseconds]`
+    /// sequence, or an extension value tagged with [`ExtValue::TIMESTAMP_TAG`].
+    ///
+    /// Leap seconds (i.e. nanoseconds of `1_000_000_000` or more) are supported,
+    /// matching chrono's representation.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"chrono"` feature.*
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_datetime(&mut self) -> Result<chrono::DateTime<chrono::Utc>> {
+        let pos = self.pos;
+
+        let (secs, nanos) = self.decode_timestamp()?;
+
+        chrono::DateTime::from_timestamp(secs, nanos)
+            .ok_or_else(|| Error::number_out_of_range(Some(pos)))
+    }
+
+    /// Decodes a `time::OffsetDateTime` value (in UTC), from either a `[seconds, nanoseconds]`
+    /// sequence, or an extension value tagged with [`ExtValue::TIMESTAMP_TAG`].
+    ///
+    /// As `time` has no representation for leap seconds, nanoseconds of `1_000_000_000`
+    /// or more carry over into the following second.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"time"` feature.*
+    #[cfg(feature = "time")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_offset_datetime(&mut self) -> Result<time::OffsetDateTime> {
+        let pos = self.pos;
+
+        let (secs, nanos) = self.decode_timestamp()?;
+        let timestamp_nanos = i128::from(secs) * 1_000_000_000 + i128::from(nanos);
+
+        time::OffsetDateTime::from_unix_timestamp_nanos(timestamp_nanos)
+            .map_err(|_| Error::number_out_of_range(Some(pos)))
+    }
+
+    // MARK: - Private
+
+    fn decode_timestamp(&mut self) -> Result<(i64, u32)> {
+        let pos = self.pos;
+
+        match self.peek_marker()? {
+            Marker::Seq => {
+                let header = self.decode_seq_header()?;
+
+                if header.len() != 2 {
+                    return Err(Error::invalid_length(
+                        header.len().to_string(),
+                        "2".to_string(),
+                        Some(pos),
+                    ));
+                }
+
+                let secs = self.decode_i64()?;
+                let nanos = self.decode_u32()?;
+
+                Ok((secs, nanos))
+            }
+            Marker::Ext => {
+                let header = self.decode_ext_header()?;
+
+                if header.tag() != ExtValue::TIMESTAMP_TAG {
+                    return Err(Error::invalid_value(
+                        header.tag().to_string(),
+                        ExtValue::TIMESTAMP_TAG.to_string(),
+                        Some(pos),
+                    ));
+                }
+
+                if header.len() != ExtValue::TIMESTAMP_LEN {
+                    return Err(Error::invalid_length(
+                        header.len().to_string(),
+                        ExtValue::TIMESTAMP_LEN.to_string(),
+                        Some(pos),
+                    ));
+                }
+
+                let mut secs_bytes = [0b0; 8];
+                self.pull_bytes_into(&mut secs_bytes)?;
+
+                let mut nanos_bytes = [0b0; 4];
+                self.pull_bytes_into(&mut nanos_bytes)?;
+
+                Ok((
+                    i64::from_be_bytes(secs_bytes),
+                    u32::from_be_bytes(nanos_bytes),
+                ))
+            }
+            marker => Err(Error::invalid_type(
+                marker.to_string(),
+                "sequence or extension".to_string(),
+                Some(pos),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{decoder::test::encode_with, error::ErrorCode, header::SeqHeader, io::SliceReader};
+
+    use super::*;
+
+    /// Timestamps around the unix epoch, as `(seconds, nanoseconds)`.
+    const TIMESTAMPS: [(i64, u32); 5] =
+        [(-1, 0), (-1, 999_999_999), (0, 0), (0, 1), (1, 500_000_000)];
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_roundtrip() {
+        for (secs, nanos) in TIMESTAMPS {
+            let datetime = chrono::DateTime::from_timestamp(secs, nanos).unwrap();
+
+            let encoded = encode_with(|encoder| encoder.encode_datetime(&datetime).unwrap());
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            assert_eq!(decoder.decode_datetime().unwrap(), datetime);
+
+            let encoded = encode_with(|encoder| encoder.encode_datetime_ext(&datetime).unwrap());
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            assert_eq!(decoder.decode_datetime().unwrap(), datetime);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_leap_second() {
+        // 2016-12-31T23:59:60.5Z
+        let datetime = chrono::DateTime::from_timestamp(1_483_228_799, 1_500_000_000).unwrap();
+
+        let encoded = encode_with(|encoder| encoder.encode_datetime(&datetime).unwrap());
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let decoded = decoder.decode_datetime().unwrap();
+        assert_eq!(decoded, datetime);
+        assert_eq!(decoded.timestamp_subsec_nanos(), 1_500_000_000);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_roundtrip() {
+        for (secs, nanos) in TIMESTAMPS {
+            let timestamp_nanos = i128::from(secs) * 1_000_000_000 + i128::from(nanos);
+            let datetime =
+                time::OffsetDateTime::from_unix_timestamp_nanos(timestamp_nanos).unwrap();
+
+            let encoded = encode_with(|encoder| encoder.encode_offset_datetime(&datetime).unwrap());
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            assert_eq!(decoder.decode_offset_datetime().unwrap(), datetime);
+
+            let encoded =
+                encode_with(|encoder| encoder.encode_offset_datetime_ext(&datetime).unwrap());
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            assert_eq!(decoder.decode_offset_datetime().unwrap(), datetime);
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_leap_second() {
+        let encoded = encode_with(|encoder| {
+            encoder.encode_seq_header(&SeqHeader::compact(2)).unwrap();
+            encoder.encode_i64(1_483_228_799).unwrap();
+            encoder.encode_u32(1_500_000_000).unwrap();
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let decoded = decoder.decode_offset_datetime().unwrap();
+        assert_eq!(decoded.unix_timestamp(), 1_483_228_800);
+        assert_eq!(decoded.nanosecond(), 500_000_000);
+    }
+
+    #[test]
+    fn decode_invalid_tag() {
+        let encoded = encode_with(|encoder| encoder.encode_ext(42, &[0b0; 12]).unwrap());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error = decoder.decode_timestamp().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidValue);
+    }
+
+    #[test]
+    fn decode_invalid_len() {
+        let encoded = encode_with(|encoder| {
+            encoder.encode_seq_header(&SeqHeader::compact(3)).unwrap();
+            encoder.encode_i64(0).unwrap();
+            encoder.encode_u32(0).unwrap();
+            encoder.encode_u32(0).unwrap();
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error = decoder.decode_timestamp().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidLength);
+
+        let encoded = encode_with(|encoder| {
+            encoder
+                .encode_ext(ExtValue::TIMESTAMP_TAG, &[0b0; 8])
+                .unwrap();
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error = decoder.decode_timestamp().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidLength);
+    }
+}
@@ -0,0 +1,338 @@
+use ::tokio::io::{AsyncRead, AsyncReadExt as _};
+
+use crate::{
+    config::DecoderConfig,
+    error::{Error, ErrorCode, Result},
+    header::Header,
+    io::SliceReader,
+    value::Value,
+};
+
+use super::{
+    scan::{Scanned, ValueScanner},
+    Decoder,
+};
+
+/// An asynchronous decoder for decoding lilliput-encoded values from a `tokio::io::AsyncRead`.
+///
+/// Bytes get read from the `reader` into an internal buffer, from which
+/// values then get decoded by a regular (synchronous) `Decoder`,
+/// ensuring both code-paths share the exact same decoding logic.
+///
+/// The lengths declared by a value's headers determine how many bytes to read,
+/// so each value gets buffered in its entirety before getting decoded exactly once.
+/// Values exceeding `AsyncDecoder::max_buffered_bytes` are rejected before being read.
+///
+/// *This type is only available if lilliput_core is built with the `"tokio"` feature.*
+#[derive(Debug)]
+pub struct AsyncDecoder<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+    max_buffered_bytes: usize,
+    config: DecoderConfig,
+}
+
+impl<R> AsyncDecoder<R> {
+    /// The default maximum number of bytes a single value is allowed to span (16 MiB).
+    pub const DEFAULT_MAX_BUFFERED_BYTES: usize = 16 * 1024 * 1024;
+
+    /// Creates a decoder from a `reader`.
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(reader, DecoderConfig::default())
+    }
+
+    /// Creates a decoder from a `reader`, configured by `config`.
+    pub fn new(reader: R, config: DecoderConfig) -> Self {
+        AsyncDecoder {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+            max_buffered_bytes: Self::DEFAULT_MAX_BUFFERED_BYTES,
+            config,
+        }
+    }
+
+    /// Returns the decoder's configuration.
+    pub fn config(&self) -> &DecoderConfig {
+        &self.config
+    }
+
+    /// Returns the decoder's internal `reader`, consuming `self`.
+    ///
+    /// Any bytes that have already been read into the internal buffer,
+    /// but not yet been decoded, get discarded.
+    pub fn into_reader(self) -> R {
+        self.reader
+    }
+
+    /// Returns the decoder's current read position.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the maximum number of bytes the decoder is allowed to buffer.
+    pub fn max_buffered_bytes(&self) -> usize {
+        self.max_buffered_bytes
+    }
+
+    /// Limits the number of bytes the decoder is allowed to buffer to `max_buffered_bytes`.
+    ///
+    /// Decoding (or skipping) a value spanning more bytes than that fails with
+    /// `ErrorCode::LengthLimitExceeded`, before reading or allocating for them.
+    pub fn set_max_buffered_bytes(&mut self, max_buffered_bytes: usize) {
+        self.max_buffered_bytes = max_buffered_bytes;
+    }
+}
+
+impl<R> AsyncDecoder<R>
+where
+    R: AsyncRead + Unpin,
+{
+    // MARK: - Value
+
+    /// Decodes a `Value`.
+    pub async fn decode_value(&mut self) -> Result<Value> {
+        let len = self.buffer_value().await?;
+
+        let mut decoder = Decoder::new(SliceReader::new(&self.buf[..len]), self.config);
+        let value = decoder
+            .decode_value()
+            .map_err(|err| err.with_pos_offset(self.pos))?;
+
+        self.consume(len);
+
+        Ok(value)
+    }
+
+    // MARK: - Header
+
+    /// Decodes a value's `Header`.
+    pub async fn decode_header(&mut self) -> Result<Header> {
+        loop {
+            let mut decoder = Decoder::new(SliceReader::new(&self.buf), self.config);
+
+            match decoder.decode_header() {
+                Ok(header) => {
+                    let len = decoder.pos();
+                    self.consume(len);
+
+                    return Ok(header);
+                }
+                Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => {
+                    // Headers span at most a handful of bytes, so re-decoding them is cheap:
+                    self.fill_buf(1).await?;
+                }
+                Err(err) => return Err(err.with_pos_offset(self.pos)),
+            }
+        }
+    }
+
+    // MARK: - Skip
+
+    /// Skips the next to-be-decoded value.
+    pub async fn skip_value(&mut self) -> Result<()> {
+        let len = self.buffer_value().await?;
+
+        self.consume(len);
+
+        Ok(())
+    }
+}
+
+// MARK: - Auxiliary Methods
+
+impl<R> AsyncDecoder<R> {
+    /// Discards the first `len` buffered bytes, advancing the read position past them.
+    fn consume(&mut self, len: usize) {
+        self.buf.drain(..len);
+        self.pos += len;
+    }
+}
+
+impl<R> AsyncDecoder<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Reads bytes into the buffer until it contains the next value in its entirety,
+    /// returning the value's encoded length.
+    async fn buffer_value(&mut self) -> Result<usize> {
+        let mut scanner = ValueScanner::new(self.config);
+
+        loop {
+            let scanned = scanner
+                .scan(&self.buf)
+                .map_err(|err| err.with_pos_offset(self.pos))?;
+
+            // All buffered bytes of an incomplete value belong to it,
+            // while reads may overshoot the end of a complete one:
+            match scanned {
+                Scanned::Complete(len) if len <= self.max_buffered_bytes => return Ok(len),
+                Scanned::Incomplete { needed }
+                    if self.buf.len().saturating_add(needed) <= self.max_buffered_bytes =>
+                {
+                    self.fill_buf(needed).await?;
+                }
+                _ => return Err(Error::length_limit_exceeded(Some(self.pos))),
+            }
+        }
+    }
+
+    /// Reads at least `needed` more bytes from the reader into the buffer.
+    async fn fill_buf(&mut self, needed: usize) -> Result<()> {
+        // Copied from the default buffer length of `std::io::BufReader`:
+        const MIN_CHUNK_LENGTH: usize = 8192;
+
+        let target = self.buf.len() + needed;
+
+        self.buf.reserve(needed.max(MIN_CHUNK_LENGTH));
+
+        while self.buf.len() < target {
+            let read = self
+                .reader
+                .read_buf(&mut self.buf)
+                .await
+                .map_err(Error::io)?;
+
+            if read == 0 {
+                return Err(Error::end_of_file());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// MARK: - Tests
+
+#[cfg(test)]
+mod tests {
+    use ::tokio::io::AsyncWriteExt as _;
+
+    use crate::{
+        encoder::Encoder,
+        header::{MapHeader, StringHeader},
+        io::VecWriter,
+        value::{IntValue, Map, MapValue, SeqValue, StringValue},
+    };
+
+    use super::*;
+
+    fn encode(values: &[Value]) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+
+        for value in values {
+            encoder.encode_value(value).unwrap();
+        }
+
+        encoded
+    }
+
+    fn value() -> Value {
+        let mut map = Map::default();
+        map.insert(
+            Value::String(StringValue::from("key".repeat(100))),
+            Value::Int(IntValue::from(u64::MAX)),
+        );
+
+        Value::Seq(SeqValue::from(vec![
+            Value::Map(MapValue::from(map)),
+            Value::Int(IntValue::from(-42_i32)),
+        ]))
+    }
+
+    #[::tokio::test]
+    async fn decode_value_from_chunked_stream() {
+        let values = vec![value(), value()];
+        let encoded = encode(&values);
+
+        // A buffer size of a single byte forces reads smaller than most headers:
+        let (mut writer, reader) = ::tokio::io::duplex(1);
+
+        let write = ::tokio::spawn({
+            let encoded = encoded.clone();
+            async move {
+                writer.write_all(&encoded).await.unwrap();
+            }
+        });
+
+        let mut decoder = AsyncDecoder::from_reader(reader);
+
+        for value in &values {
+            let decoded = decoder.decode_value().await.unwrap();
+            assert_eq!(&decoded, value);
+        }
+
+        assert_eq!(decoder.pos(), encoded.len());
+
+        write.await.unwrap();
+
+        let error_code = decoder.decode_value().await.unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[::tokio::test]
+    async fn decode_value_from_truncated_stream() {
+        let encoded = encode(&[value()]);
+        let truncated = &encoded[..(encoded.len() - 1)];
+
+        let mut decoder = AsyncDecoder::from_reader(truncated);
+
+        let error_code = decoder.decode_value().await.unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(decoder.pos(), 0);
+    }
+
+    #[::tokio::test]
+    async fn max_buffered_bytes() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder
+            .encode_string_header(&StringHeader::extended(usize::MAX / 2))
+            .unwrap();
+
+        // Only the header is available, but its declared length exceeds the limit:
+        let mut decoder = AsyncDecoder::from_reader(&encoded[..]);
+        assert_eq!(
+            decoder.max_buffered_bytes(),
+            AsyncDecoder::<&[u8]>::DEFAULT_MAX_BUFFERED_BYTES
+        );
+
+        let err = decoder.decode_value().await.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LengthLimitExceeded);
+        assert_eq!(err.pos(), Some(0));
+
+        let encoded = encode(&[value()]);
+
+        let mut decoder = AsyncDecoder::from_reader(&encoded[..]);
+        decoder.set_max_buffered_bytes(encoded.len() - 1);
+        let err = decoder.skip_value().await.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LengthLimitExceeded);
+
+        let mut decoder = AsyncDecoder::from_reader(&encoded[..]);
+        decoder.set_max_buffered_bytes(encoded.len());
+        decoder.skip_value().await.unwrap();
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[::tokio::test]
+    async fn decode_value_with_config() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_map_header(&MapHeader::compact(2)).unwrap();
+        for _ in 0..2 {
+            encoder.encode_str("key").unwrap();
+            encoder.encode_u8(42).unwrap();
+        }
+
+        let config = DecoderConfig::default().with_deny_duplicate_keys(true);
+        let mut decoder = AsyncDecoder::new(&encoded[..], config);
+
+        let error_code = decoder.decode_value().await.unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::DuplicateKey);
+    }
+}
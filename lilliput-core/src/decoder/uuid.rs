@@ -0,0 +1,72 @@
+use ::uuid::Uuid;
+
+use crate::{error::Result, io::Read};
+
+use super::Decoder;
+
+/// The byte-length of an encoded UUID value.
+const UUID_LEN: usize = 16;
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a UUID value, from a 16-byte byte array value.
+    ///
+    /// Returns an error if the byte array's length is not exactly 16 bytes.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"uuid"` feature.*
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_uuid(&mut self) -> Result<Uuid> {
+        self.decode_bytes_array::<UUID_LEN>().map(Uuid::from_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{SliceReader, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_uuid(&uuid).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.decode_uuid().unwrap(), uuid);
+        assert_eq!(decoder.pos(), encoded.len());
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.decode_value().unwrap(), Value::from(uuid));
+    }
+
+    #[test]
+    fn decode_invalid_len() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_bytes(&[0xab; 15]).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let error = decoder.decode_uuid().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidLength);
+        assert_eq!(error.pos(), Some(0));
+    }
+}
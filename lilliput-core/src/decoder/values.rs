@@ -0,0 +1,186 @@
+use crate::{
+    error::{ErrorCode, Result},
+    value::Value,
+};
+
+use super::{Decoder, Read};
+
+impl<'r, R> Decoder<R>
+where
+    R: Read<'r>,
+{
+    /// Returns an iterator over the top-level values, consuming `self`.
+    ///
+    /// The iterator stops at a clean end-of-stream, or after yielding the first error.
+    pub fn into_values(self) -> ValuesIter<R> {
+        ValuesIter {
+            decoder: self,
+            is_done: false,
+        }
+    }
+
+    /// Returns an iterator over the top-level values.
+    ///
+    /// The iterator stops at a clean end-of-stream, or after yielding the first error.
+    pub fn values(&mut self) -> Values<'_, R> {
+        Values {
+            decoder: self,
+            is_done: false,
+        }
+    }
+
+    /// Decodes the next top-level value, returning `None` on a clean end-of-stream.
+    fn decode_next_value(&mut self, is_done: &mut bool) -> Option<Result<Value>> {
+        if *is_done {
+            return None;
+        }
+
+        // Running out of bytes before a value's first byte is a clean end-of-stream,
+        // while running out of bytes anywhere after it means the value was truncated:
+        if let Err(err) = self.peek_byte() {
+            *is_done = true;
+
+            if err.code() == ErrorCode::UnexpectedEndOfFile {
+                return None;
+            }
+
+            return Some(Err(err));
+        }
+
+        let result = self.decode_value();
+        *is_done = result.is_err();
+
+        Some(result)
+    }
+}
+
+/// An iterator over the top-level values of a decoder.
+///
+/// This struct is created by [`Decoder::into_values`].
+#[derive(Debug)]
+pub struct ValuesIter<R> {
+    decoder: Decoder<R>,
+    is_done: bool,
+}
+
+impl<R> ValuesIter<R> {
+    /// Returns the iterator's internal decoder, consuming `self`.
+    pub fn into_decoder(self) -> Decoder<R> {
+        self.decoder
+    }
+}
+
+impl<'r, R> Iterator for ValuesIter<R>
+where
+    R: Read<'r>,
+{
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.decode_next_value(&mut self.is_done)
+    }
+}
+
+/// A borrowing iterator over the top-level values of a decoder.
+///
+/// This struct is created by [`Decoder::values`].
+#[derive(Debug)]
+pub struct Values<'a, R> {
+    decoder: &'a mut Decoder<R>,
+    is_done: bool,
+}
+
+impl<'r, R> Iterator for Values<'_, R>
+where
+    R: Read<'r>,
+{
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.decode_next_value(&mut self.is_done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        encoder::Encoder,
+        io::{SliceReader, StdIoReader, VecWriter},
+        value::{BoolValue, IntValue, StringValue},
+    };
+
+    use super::*;
+
+    fn values() -> Vec<Value> {
+        vec![
+            Value::Int(IntValue::from(42_u8)),
+            Value::String(StringValue::from("lilliput".to_owned())),
+            Value::Bool(BoolValue::from(true)),
+        ]
+    }
+
+    fn encode(values: &[Value]) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+
+        for value in values {
+            encoder.encode_value(value).unwrap();
+        }
+
+        encoded
+    }
+
+    #[test]
+    fn into_values() {
+        let values = values();
+        let encoded = encode(&values);
+
+        let reader = SliceReader::new(&encoded);
+        let decoder = Decoder::from_reader(reader);
+
+        let decoded: Vec<Value> = decoder.into_values().collect::<Result<_>>().unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn values_from_std_io() {
+        let values = values();
+        let encoded = encode(&values);
+
+        let reader = StdIoReader::new(encoded.as_slice());
+        let mut decoder = Decoder::from_reader(reader);
+
+        let decoded: Vec<Value> = decoder.values().collect::<Result<_>>().unwrap();
+        assert_eq!(decoded, values);
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn values_empty() {
+        let reader = SliceReader::new(&[]);
+        let mut decoder = Decoder::from_reader(reader);
+
+        assert!(decoder.values().next().is_none());
+    }
+
+    #[test]
+    fn values_truncated() {
+        let values = values();
+        let encoded = encode(&values);
+        let truncated = &encoded[..(encoded.len() - 2)];
+
+        let reader = SliceReader::new(truncated);
+        let mut decoder = Decoder::from_reader(reader);
+        let mut iter = decoder.values();
+
+        assert_eq!(iter.next().unwrap().unwrap(), values[0]);
+
+        let error_code = iter.next().unwrap().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+
+        assert!(iter.next().is_none());
+    }
+}
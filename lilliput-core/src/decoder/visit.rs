@@ -0,0 +1,219 @@
+use crate::{
+    error::{Error, Result},
+    header::Header,
+    io::Read,
+    value::Value,
+};
+
+use super::{Decoder, MAX_TRAVERSAL_DEPTH};
+
+/// A visitor's choice of how to proceed with a visited value.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Visit {
+    /// Descend into the value, i.e. visit a sequence's or map's contents,
+    /// or decode any other value and pass it to `Visitor::visit_value`.
+    Descend,
+    /// Skip the value's contents.
+    Skip,
+}
+
+/// A visitor for traversing encoded values, as driven by `Decoder::visit`.
+pub trait Visitor {
+    /// Visits a value's `header`, returning whether to descend into the value, or to skip it.
+    fn visit_header(&mut self, header: &Header) -> Visit;
+
+    /// Visits a decoded (non-sequence, non-map) value, after descending into it.
+    fn visit_value(&mut self, value: Value) {
+        let _ = value;
+    }
+}
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Visit
+
+    /// Traverses the next value, calling back to `visitor` for every value's header
+    /// (including the ones of sequence items and map keys/values).
+    ///
+    /// For each value the visitor chooses (see `Visit`) whether to descend into it,
+    /// or to skip it, without allocating any `Value`s for skipped values.
+    ///
+    /// Returns a depth-limit error for values nested deeper than 128 levels.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn visit<V>(&mut self, visitor: &mut V) -> Result<()>
+    where
+        V: Visitor,
+    {
+        self.visit_with_depth(visitor, MAX_TRAVERSAL_DEPTH)
+    }
+
+    // MARK: - Private
+
+    fn visit_with_depth<V>(&mut self, visitor: &mut V, remaining_depth: usize) -> Result<()>
+    where
+        V: Visitor,
+    {
+        let pos = self.pos;
+
+        let header = self.decode_header()?;
+
+        if visitor.visit_header(&header) == Visit::Skip {
+            return self.skip_value_of(header);
+        }
+
+        match header {
+            Header::Seq(header) => {
+                let remaining_depth = remaining_depth
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::depth_limit_exceeded(Some(pos)))?;
+
+                for _ in 0..header.len() {
+                    self.visit_with_depth(visitor, remaining_depth)?; // item
+                }
+
+                Ok(())
+            }
+            Header::Map(header) => {
+                let remaining_depth = remaining_depth
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::depth_limit_exceeded(Some(pos)))?;
+
+                for _ in 0..header.len() {
+                    self.visit_with_depth(visitor, remaining_depth)?; // key
+                    self.visit_with_depth(visitor, remaining_depth)?; // value
+                }
+
+                Ok(())
+            }
+            header => {
+                let value = self.decode_value_of(header)?;
+                visitor.visit_value(value);
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{SliceReader, VecWriter},
+    };
+
+    use super::*;
+
+    /// Counts maps, skipping byte arrays, while collecting all other (non-container) values.
+    #[derive(Default)]
+    struct MapCounter {
+        maps: usize,
+        skipped_bytes: usize,
+        values: Vec<Value>,
+    }
+
+    impl Visitor for MapCounter {
+        fn visit_header(&mut self, header: &Header) -> Visit {
+            match header {
+                Header::Map(_) => {
+                    self.maps += 1;
+                    Visit::Descend
+                }
+                Header::Bytes(_) => {
+                    self.skipped_bytes += 1;
+                    Visit::Skip
+                }
+                _ => Visit::Descend,
+            }
+        }
+
+        fn visit_value(&mut self, value: Value) {
+            self.values.push(value);
+        }
+    }
+
+    fn encode(value: &Value) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_value(value).unwrap();
+        encoded
+    }
+
+    #[test]
+    fn count_maps_and_skip_bytes() {
+        let inner: Value = [(Value::from("bytes"), Value::from(vec![1_u8, 2, 3]))]
+            .into_iter()
+            .collect();
+        let value: Value = [
+            (Value::from("a"), inner.clone()),
+            (
+                Value::from("b"),
+                [inner, Value::from(vec![4_u8]), Value::from(42_u8)]
+                    .into_iter()
+                    .collect(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let encoded = encode(&value);
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+
+        let mut visitor = MapCounter::default();
+        decoder.visit(&mut visitor).unwrap();
+
+        assert_eq!(decoder.pos(), encoded.len());
+        assert_eq!(visitor.maps, 3);
+        assert_eq!(visitor.skipped_bytes, 3);
+        assert_eq!(
+            visitor.values,
+            vec![
+                Value::from("a"),
+                Value::from("bytes"),
+                Value::from("b"),
+                Value::from("bytes"),
+                Value::from(42_u8),
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_container() {
+        struct SkipAll;
+
+        impl Visitor for SkipAll {
+            fn visit_header(&mut self, _header: &Header) -> Visit {
+                Visit::Skip
+            }
+        }
+
+        let value: Value = (0..10_u8).map(Value::from).collect();
+        let encoded = encode(&value);
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.visit(&mut SkipAll).unwrap();
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn depth_limit() {
+        let mut value = Value::from(1_u8);
+        for _ in 0..=MAX_TRAVERSAL_DEPTH {
+            value = [value].into_iter().collect();
+        }
+        let encoded = encode(&value);
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let err = decoder.visit(&mut MapCounter::default()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+    }
+}
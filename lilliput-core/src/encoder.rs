@@ -1,22 +1,45 @@
 //! Encoders for encoding lilliput values.
 
-use crate::{config::EncoderConfig, error::Result, header::Header, io::Write, value::Value};
+use alloc::string::ToString;
+
+use crate::{
+    config::EncoderConfig,
+    decoder::Decoder,
+    error::{Error, Result},
+    header::Header,
+    io::{SliceReader, Write},
+    value::Value,
+};
 
 mod bool;
 mod bytes;
+mod duration;
+mod ext;
 mod float;
+mod framed;
 mod int;
 mod map;
 mod null;
 mod seq;
 mod string;
+mod transcode;
 mod unit;
 
+#[cfg(feature = "bytes")]
+mod shared_bytes;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod timestamp;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+pub use self::{seq::DeferredSeq, transcode::transcode};
+
 /// An encoder for encoding lilliput values.
 #[derive(Debug)]
 pub struct Encoder<W> {
     writer: W,
     pos: usize,
+    resets: usize,
     config: EncoderConfig,
 }
 
@@ -31,6 +54,7 @@ impl<W> Encoder<W> {
         Encoder {
             writer,
             pos: 0,
+            resets: 0,
             config,
         }
     }
@@ -44,6 +68,18 @@ impl<W> Encoder<W> {
     pub fn pos(&self) -> usize {
         self.pos
     }
+
+    /// Replaces the encoder's internal `writer`, resetting its write position
+    /// while retaining its configuration, returning the previous writer.
+    ///
+    /// This allows for reusing an encoder for encoding many values into separate outputs.
+    /// Any deferred sequences begun before resetting can no longer be ended.
+    pub fn reset(&mut self, writer: W) -> W {
+        self.pos = 0;
+        self.resets = self.resets.wrapping_add(1);
+
+        core::mem::replace(&mut self.writer, writer)
+    }
 }
 
 impl<W> Encoder<W>
@@ -62,6 +98,7 @@ where
             Header::Bool(value) => self.encode_bool_header(value),
             Header::Unit(value) => self.encode_unit_header(value),
             Header::Null(value) => self.encode_null_header(value),
+            Header::Ext(value) => self.encode_ext_header(value),
         }
     }
 
@@ -77,7 +114,52 @@ where
             Value::Bool(value) => self.encode_bool_value(value),
             Value::Unit(value) => self.encode_unit_value(value),
             Value::Null(value) => self.encode_null_value(value),
+            Value::Ext(value) => self.encode_ext_value(value),
+        }
+    }
+
+    /// Encodes `value`, converting it into a `Value` first.
+    ///
+    /// This is a convenience for ad-hoc values, e.g. `encoder.encode_into(42_u32)`.
+    /// Prefer the typed methods (e.g. `Encoder::encode_u32`) in hot paths,
+    /// as the conversion may allocate (e.g. for `&str`).
+    pub fn encode_into<T>(&mut self, value: T) -> Result<()>
+    where
+        T: Into<Value>,
+    {
+        self.encode_value(&value.into())
+    }
+
+    /// Encodes already-encoded `bytes` verbatim, e.g. for splicing cached values
+    /// into a larger structure, without decoding and re-encoding them.
+    ///
+    /// The bytes are expected to form exactly one complete value, which is not checked
+    /// (see `encode_raw_checked` for a validating variant). Otherwise the encoded output gets corrupted.
+    pub fn encode_raw(&mut self, bytes: &[u8]) -> Result<()> {
+        self.push_bytes(bytes)
+    }
+
+    /// Encodes already-encoded `bytes` verbatim, after validating that they form
+    /// exactly one complete value (see `Decoder::validate`).
+    ///
+    /// Returns the validation error (with its position relative to `bytes`) otherwise,
+    /// without writing anything.
+    pub fn encode_raw_checked(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut decoder = Decoder::from_reader(SliceReader::new(bytes));
+
+        decoder.validate()?;
+
+        let len = decoder.pos();
+
+        if len != bytes.len() {
+            return Err(Error::invalid_length(
+                bytes.len().to_string(),
+                len.to_string(),
+                Some(len),
+            ));
         }
+
+        self.encode_raw(bytes)
     }
 }
 
@@ -104,7 +186,13 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::io::{StdIoWriter, VecWriter};
+    use crate::{
+        config::PackingMode,
+        error::ErrorCode,
+        header::SeqHeader,
+        io::{SliceWriter, StdIoWriter, VecWriter},
+        value::{NullValue, SeqValue},
+    };
 
     use super::*;
 
@@ -130,4 +218,271 @@ mod test {
 
         assert_eq!(vec, vec![1, 2, 3]);
     }
+
+    #[test]
+    fn encode_raw() {
+        let elements = [
+            Value::from(42_u8),
+            Value::from("lilliput"),
+            Value::from_iter([(Value::from("a"), Value::from(vec![1_u8, 2, 3]))]),
+        ];
+
+        let cached: Vec<Vec<u8>> = elements
+            .iter()
+            .map(|element| {
+                let mut vec: Vec<u8> = Vec::new();
+                let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+                encoder.encode_value(element).unwrap();
+                vec
+            })
+            .collect();
+
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+        encoder
+            .encode_seq_header(&SeqHeader::for_len(cached.len(), PackingMode::Optimal))
+            .unwrap();
+        encoder.encode_raw(&cached[0]).unwrap();
+        encoder.encode_raw_checked(&cached[1]).unwrap();
+        encoder.encode_raw_checked(&cached[2]).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&vec));
+        let decoded = decoder.decode_value().unwrap();
+        assert_eq!(decoded, Value::Seq(SeqValue::from(elements.to_vec())));
+    }
+
+    #[test]
+    fn encode_raw_checked_rejects_invalid() {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut bytes));
+        encoder.encode_str("lilliput").unwrap();
+
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+
+        // Incomplete values:
+        let err = encoder
+            .encode_raw_checked(&bytes[..bytes.len() - 1])
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+
+        // More than one value:
+        let mut trailing = bytes.clone();
+        trailing.push(0x00);
+        let err = encoder.encode_raw_checked(&trailing).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidLength);
+        assert_eq!(err.pos(), Some(bytes.len()));
+
+        // Nothing gets written on failure:
+        assert_eq!(encoder.pos(), 0);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn encode_seq_iter() {
+        let expected: Value = (0..300_u16).map(Value::from).collect();
+
+        let mut collected: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut collected));
+        encoder.encode_value(&expected).unwrap();
+
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+        encoder
+            .encode_seq_iter((0..300_u16).map(Value::from))
+            .unwrap();
+        assert_eq!(vec, collected);
+
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+        encoder
+            .encode_seq_streaming((0..300_u16).map(Value::from).filter(|_| true))
+            .unwrap();
+        assert_eq!(vec, collected);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&vec));
+        assert_eq!(decoder.decode_value().unwrap(), expected);
+    }
+
+    #[test]
+    fn encode_seq_iter_mismatched_len() {
+        /// An iterator misreporting its length as `len`.
+        struct Misreported<I> {
+            iter: I,
+            len: usize,
+        }
+
+        impl<I: Iterator> Iterator for Misreported<I> {
+            type Item = I::Item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.iter.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<I: Iterator> ExactSizeIterator for Misreported<I> {}
+
+        for len in [2, 4] {
+            let mut vec: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+            let iter = Misreported {
+                iter: (0..3_u8).map(Value::from),
+                len,
+            };
+            let err = encoder.encode_seq_iter(iter).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidLength);
+        }
+    }
+
+    #[test]
+    fn encode_into() {
+        let expected = [
+            Value::from(42_u32),
+            Value::from(-7_i8),
+            Value::from(1.5_f64),
+            Value::from(true),
+            Value::from("lilliput"),
+            Value::from(vec![1_u8, 2, 3]),
+            Value::Null(NullValue),
+        ];
+
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+        encoder.encode_into(42_u32).unwrap();
+        encoder.encode_into(-7_i8).unwrap();
+        encoder.encode_into(1.5_f64).unwrap();
+        encoder.encode_into(true).unwrap();
+        encoder.encode_into("lilliput").unwrap();
+        encoder.encode_into(vec![1_u8, 2, 3]).unwrap();
+        encoder.encode_into(None::<u8>).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&vec));
+        for expected in expected {
+            assert_eq!(decoder.decode_value().unwrap(), expected);
+        }
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn reset() {
+        let mut first: Vec<u8> = Vec::new();
+        let mut second: Vec<u8> = Vec::new();
+
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut first));
+        encoder.encode_value(&Value::from("lilliput")).unwrap();
+        let first_len = encoder.pos();
+
+        let writer = encoder.reset(VecWriter::new(&mut second));
+        assert_eq!(writer.vec().len(), first_len);
+        assert_eq!(encoder.pos(), 0);
+        encoder.encode_value(&Value::from(42_u8)).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&first));
+        assert_eq!(decoder.decode_value().unwrap(), Value::from("lilliput"));
+        assert_eq!(decoder.pos(), first_len);
+
+        decoder.reset(SliceReader::new(&second));
+        assert_eq!(decoder.pos(), 0);
+        assert_eq!(decoder.decode_value().unwrap(), Value::from(42_u8));
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn encode_seq_deferred() {
+        let expected: Value = (0..300_u16).map(Value::from).collect();
+
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+        let mut seq = encoder.begin_seq_deferred().unwrap();
+        for value in (0..300_u16).map(Value::from) {
+            encoder
+                .encode_deferred_element(&mut seq, |encoder| encoder.encode_value(&value))
+                .unwrap();
+        }
+        assert_eq!(seq.len(), 300);
+        encoder.end_seq_deferred(seq).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&vec));
+        assert_eq!(decoder.decode_value().unwrap(), expected);
+
+        let mut buf = vec![0b0; vec.len()];
+        let mut encoder = Encoder::from_writer(SliceWriter::new(&mut buf));
+        let mut seq = encoder.begin_seq_deferred().unwrap();
+        for value in (0..300_u16).map(Value::from) {
+            encoder
+                .encode_deferred_element(&mut seq, |encoder| encoder.encode_value(&value))
+                .unwrap();
+        }
+        encoder.end_seq_deferred(seq).unwrap();
+        assert_eq!(buf, vec);
+    }
+
+    #[test]
+    fn encode_seq_deferred_nested() {
+        let expected: Value = [
+            Value::from(1_u8),
+            [Value::from("a"), Value::from("b")].into_iter().collect(),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+        let mut outer = encoder.begin_seq_deferred().unwrap();
+        encoder
+            .encode_deferred_element(&mut outer, |encoder| encoder.encode_u8(1))
+            .unwrap();
+        encoder
+            .encode_deferred_element(&mut outer, |encoder| {
+                let mut inner = encoder.begin_seq_deferred()?;
+                encoder.encode_deferred_element(&mut inner, |encoder| encoder.encode_str("a"))?;
+                encoder.encode_deferred_element(&mut inner, |encoder| encoder.encode_str("b"))?;
+                encoder.end_seq_deferred(inner)
+            })
+            .unwrap();
+        encoder.end_seq_deferred(outer).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&vec));
+        assert_eq!(decoder.decode_value().unwrap(), expected);
+    }
+
+    #[test]
+    fn encode_seq_deferred_strict() {
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut vec));
+        let seq = encoder.begin_seq_deferred().unwrap();
+        encoder.end_seq_deferred(seq).unwrap();
+
+        // The placeholder's fixed width is not canonical:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&vec));
+        decoder.set_strict(true);
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NonCanonicalEncoding);
+    }
+
+    #[test]
+    fn encode_seq_deferred_after_reset() {
+        let mut first: Vec<u8> = Vec::new();
+        let mut second: Vec<u8> = Vec::new();
+
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut first));
+        let seq = encoder.begin_seq_deferred().unwrap();
+        encoder.reset(VecWriter::new(&mut second));
+
+        let err = encoder.end_seq_deferred(seq).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Uncategorized);
+    }
+
+    #[test]
+    fn encode_seq_deferred_unsupported_writer() {
+        let mut vec: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(StdIoWriter::new(&mut vec));
+        let seq = encoder.begin_seq_deferred().unwrap();
+        let err = encoder.end_seq_deferred(seq).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Uncategorized);
+    }
 }
@@ -1,6 +1,10 @@
 use crate::{
-    config::PackingMode, error::Result, header::BytesHeader, io::Write,
-    num::WithPackedBeBytes as _, value::BytesValue,
+    config::PackingMode,
+    error::Result,
+    header::{BytesHeader, LenWidth},
+    io::Write,
+    num::WithPackedBeBytes as _,
+    value::BytesValue,
 };
 
 use super::Encoder;
@@ -36,15 +40,14 @@ where
         let packing_mode = self.config.lengths.packing.min(PackingMode::Native);
 
         len.with_packed_be_bytes(packing_mode, |bytes| {
-            let width = bytes.len();
+            let width = LenWidth::of_be_bytes(bytes);
 
-            debug_assert!(width.count_ones() == 1, "must be a power of two");
+            let Some(exponent) = width.exponent_bits() else {
+                unreachable!("native packing should produce power-of-two widths");
+            };
 
             let mut byte = BytesHeader::TYPE_BITS;
 
-            const EXPONENT: [u8; 8] = [0, 1, 2, 2, 3, 3, 3, 3];
-            let exponent = EXPONENT[width - 1];
-
             byte |= exponent & BytesHeader::LEN_WIDTH_EXPONENT_BITS;
 
             #[cfg(feature = "tracing")]
@@ -0,0 +1,19 @@
+use core::time::Duration;
+
+use crate::{error::Result, header::SeqHeader, io::Write};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a `Duration` value, as a `[seconds, nanoseconds]` sequence.
+    pub fn encode_duration(&mut self, value: Duration) -> Result<()> {
+        self.encode_seq_header(&SeqHeader::for_len(2, self.config.lengths.packing))?;
+        self.encode_u64(value.as_secs())?;
+        self.encode_u32(value.subsec_nanos())
+    }
+}
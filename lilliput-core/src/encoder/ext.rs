@@ -0,0 +1,67 @@
+use crate::{
+    error::Result,
+    header::{ExtHeader, LenWidth},
+    io::Write,
+    num::WithPackedBeBytes as _,
+    value::ExtValue,
+};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes an extension value, from its `tag` and payload `data`.
+    pub fn encode_ext(&mut self, tag: u64, data: &[u8]) -> Result<()> {
+        self.encode_ext_header(&self.header_for_ext(tag, data.len()))?;
+
+        // Push the value's payload:
+        self.push_bytes(data)?;
+
+        Ok(())
+    }
+
+    /// Encodes an extension value, from an `ExtValue`.
+    pub fn encode_ext_value(&mut self, value: &ExtValue) -> Result<()> {
+        self.encode_ext(value.tag(), value.as_slice())
+    }
+
+    // MARK: - Header
+
+    /// Encodes an extension value's header.
+    pub fn encode_ext_header(&mut self, header: &ExtHeader) -> Result<()> {
+        let len = header.len();
+        let packing_mode = self.config.lengths.packing;
+
+        len.with_packed_be_bytes(packing_mode, |bytes| {
+            let width = LenWidth::of_be_bytes(bytes);
+
+            let mut byte = ExtHeader::TYPE_BITS;
+            byte |= width.width_bits() & ExtHeader::LEN_WIDTH_BITS;
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                byte = crate::binary::fmt_byte(byte),
+                bytes = format!("{:b}", crate::binary::BytesSlice(bytes)),
+                len = len
+            );
+
+            // Push the value's header:
+            self.push_byte(byte)?;
+
+            // Push the value's length:
+            self.push_bytes(bytes)
+        })?;
+
+        // Push the value's tag:
+        self.encode_unsigned_int_packed(header.tag(), packing_mode)
+    }
+
+    /// Creates a header for an extension value, from its `tag` and payload length.
+    pub fn header_for_ext(&self, tag: u64, len: usize) -> ExtHeader {
+        ExtHeader::new(tag, len)
+    }
+}
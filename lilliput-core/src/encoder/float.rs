@@ -13,6 +13,8 @@ where
 
     /// Encodes a 32-bit floating-point value.
     pub fn encode_f32(&mut self, value: f32) -> Result<()> {
+        let value = self.config.floats.normalized_f32(value);
+
         let validator = self.config.floats.validation.f32.clone();
 
         value.with_validated_packed_be_bytes(self.config.floats.packing, &validator, |bytes| {
@@ -24,7 +26,15 @@ where
     }
 
     /// Encodes a 64-bit floating-point value.
+    ///
+    /// If float-shrinking is enabled, values exactly representable as `f32` get encoded as such.
     pub fn encode_f64(&mut self, value: f64) -> Result<()> {
+        let value = self.config.floats.normalized_f64(value);
+
+        if let Some(value) = self.config.floats.shrunk_f64(value) {
+            return self.encode_f32(value);
+        }
+
         let validator = self.config.floats.validation.f64.clone();
 
         value.with_validated_packed_be_bytes(self.config.floats.packing, &validator, |bytes| {
@@ -0,0 +1,36 @@
+use alloc::vec::Vec;
+
+use crate::{
+    config::PackingMode,
+    error::Result,
+    io::{VecWriter, Write},
+    value::Value,
+};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a `Value`, prefixed with its encoded length in bytes.
+    ///
+    /// The length prefix gets encoded as an unpacked (i.e. extended) unsigned integer,
+    /// making it occupy a fixed 9 bytes, regardless of the encoder's configuration.
+    ///
+    /// Use `Decoder::decode_framed()` for decoding framed values.
+    pub fn encode_framed(&mut self, value: &Value) -> Result<()> {
+        let mut body: Vec<u8> = Vec::new();
+
+        let mut encoder = Encoder::new(VecWriter::new(&mut body), self.config.clone());
+        encoder.encode_value(value)?;
+
+        // Push the frame's length:
+        self.encode_unsigned_int_packed(body.len() as u64, PackingMode::None)?;
+
+        // Push the frame's body:
+        self.push_bytes(&body)
+    }
+}
@@ -2,6 +2,7 @@ use num_traits::{Signed, Unsigned};
 
 use crate::{
     binary::bits_if,
+    config::{IntWidth, PackingMode},
     error::Result,
     header::{CompactIntHeader, ExtendedIntHeader, IntHeader},
     io::Write,
@@ -128,7 +129,10 @@ where
     where
         T: Signed + WithPackedBeBytes,
     {
-        IntHeader::for_signed(value, self.config.ints.packing)
+        match self.config.ints.width {
+            IntWidth::Minimal => IntHeader::for_signed(value, self.config.ints.packing),
+            IntWidth::Fixed64 => IntHeader::extended(true, 8),
+        }
     }
 
     /// Creates a header for an unsigned integer value.
@@ -136,15 +140,39 @@ where
     where
         T: Unsigned + WithPackedBeBytes,
     {
-        IntHeader::for_unsigned(value, self.config.ints.packing)
+        match self.config.ints.width {
+            IntWidth::Minimal => IntHeader::for_unsigned(value, self.config.ints.packing),
+            IntWidth::Fixed64 => IntHeader::extended(false, 8),
+        }
     }
 
     #[inline]
     fn encode_signed_int<S>(&mut self, value: S) -> Result<()>
+    where
+        S: Signed + WithPackedBeBytes + Into<i64>,
+    {
+        match self.config.ints.width {
+            IntWidth::Minimal => self.encode_signed_int_packed(value, self.config.ints.packing),
+            IntWidth::Fixed64 => self.encode_signed_int_packed(value.into(), PackingMode::None),
+        }
+    }
+
+    #[inline]
+    fn encode_unsigned_int<U>(&mut self, value: U) -> Result<()>
+    where
+        U: Unsigned + WithPackedBeBytes + Into<u64>,
+    {
+        match self.config.ints.width {
+            IntWidth::Minimal => self.encode_unsigned_int_packed(value, self.config.ints.packing),
+            IntWidth::Fixed64 => self.encode_unsigned_int_packed(value.into(), PackingMode::None),
+        }
+    }
+
+    #[inline]
+    fn encode_signed_int_packed<S>(&mut self, value: S, packing_mode: PackingMode) -> Result<()>
     where
         S: Signed + WithPackedBeBytes,
     {
-        let packing_mode = self.config.ints.packing;
         value.with_packed_be_bytes(packing_mode, |bytes| {
             let header = IntHeader::for_int_be_bytes(true, bytes, packing_mode);
 
@@ -161,12 +189,16 @@ where
         })
     }
 
+    /// Encodes an unsigned integer value, for a given `packing_mode`.
     #[inline]
-    fn encode_unsigned_int<U>(&mut self, value: U) -> Result<()>
+    pub(super) fn encode_unsigned_int_packed<U>(
+        &mut self,
+        value: U,
+        packing_mode: PackingMode,
+    ) -> Result<()>
     where
         U: Unsigned + WithPackedBeBytes,
     {
-        let packing_mode = self.config.ints.packing;
         value.with_packed_be_bytes(packing_mode, |bytes| {
             let header = IntHeader::for_int_be_bytes(false, bytes, packing_mode);
 
@@ -1,9 +1,9 @@
 use crate::{
     error::Result,
-    header::{CompactMapHeader, ExtendedMapHeader, MapHeader},
+    header::{CompactMapHeader, ExtendedMapHeader, LenWidth, MapHeader},
     io::Write,
     num::WithPackedBeBytes as _,
-    value::{Map, MapValue},
+    value::{Map, MapValue, Value},
 };
 
 use super::Encoder;
@@ -31,6 +31,21 @@ where
         self.encode_map(&value.0)
     }
 
+    /// Encodes a map value, from its `entries`, in their given order.
+    ///
+    /// Unlike `encode_map`, this preserves the entries' order (including any duplicate keys),
+    /// regardless of the `"preserve_order"` feature, mirroring `Decoder::decode_map_entries`.
+    pub fn encode_map_entries(&mut self, entries: &[(Value, Value)]) -> Result<()> {
+        self.encode_map_header(&self.header_for_map_len(entries.len()))?;
+
+        for (key, value) in entries {
+            self.encode_value(key)?;
+            self.encode_value(value)?;
+        }
+
+        Ok(())
+    }
+
     // MARK: - Header
 
     /// Encodes a map value's header.
@@ -47,9 +62,9 @@ where
             }
             MapHeader::Extended(ExtendedMapHeader { len }) => {
                 len.with_packed_be_bytes(self.config.lengths.packing, |bytes| {
-                    let width = bytes.len() as u8;
+                    let width = LenWidth::of_be_bytes(bytes);
 
-                    byte |= (width - 1) & MapHeader::EXTENDED_LEN_WIDTH_BITS;
+                    byte |= width.width_bits() & MapHeader::EXTENDED_LEN_WIDTH_BITS;
 
                     #[cfg(feature = "tracing")]
                     tracing::debug!(
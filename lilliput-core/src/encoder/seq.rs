@@ -1,13 +1,39 @@
+use alloc::{string::ToString, vec::Vec};
+
 use crate::{
-    error::Result,
-    header::{CompactSeqHeader, ExtendedSeqHeader, SeqHeader},
-    io::Write,
+    error::{Error, Result},
+    header::{CompactSeqHeader, ExtendedSeqHeader, LenWidth, SeqHeader},
+    io::{VecWriter, Write},
     num::WithPackedBeBytes as _,
     value::{SeqValue, Value},
 };
 
 use super::Encoder;
 
+/// A token for a sequence whose header got encoded with a placeholder length,
+/// as returned by `Encoder::begin_seq_deferred`.
+#[must_use = "the sequence's length needs to be backfilled via `Encoder::end_seq_deferred`"]
+#[derive(Debug)]
+pub struct DeferredSeq {
+    pos: usize,
+    len: usize,
+    resets: usize,
+}
+
+impl DeferredSeq {
+    const LEN_WIDTH: usize = 8;
+
+    /// Returns the number of elements encoded so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true`, if no elements have been encoded so far, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
 impl<W> Encoder<W>
 where
     W: Write,
@@ -30,6 +56,138 @@ where
         self.encode_seq(&value.0)
     }
 
+    /// Encodes a sequence value, from an iterator of known length,
+    /// without collecting its elements first.
+    ///
+    /// The header gets encoded upfront, based on the iterator's reported length.
+    /// If the iterator yields a different number of elements this fails with
+    /// `ErrorCode::InvalidLength`, leaving the output in an inconsistent state.
+    ///
+    /// Use `encode_seq_streaming` for iterators of unknown length.
+    pub fn encode_seq_iter<I>(&mut self, iter: I) -> Result<()>
+    where
+        I: IntoIterator<Item = Value>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        let len = iter.len();
+
+        self.encode_seq_header(&self.header_for_seq_len(len))?;
+
+        let mut count = 0;
+
+        for value in iter {
+            if count == len {
+                return Err(Error::invalid_length(
+                    "more elements".to_string(),
+                    len.to_string(),
+                    Some(self.pos),
+                ));
+            }
+
+            self.encode_value(&value)?;
+            count += 1;
+        }
+
+        if count != len {
+            return Err(Error::invalid_length(
+                count.to_string(),
+                len.to_string(),
+                Some(self.pos),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a sequence value, from an iterator of unknown length,
+    /// without collecting its elements first.
+    ///
+    /// As the header needs to precede the elements, they get encoded into
+    /// an intermediate buffer first, while counting them.
+    pub fn encode_seq_streaming<I>(&mut self, iter: I) -> Result<()>
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let mut body: Vec<u8> = Vec::new();
+        let mut len = 0;
+
+        let mut encoder = Encoder::new(VecWriter::new(&mut body), self.config.clone());
+
+        for value in iter {
+            encoder.encode_value(&value)?;
+            len += 1;
+        }
+
+        self.encode_seq_header(&self.header_for_seq_len(len))?;
+
+        // Push the sequence's elements:
+        self.push_bytes(&body)
+    }
+
+    /// Encodes a sequence value's header with a placeholder length,
+    /// to be backfilled by `end_seq_deferred` once its elements have been encoded
+    /// (via `encode_deferred_element`).
+    ///
+    /// Unlike `encode_seq_streaming` this doesn't buffer the elements,
+    /// but requires a writer that supports `Write::rewrite`.
+    ///
+    /// The placeholder always occupies the maximum length width,
+    /// regardless of the configured packing mode. The resulting encoding
+    /// is thus not canonical, and gets rejected by decoders in strict mode
+    /// (see `Decoder::set_strict`).
+    pub fn begin_seq_deferred(&mut self) -> Result<DeferredSeq> {
+        let pos = self.pos;
+        let width = LenWidth::of_be_bytes(&[0b0; DeferredSeq::LEN_WIDTH]);
+
+        let byte = SeqHeader::TYPE_BITS | (width.width_bits() & SeqHeader::EXTENDED_LEN_WIDTH_BITS);
+
+        // Push the value's header:
+        self.push_byte(byte)?;
+
+        // Push the value's (placeholder) length:
+        self.push_bytes(&[0b0; DeferredSeq::LEN_WIDTH])?;
+
+        Ok(DeferredSeq {
+            pos,
+            len: 0,
+            resets: self.resets,
+        })
+    }
+
+    /// Encodes an element of the deferred sequence `seq` by calling `encode`,
+    /// which is expected to encode exactly one value (e.g. a nested deferred sequence).
+    pub fn encode_deferred_element<F>(&mut self, seq: &mut DeferredSeq, encode: F) -> Result<()>
+    where
+        F: FnOnce(&mut Self) -> Result<()>,
+    {
+        encode(self)?;
+
+        seq.len += 1;
+
+        Ok(())
+    }
+
+    /// Backfills the placeholder header written by `begin_seq_deferred`
+    /// with the number of elements encoded via `encode_deferred_element`.
+    ///
+    /// Fails with `ErrorCode::Uncategorized` if the writer doesn't support `Write::rewrite`,
+    /// or if the encoder got reset since the sequence was begun.
+    pub fn end_seq_deferred(&mut self, seq: DeferredSeq) -> Result<()> {
+        if seq.resets != self.resets {
+            return Err(Error::uncategorized(
+                "deferred sequence was begun before resetting the encoder",
+                Some(self.pos),
+            ));
+        }
+
+        // Skip the header's leading byte, which doesn't depend on the length:
+        let distance = self.pos - seq.pos - 1;
+
+        self.writer
+            .rewrite(distance, &(seq.len as u64).to_be_bytes())
+    }
+
     // MARK: - Header
 
     /// Encodes a sequence value's header.
@@ -46,9 +204,9 @@ where
             }
             SeqHeader::Extended(ExtendedSeqHeader { len }) => {
                 len.with_packed_be_bytes(self.config.lengths.packing, |bytes| {
-                    let width = bytes.len() as u8;
+                    let width = LenWidth::of_be_bytes(bytes);
 
-                    byte |= (width - 1) & SeqHeader::EXTENDED_LEN_WIDTH_BITS;
+                    byte |= width.width_bits() & SeqHeader::EXTENDED_LEN_WIDTH_BITS;
 
                     #[cfg(feature = "tracing")]
                     tracing::debug!(
@@ -0,0 +1,19 @@
+use ::bytes::Bytes;
+
+use crate::{error::Result, io::Write};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a byte array value, from a `Bytes` buffer.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"bytes"` feature.*
+    pub fn encode_bytes_from(&mut self, value: &Bytes) -> Result<()> {
+        self.encode_bytes(value)
+    }
+}
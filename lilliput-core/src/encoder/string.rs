@@ -1,6 +1,6 @@
 use crate::{
     error::Result,
-    header::{CompactStringHeader, ExtendedStringHeader, StringHeader},
+    header::{CompactStringHeader, ExtendedStringHeader, LenWidth, StringHeader},
     io::Write,
     num::WithPackedBeBytes as _,
     value::StringValue,
@@ -47,9 +47,9 @@ where
             }
             StringHeader::Extended(ExtendedStringHeader { len }) => {
                 len.with_packed_be_bytes(self.config.lengths.packing, |bytes| {
-                    let width = bytes.len() as u8;
+                    let width = LenWidth::of_be_bytes(bytes);
 
-                    byte |= (width - 1) & StringHeader::EXTENDED_LEN_WIDTH_BITS;
+                    byte |= width.width_bits() & StringHeader::EXTENDED_LEN_WIDTH_BITS;
 
                     #[cfg(feature = "tracing")]
                     tracing::debug!(
@@ -0,0 +1,60 @@
+use crate::{error::Result, header::SeqHeader, io::Write, value::ExtValue};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a `chrono::DateTime<Utc>` value, as a `[seconds, nanoseconds]` sequence.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"chrono"` feature.*
+    #[cfg(feature = "chrono")]
+    pub fn encode_datetime(&mut self, value: &chrono::DateTime<chrono::Utc>) -> Result<()> {
+        self.encode_timestamp(value.timestamp(), value.timestamp_subsec_nanos())
+    }
+
+    /// Encodes a `chrono::DateTime<Utc>` value, as an extension value
+    /// tagged with [`ExtValue::TIMESTAMP_TAG`].
+    ///
+    /// *This method is only available if lilliput_core is built with the `"chrono"` feature.*
+    #[cfg(feature = "chrono")]
+    pub fn encode_datetime_ext(&mut self, value: &chrono::DateTime<chrono::Utc>) -> Result<()> {
+        self.encode_timestamp_ext(value.timestamp(), value.timestamp_subsec_nanos())
+    }
+
+    /// Encodes a `time::OffsetDateTime` value, as a `[seconds, nanoseconds]` sequence.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"time"` feature.*
+    #[cfg(feature = "time")]
+    pub fn encode_offset_datetime(&mut self, value: &time::OffsetDateTime) -> Result<()> {
+        self.encode_timestamp(value.unix_timestamp(), value.nanosecond())
+    }
+
+    /// Encodes a `time::OffsetDateTime` value, as an extension value
+    /// tagged with [`ExtValue::TIMESTAMP_TAG`].
+    ///
+    /// *This method is only available if lilliput_core is built with the `"time"` feature.*
+    #[cfg(feature = "time")]
+    pub fn encode_offset_datetime_ext(&mut self, value: &time::OffsetDateTime) -> Result<()> {
+        self.encode_timestamp_ext(value.unix_timestamp(), value.nanosecond())
+    }
+
+    // MARK: - Private
+
+    fn encode_timestamp(&mut self, secs: i64, nanos: u32) -> Result<()> {
+        self.encode_seq_header(&SeqHeader::for_len(2, self.config.lengths.packing))?;
+        self.encode_i64(secs)?;
+        self.encode_u32(nanos)
+    }
+
+    fn encode_timestamp_ext(&mut self, secs: i64, nanos: u32) -> Result<()> {
+        let mut data = [0b0; ExtValue::TIMESTAMP_LEN];
+        data[..8].copy_from_slice(&secs.to_be_bytes());
+        data[8..].copy_from_slice(&nanos.to_be_bytes());
+
+        self.encode_ext(ExtValue::TIMESTAMP_TAG, &data)
+    }
+}
@@ -0,0 +1,202 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    config::EncoderConfig,
+    decoder::Decoder,
+    error::Result,
+    header::Header,
+    io::{Read, Write},
+};
+
+use super::Encoder;
+
+/// Re-encodes all values read from `reader` into `writer`, as configured by `config`,
+/// returning the writer.
+///
+/// Sequences and maps get streamed element by element, without materializing them
+/// as `Value`s, while scalars get decoded and re-encoded one at a time.
+/// As such this allows for converting between configurations (e.g. from compact to canonical)
+/// of arbitrarily large documents, using memory proportional to their nesting depth
+/// (and their largest string, byte array or extension value).
+///
+/// See `Encoder::transcode_value` for transcoding individual values.
+pub fn transcode<'de, R, W>(reader: R, writer: W, config: EncoderConfig) -> Result<W>
+where
+    R: Read<'de>,
+    W: Write,
+{
+    let mut decoder = Decoder::from_reader(reader);
+    let mut encoder = Encoder::new(writer, config);
+
+    while !decoder.is_at_end()? {
+        encoder.transcode_value(&mut decoder)?;
+    }
+
+    Ok(encoder.into_writer())
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Re-encodes the next value decoded by `decoder`, as configured by the encoder.
+    ///
+    /// Sequences and maps get re-encoded element by element, as they get decoded,
+    /// without materializing them as `Value`s, and without recursing into nested values.
+    pub fn transcode_value<'de, R>(&mut self, decoder: &mut Decoder<R>) -> Result<()>
+    where
+        R: Read<'de>,
+    {
+        // The number of values remaining per nesting level (starting with the top-level):
+        let mut remaining: Vec<usize> = vec![1];
+
+        while let Some(count) = remaining.last_mut() {
+            if *count == 0 {
+                remaining.pop();
+                continue;
+            }
+
+            *count -= 1;
+
+            match decoder.decode_header()? {
+                Header::Seq(header) => {
+                    let len = header.len();
+                    self.encode_seq_header(&self.header_for_seq_len(len))?;
+                    remaining.push(len);
+                }
+                Header::Map(header) => {
+                    let len = header.len();
+                    self.encode_map_header(&self.header_for_map_len(len))?;
+                    remaining.push(len.saturating_mul(2));
+                }
+                header => {
+                    let value = decoder.decode_value_of(header)?;
+                    self.encode_value(&value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::PackingMode,
+        io::{SliceReader, VecWriter},
+        value::{FloatValue, Value},
+    };
+
+    use super::*;
+
+    fn encode(value: &Value, config: &EncoderConfig) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::new(VecWriter::new(&mut encoded), config.clone());
+        encoder.encode_value(value).unwrap();
+        encoded
+    }
+
+    fn decode_all(encoded: &[u8]) -> Vec<Value> {
+        let mut decoder = Decoder::from_reader(SliceReader::new(encoded));
+        let mut values = Vec::new();
+        while !decoder.is_at_end().unwrap() {
+            values.push(decoder.decode_value().unwrap());
+        }
+        values
+    }
+
+    fn transcoded(encoded: &[u8], config: &EncoderConfig) -> Vec<u8> {
+        let mut transcoded: Vec<u8> = Vec::new();
+        transcode(
+            SliceReader::new(encoded),
+            VecWriter::new(&mut transcoded),
+            config.clone(),
+        )
+        .unwrap();
+        transcoded
+    }
+
+    #[test]
+    fn nested_to_canonical() {
+        let value: Value = [
+            (Value::from("name"), Value::from("lilliput")),
+            (
+                Value::from("values"),
+                (0..100_u64)
+                    .map(|int| Value::from(int * 1_000))
+                    .collect::<Value>(),
+            ),
+            (
+                Value::from("nested"),
+                [(
+                    Value::from(1_u8),
+                    Value::from_iter([
+                        Value::Float(FloatValue::from(1.5_f64)),
+                        Value::from(vec![1_u8, 2, 3]),
+                        Value::from(None::<bool>),
+                    ]),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let verbose = EncoderConfig::builder().packing(PackingMode::None).build();
+        let canonical = EncoderConfig::builder().canonical(true).build();
+
+        let encoded = encode(&value, &verbose);
+        let transcoded = transcoded(&encoded, &canonical);
+
+        assert_eq!(decode_all(&transcoded), vec![value.clone()]);
+        assert_eq!(transcoded, encode(&value, &canonical));
+        assert!(transcoded.len() < encoded.len());
+    }
+
+    #[test]
+    fn multiple_values() {
+        let config = EncoderConfig::default();
+
+        let mut encoded = encode(&Value::from(42_u8), &config);
+        encoded.extend(encode(&Value::from_iter([Value::from("a")]), &config));
+
+        assert_eq!(
+            decode_all(&transcoded(&encoded, &config)),
+            decode_all(&encoded)
+        );
+        assert!(transcoded(&[], &config).is_empty());
+    }
+
+    #[test]
+    fn truncated() {
+        let value = Value::from_iter([Value::from(1_u8), Value::from("abc")]);
+        let encoded = encode(&value, &EncoderConfig::default());
+
+        for len in 1..encoded.len() {
+            let mut transcoded: Vec<u8> = Vec::new();
+            let result = transcode(
+                SliceReader::new(&encoded[..len]),
+                VecWriter::new(&mut transcoded),
+                EncoderConfig::default(),
+            );
+            assert!(result.is_err(), "{len}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(value in Value::arbitrary(), from in EncoderConfig::arbitrary(), to in EncoderConfig::arbitrary()) {
+            let encoded = encode(&value, &from);
+            let transcoded = transcoded(&encoded, &to);
+
+            prop_assert_eq!(&transcoded, &encode(&decode_all(&encoded)[0], &to));
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use ::uuid::Uuid;
+
+use crate::{error::Result, io::Write};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a UUID value, as a 16-byte byte array value.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"uuid"` feature.*
+    pub fn encode_uuid(&mut self, value: &Uuid) -> Result<()> {
+        self.encode_bytes(value.as_bytes())
+    }
+}
@@ -1,10 +1,13 @@
 //! When encoding or decoding Lilliput goes wrong.
 
 use alloc::boxed::Box;
-use alloc::string::ToString;
+use alloc::format;
+use alloc::string::{String, ToString};
 use core::fmt::{self, Debug, Display};
 use core::result;
 
+use crate::marker::Marker;
+
 /// Alias for a `Result` with the error type `Error`.
 pub type Result<T> = result::Result<T, Error>;
 
@@ -19,92 +22,208 @@ pub struct Expectation<U, E = U> {
 
 /// A minimal representation of all possible errors that can occur.
 pub struct Error {
-    kind: Box<ErrorKind>,
+    repr: Box<Repr>,
     pos: Option<usize>,
 }
 
+/// The heap-allocated part of an `Error`, keeping the latter small.
+struct Repr {
+    kind: ErrorKind,
+    marker_mismatch: Option<MarkerMismatch>,
+    range_overflow: Option<RangeOverflow>,
+}
+
+/// A decoded number which didn't fit into its target type,
+/// such as a length exceeding `usize::MAX` on a 32-bit target.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RangeOverflow {
+    /// The decoded number.
+    pub value: u64,
+    /// The width of the target type, in bits.
+    pub target_bits: u32,
+}
+
+impl Display for RangeOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} does not fit into {} bits",
+            self.value, self.target_bits
+        )
+    }
+}
+
+/// A mismatch between the type of value expected by a decoder,
+/// and the type of value actually found, as detected from its header byte.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MarkerMismatch {
+    /// The expected type's marker.
+    pub expected: Marker,
+    /// The found type's marker.
+    pub found: Marker,
+    /// The found header byte.
+    pub byte: u8,
+}
+
 impl Error {
     #[cold]
-    pub(crate) fn new(kind: Box<ErrorKind>, pos: Option<usize>) -> Self {
-        Self { kind, pos }
+    pub(crate) fn new(kind: ErrorKind, pos: Option<usize>) -> Self {
+        let repr = Box::new(Repr {
+            kind,
+            marker_mismatch: None,
+            range_overflow: None,
+        });
+
+        Self { repr, pos }
     }
 
     /// EOF while parsing.
     #[cold]
     pub fn end_of_file() -> Self {
-        Self::new(Box::new(ErrorKind::end_of_file()), None)
+        Self::new(ErrorKind::end_of_file(), None)
     }
 
     /// A mismatch occurred between the decoded and expected value types.
     #[cold]
     pub fn invalid_type(unexpected: String, expected: String, pos: Option<usize>) -> Self {
-        Self::new(Box::new(ErrorKind::invalid_type(unexpected, expected)), pos)
+        Self::new(ErrorKind::invalid_type(unexpected, expected), pos)
+    }
+
+    /// A mismatch occurred between the decoded and expected value types,
+    /// as detected from the decoded value's header `byte`.
+    ///
+    /// The mismatch is available via `Error::marker_mismatch`.
+    #[cold]
+    pub fn invalid_marker(expected: Marker, byte: u8, pos: Option<usize>) -> Self {
+        let found = Marker::detect(byte);
+
+        let mut err = Self::invalid_type(found.to_string(), expected.to_string(), pos);
+        err.repr.marker_mismatch = Some(MarkerMismatch {
+            expected,
+            found,
+            byte,
+        });
+        err
     }
 
     /// The enclosed I/O error occurred while trying to read the encoded
     /// MessagePack data.
     #[cold]
     pub fn invalid_value(unexpected: String, expected: String, pos: Option<usize>) -> Self {
-        Self::new(
-            Box::new(ErrorKind::invalid_value(unexpected, expected)),
-            pos,
-        )
+        Self::new(ErrorKind::invalid_value(unexpected, expected), pos)
     }
 
     /// A decoded sequence/map did not have the enclosed expected length.
     #[cold]
     pub fn invalid_length(unexpected: String, expected: String, pos: Option<usize>) -> Self {
-        Self::new(
-            Box::new(ErrorKind::invalid_length(unexpected, expected)),
-            pos,
-        )
+        Self::new(ErrorKind::invalid_length(unexpected, expected), pos)
     }
 
     /// An encoded sequence/map did not provide a length.
     #[cold]
     pub fn unknown_length() -> Self {
-        Self::new(Box::new(ErrorKind::unknown_length()), None)
+        Self::new(ErrorKind::unknown_length(), None)
     }
 
     /// A numeric cast failed due to an out-of-range error.
     #[cold]
     pub fn number_out_of_range(pos: Option<usize>) -> Self {
-        Self::new(Box::new(ErrorKind::number_out_of_range()), pos)
+        Self::new(ErrorKind::number_out_of_range(), pos)
+    }
+
+    /// A decoded length (or other number) `value` did not fit into a type of `target_bits` bits.
+    ///
+    /// The overflow is available via `Error::range_overflow`.
+    #[cold]
+    pub fn length_out_of_range(value: u64, target_bits: u32, pos: Option<usize>) -> Self {
+        let mut err = Self::number_out_of_range(pos);
+        err.repr.range_overflow = Some(RangeOverflow { value, target_bits });
+        err
     }
 
     /// An otherwise uncategorized error occurred.
     #[cold]
     pub fn uncategorized(msg: impl Display, pos: Option<usize>) -> Self {
-        Self::new(Box::new(ErrorKind::uncategorized(msg)), pos)
+        Self::new(ErrorKind::uncategorized(msg), pos)
     }
 
     /// The depth limit was exceeded.
     #[cold]
     pub fn depth_limit_exceeded(pos: Option<usize>) -> Self {
-        Self::new(Box::new(ErrorKind::depth_limit_exceeded()), pos)
+        Self::new(ErrorKind::depth_limit_exceeded(), pos)
+    }
+
+    /// The length limit was exceeded.
+    #[cold]
+    pub fn length_limit_exceeded(pos: Option<usize>) -> Self {
+        Self::new(ErrorKind::length_limit_exceeded(), pos)
+    }
+
+    /// A value was not encoded in its canonical (i.e. smallest possible) form.
+    #[cold]
+    pub fn non_canonical_encoding(pos: Option<usize>) -> Self {
+        Self::new(ErrorKind::non_canonical_encoding(), pos)
+    }
+
+    /// A writer's fixed-size buffer was too small for the encoded data.
+    #[cold]
+    pub fn buffer_overflow(pos: Option<usize>) -> Self {
+        Self::new(ErrorKind::buffer_overflow(), pos)
+    }
+
+    /// A decoded map contained the enclosed key more than once.
+    #[cold]
+    pub fn duplicate_key(key: impl Display, pos: Option<usize>) -> Self {
+        Self::new(ErrorKind::duplicate_key(key), pos)
+    }
+
+    /// The decoder's step budget was exhausted.
+    #[cold]
+    pub fn step_budget_exhausted(pos: Option<usize>) -> Self {
+        Self::new(ErrorKind::step_budget_exhausted(), pos)
+    }
+
+    /// The input contained trailing bytes after the decoded value.
+    #[cold]
+    pub fn trailing_data(pos: Option<usize>) -> Self {
+        Self::new(ErrorKind::trailing_data(), pos)
     }
 
     /// An encoded string could not be parsed as UTF-8.
     #[cold]
     pub fn utf8(err: core::str::Utf8Error, pos: Option<usize>) -> Self {
-        Self::new(Box::new(ErrorKind::utf8(err)), pos)
+        Self::new(ErrorKind::utf8(err), pos)
     }
 
     /// Reserved type.
     #[cold]
     pub fn reserved_type() -> Self {
-        Self::new(Box::new(ErrorKind::reserved_type()), None)
+        Self::new(ErrorKind::reserved_type(), None)
     }
 
     /// A `std::io::Error`.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"std"` feature.*
     #[cfg(feature = "std")]
     pub fn io(err: std::io::Error) -> Self {
-        Self::new(Box::new(ErrorKind::io(err)), None)
+        Self::new(ErrorKind::io(err), None)
     }
 
     /// Returns the error's kind.
     pub fn kind(&self) -> &ErrorKind {
-        &self.kind
+        &self.repr.kind
+    }
+
+    /// Returns the mismatch between the expected and found value types,
+    /// if the error was caused by a decoder encountering an unexpected header byte.
+    pub fn marker_mismatch(&self) -> Option<MarkerMismatch> {
+        self.repr.marker_mismatch
+    }
+
+    /// Returns the decoded number and its target type's width,
+    /// if the error was caused by a decoded length not fitting into its target type.
+    pub fn range_overflow(&self) -> Option<RangeOverflow> {
+        self.repr.range_overflow
     }
 
     /// Returns the error's position.
@@ -114,7 +233,27 @@ impl Error {
 
     /// Returns the error's code.
     pub fn code(&self) -> ErrorCode {
-        self.kind.as_code()
+        self.repr.kind.as_code()
+    }
+
+    /// Sets the error's position to `pos`, unless it already has one, returning `self`.
+    pub(crate) fn with_default_pos(mut self, pos: usize) -> Self {
+        self.pos = self.pos.or(Some(pos));
+        self
+    }
+
+    /// Returns the error's human-readable message, including any structured details.
+    fn message(&self) -> String {
+        match self.repr.range_overflow {
+            Some(overflow) => format!("{}: {overflow}", self.repr.kind),
+            None => self.repr.kind.to_string(),
+        }
+    }
+
+    /// Shifts the error's position (if any) by `offset`, returning `self`.
+    pub(crate) fn with_pos_offset(mut self, offset: usize) -> Self {
+        self.pos = self.pos.map(|pos| pos + offset);
+        self
     }
 }
 
@@ -122,9 +261,9 @@ impl Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Humans often end up seeing this representation because it is what `.unwrap()` shows.
         if let Some(pos) = self.pos {
-            write!(f, "Error({:?}, position: {pos:?})", self.kind.to_string())
+            write!(f, "Error({:?}, position: {pos:?})", self.message())
         } else {
-            write!(f, "Error({:?})", self.kind.to_string())
+            write!(f, "Error({:?})", self.message())
         }
     }
 }
@@ -133,16 +272,17 @@ impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Humans often end up seeing this representation because it is what `.unwrap()` shows.
         if let Some(pos) = self.pos {
-            write!(f, "{:?}, at position: {pos:?}", self.kind.to_string())
+            write!(f, "{:?}, at position: {pos:?}", self.message())
         } else {
-            write!(f, "{:?}", self.kind.to_string(),)
+            write!(f, "{:?}", self.message())
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match &*self.kind {
+        match &self.repr.kind {
             ErrorKind::UnexpectedEndOfFile => None,
             ErrorKind::InvalidType(_) => None,
             ErrorKind::InvalidValue(_) => None,
@@ -153,6 +293,12 @@ impl std::error::Error for Error {
             ErrorKind::DepthLimitExceeded => None,
             ErrorKind::Utf8(err) => Some(err),
             ErrorKind::ReservedType => None,
+            ErrorKind::LengthLimitExceeded => None,
+            ErrorKind::NonCanonicalEncoding => None,
+            ErrorKind::BufferOverflow => None,
+            ErrorKind::DuplicateKey(_) => None,
+            ErrorKind::StepBudgetExhausted => None,
+            ErrorKind::TrailingData => None,
             #[cfg(feature = "std")]
             ErrorKind::StdIo(err) => Some(err),
         }
@@ -221,6 +367,18 @@ pub enum ErrorCode {
     Utf8 = 81,
     /// Reserved type
     ReservedType = 91,
+    /// The length limit was exceeded.
+    LengthLimitExceeded = 101,
+    /// A value was not encoded in its canonical (i.e. smallest possible) form.
+    NonCanonicalEncoding = 111,
+    /// A writer's fixed-size buffer was too small for the encoded data.
+    BufferOverflow = 121,
+    /// A decoded map contained a key more than once.
+    DuplicateKey = 131,
+    /// The decoder's step budget was exhausted.
+    StepBudgetExhausted = 141,
+    /// The input contained trailing bytes after the decoded value.
+    TrailingData = 151,
     /// `std::io::Error`.
     #[cfg(feature = "std")]
     StdIo = 255,
@@ -251,6 +409,18 @@ pub enum ErrorKind {
     Utf8(core::str::Utf8Error),
     /// ReservedType.
     ReservedType,
+    /// The length limit was exceeded.
+    LengthLimitExceeded,
+    /// A value was not encoded in its canonical (i.e. smallest possible) form.
+    NonCanonicalEncoding,
+    /// A writer's fixed-size buffer was too small for the encoded data.
+    BufferOverflow,
+    /// A decoded map contained the enclosed key more than once.
+    DuplicateKey(String),
+    /// The decoder's step budget was exhausted.
+    StepBudgetExhausted,
+    /// The input contained trailing bytes after the decoded value.
+    TrailingData,
     /// `std::io::Error`.
     #[cfg(feature = "std")]
     StdIo(std::io::Error),
@@ -308,6 +478,36 @@ impl ErrorKind {
         Self::DepthLimitExceeded
     }
 
+    /// The length limit was exceeded.
+    fn length_limit_exceeded() -> Self {
+        Self::LengthLimitExceeded
+    }
+
+    /// A value was not encoded in its canonical (i.e. smallest possible) form.
+    fn non_canonical_encoding() -> Self {
+        Self::NonCanonicalEncoding
+    }
+
+    /// A writer's fixed-size buffer was too small for the encoded data.
+    fn buffer_overflow() -> Self {
+        Self::BufferOverflow
+    }
+
+    /// A decoded map contained the enclosed key more than once.
+    fn duplicate_key(key: impl Display) -> Self {
+        Self::DuplicateKey(key.to_string())
+    }
+
+    /// The decoder's step budget was exhausted.
+    fn step_budget_exhausted() -> Self {
+        Self::StepBudgetExhausted
+    }
+
+    /// The input contained trailing bytes after the decoded value.
+    fn trailing_data() -> Self {
+        Self::TrailingData
+    }
+
     /// An encoded string could not be parsed as UTF-8.
     fn utf8(err: core::str::Utf8Error) -> Self {
         Self::Utf8(err)
@@ -340,6 +540,13 @@ impl ErrorKind {
             ErrorKind::DepthLimitExceeded => ErrorCode::DepthLimitExceeded,
             ErrorKind::Utf8(_) => ErrorCode::Utf8,
             ErrorKind::ReservedType => ErrorCode::ReservedType,
+            ErrorKind::LengthLimitExceeded => ErrorCode::LengthLimitExceeded,
+            ErrorKind::NonCanonicalEncoding => ErrorCode::NonCanonicalEncoding,
+            ErrorKind::BufferOverflow => ErrorCode::BufferOverflow,
+            ErrorKind::DuplicateKey(_) => ErrorCode::DuplicateKey,
+            ErrorKind::StepBudgetExhausted => ErrorCode::StepBudgetExhausted,
+            ErrorKind::TrailingData => ErrorCode::TrailingData,
+            #[cfg(feature = "std")]
             ErrorKind::StdIo(_) => ErrorCode::StdIo,
         }
     }
@@ -371,15 +578,74 @@ impl Display for ErrorKind {
                 )
             }
             Self::UnknownLength => f.write_str("unknown length"),
-            Self::NumberOutOfRange => f.write_str("unexpected EOF while parsing"),
+            Self::NumberOutOfRange => f.write_str("number out of range"),
             Self::Uncategorized(msg) => f.write_str(msg),
-            Self::DepthLimitExceeded => {
-                f.write_str("a numeric cast failed due to an out-of-range error")
-            }
+            Self::DepthLimitExceeded => f.write_str("depth limit exceeded"),
             Self::Utf8(err) => Display::fmt(err, f),
             Self::ReservedType => f.write_str("reserved type"),
+            Self::LengthLimitExceeded => f.write_str("length limit exceeded"),
+            Self::NonCanonicalEncoding => f.write_str("non-canonical encoding"),
+            Self::BufferOverflow => f.write_str("buffer overflow"),
+            Self::DuplicateKey(key) => write!(f, "duplicate map key {key}"),
+            Self::StepBudgetExhausted => f.write_str("step budget exhausted"),
+            Self::TrailingData => f.write_str("trailing data"),
             #[cfg(feature = "std")]
             Self::StdIo(err) => Display::fmt(err, f),
         }
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::error::Error as _;
+
+    use test_log::test;
+
+    use crate::{
+        decoder::Decoder,
+        io::{SliceReader, StdIoReader},
+    };
+
+    use super::*;
+
+    /// A reader failing on every read.
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "connection reset",
+            ))
+        }
+    }
+
+    #[test]
+    fn source_io() {
+        let mut decoder = Decoder::from_reader(StdIoReader::new(FailingReader));
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::StdIo);
+
+        let source = err.source().expect("io errors should have a source");
+        let io_err = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io_err.kind(), std::io::ErrorKind::ConnectionReset);
+    }
+
+    #[test]
+    fn source_utf8() {
+        // A compact string header of length 1, followed by an invalid UTF-8 byte:
+        let encoded = [0b01100001, 0xff];
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder.decode_string().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Utf8);
+
+        let source = err.source().expect("utf-8 errors should have a source");
+        assert!(source.downcast_ref::<core::str::Utf8Error>().is_some());
+    }
+
+    #[test]
+    fn source_none() {
+        assert!(Error::end_of_file().source().is_none());
+        assert!(Error::trailing_data(Some(42)).source().is_none());
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    config::{EncoderConfig, PackingMode},
+    decoder::Decoder,
+    encoder::Encoder,
+    error::Result,
+    header::Header,
+    io::{SliceReader, VecWriter},
+    value::Value,
+};
+
+/// The maximum nesting depth of sequences and maps accepted by `fuzz_roundtrip`.
+///
+/// Decoding values recurses once per nesting level, so deeper inputs get rejected
+/// upfront, rather than overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Checks the round-trip invariants of the value encoded at the start of `data`,
+/// returning `false` if they are violated.
+///
+/// The value gets decoded, re-encoded and decoded once more, which must:
+///
+/// - decode structurally equal to the original value, if re-encoded with optimal
+///   packing and minimal integer widths (but neither packed nor normalized floats,
+///   both of which are lossy for NaN payloads, by design),
+/// - decode into a fix-point of canonical re-encoding, if re-encoded canonically
+///   (as normalizing floats may merge map keys, this holds after the first round-trip).
+///
+/// Malformed (or excessively nested) input is not an inconsistency and returns `true`.
+/// This function never panics, making it suitable as the body of a fuzz target:
+///
+/// ```ignore
+/// fuzz_target!(|data: &[u8]| {
+///     assert!(lilliput_core::fuzz_roundtrip(data));
+/// });
+/// ```
+///
+/// *This function is only available if lilliput_core is built with the `"fuzzing"` feature.*
+pub fn fuzz_roundtrip(data: &[u8]) -> bool {
+    if !is_within_depth(data, MAX_DEPTH) {
+        return true;
+    }
+
+    let Ok(value) = decode(data) else {
+        return true;
+    };
+
+    roundtrip(&value).unwrap_or(false)
+}
+
+fn roundtrip(value: &Value) -> Result<bool> {
+    let canonical = EncoderConfig::builder().canonical(true).build();
+
+    let mut lossless = EncoderConfig::builder()
+        .canonical(true)
+        .normalize_floats(false)
+        .build();
+    lossless.floats = lossless.floats.with_packing(PackingMode::None);
+
+    // Packing and integer widths must not change the value:
+    if decode(&encode(value, &lossless)?)? != *value {
+        return Ok(false);
+    }
+
+    // Canonical re-encoding must be stable:
+    let canonicalized = decode(&encode(value, &canonical)?)?;
+    let recanonicalized = decode(&encode(&canonicalized, &canonical)?)?;
+
+    Ok(canonicalized == recanonicalized)
+}
+
+fn encode(value: &Value, config: &EncoderConfig) -> Result<Vec<u8>> {
+    let mut encoded: Vec<u8> = Vec::new();
+    let mut encoder = Encoder::new(VecWriter::new(&mut encoded), config.clone());
+    encoder.encode_value(value)?;
+    Ok(encoded)
+}
+
+fn decode(encoded: &[u8]) -> Result<Value> {
+    Decoder::from_reader(SliceReader::new(encoded)).decode_value()
+}
+
+/// Returns `false` if the value at the start of `data` nests sequences
+/// and maps deeper than `max_depth`, using a heap-allocated stack.
+///
+/// Malformed values are reported as within depth, leaving it to the decoder to reject them.
+fn is_within_depth(data: &[u8], max_depth: usize) -> bool {
+    let mut decoder = Decoder::from_reader(SliceReader::new(data));
+
+    // The number of values remaining per nesting level (starting with the top-level):
+    let mut remaining: Vec<usize> = vec![1];
+
+    while let Some(count) = remaining.last_mut() {
+        if *count == 0 {
+            remaining.pop();
+            continue;
+        }
+
+        *count -= 1;
+
+        let Ok(header) = decoder.decode_header() else {
+            return true;
+        };
+
+        match header {
+            Header::Seq(header) => remaining.push(header.len()),
+            Header::Map(header) => remaining.push(header.len().saturating_mul(2)),
+            header => {
+                if decoder.skip_value_of(header).is_err() {
+                    return true;
+                }
+            }
+        }
+
+        if remaining.len() > max_depth + 1 {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::value::{FloatValue, SeqValue};
+
+    use super::*;
+
+    #[test]
+    fn corpus() {
+        let values = [
+            Value::from(42_u8),
+            Value::from(-1_i64),
+            Value::from(u64::MAX),
+            Value::from("lilliput"),
+            Value::from(vec![1_u8, 2, 3]),
+            Value::Float(FloatValue::from(-0.0_f64)),
+            Value::Float(FloatValue::from(f64::NAN)),
+            Value::Float(FloatValue::from(1.5_f64)),
+            Value::from(Some(true)),
+            Value::from(None::<bool>),
+            Value::Seq(SeqValue::from(vec![Value::from(1_u8), Value::from("a")])),
+            Value::from_iter([(Value::from("a"), Value::from(1_u8))]),
+        ];
+
+        for value in &values {
+            let encoded = encode(value, &EncoderConfig::default()).unwrap();
+            assert!(fuzz_roundtrip(&encoded), "{value:?}");
+
+            // Truncated (and thus malformed) input:
+            for len in 0..encoded.len() {
+                assert!(fuzz_roundtrip(&encoded[..len]));
+            }
+        }
+
+        // Every possible single byte, whether valid on its own or not:
+        for byte in u8::MIN..=u8::MAX {
+            assert!(fuzz_roundtrip(&[byte]));
+        }
+
+        // Arbitrary garbage, as well as trailing bytes:
+        assert!(fuzz_roundtrip(&[0xff; 64]));
+        assert!(fuzz_roundtrip(&[0x00, 0xde, 0xad, 0xbe, 0xef]));
+    }
+
+    #[test]
+    fn deeply_nested() {
+        let nested = Value::Seq(SeqValue::from(vec![Value::from(42_u8)]));
+        let encoded = encode(&nested, &EncoderConfig::default()).unwrap();
+        let seq_header = encoded[0];
+
+        // Excessive nesting must not overflow the stack:
+        let mut data = vec![seq_header; 1_000_000];
+        data.push(0x00);
+        assert!(!is_within_depth(&data, MAX_DEPTH));
+        assert!(fuzz_roundtrip(&data));
+
+        let mut data = vec![seq_header; MAX_DEPTH - 1];
+        data.extend_from_slice(&encoded);
+        assert!(is_within_depth(&data, MAX_DEPTH));
+        assert!(fuzz_roundtrip(&data));
+    }
+
+    proptest! {
+        #[test]
+        fn valid_input(value in Value::arbitrary()) {
+            let encoded = encode(&value, &EncoderConfig::default()).unwrap();
+            prop_assert!(fuzz_roundtrip(&encoded));
+        }
+
+        #[test]
+        fn arbitrary_input(data in prop::collection::vec(any::<u8>(), 0..64)) {
+            prop_assert!(fuzz_roundtrip(&data));
+        }
+    }
+}
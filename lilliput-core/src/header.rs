@@ -2,8 +2,10 @@
 
 mod bool;
 mod bytes;
+mod ext;
 mod float;
 mod int;
+mod len_width;
 mod map;
 mod null;
 mod seq;
@@ -15,13 +17,15 @@ use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
 use proptest_derive::Arbitrary;
 
-use crate::marker::Marker;
+use crate::{config::PackingMode, marker::Marker, sealed::Sealed};
 
 pub use self::{
     bool::BoolHeader,
     bytes::BytesHeader,
+    ext::ExtHeader,
     float::FloatHeader,
     int::{CompactIntHeader, ExtendedIntHeader, IntHeader},
+    len_width::LenWidth,
     map::{CompactMapHeader, ExtendedMapHeader, MapHeader},
     null::NullHeader,
     seq::{CompactSeqHeader, ExtendedSeqHeader, SeqHeader},
@@ -39,6 +43,34 @@ pub(crate) fn arbitrary_len() -> impl Strategy<Value = usize> {
     ]
 }
 
+/// A header of a length-bearing value.
+///
+/// This trait is sealed and cannot be implemented outside of `lilliput_core`.
+pub trait HasLen: Sealed {
+    /// Returns the associated value's length.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the associated value has a length of zero, otherwise `false`.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+macro_rules! impl_has_len {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl HasLen for $t {
+                #[inline]
+                fn len(&self) -> usize {
+                    <$t>::len(self)
+                }
+            }
+        )+
+    }
+}
+
+impl_has_len!(StringHeader, SeqHeader, MapHeader, BytesHeader, ExtHeader);
+
 /// A value's header.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -73,6 +105,9 @@ pub enum Header {
 
     /// Represents a null value.
     Null(NullHeader),
+
+    /// Represents an application-defined extension value.
+    Ext(ExtHeader),
 }
 
 impl Default for Header {
@@ -145,6 +180,13 @@ impl From<NullHeader> for Header {
     }
 }
 
+impl From<ExtHeader> for Header {
+    #[inline]
+    fn from(value: ExtHeader) -> Self {
+        Self::Ext(value)
+    }
+}
+
 impl Header {
     /// Returns the header's type marker.
     pub fn marker(&self) -> Marker {
@@ -158,6 +200,23 @@ impl Header {
             Header::Bool(_) => Marker::Bool,
             Header::Unit(_) => Marker::Unit,
             Header::Null(_) => Marker::Null,
+            Header::Ext(_) => Marker::Ext,
+        }
+    }
+
+    /// Returns the header's encoded length in bytes, for a given length `packing_mode`.
+    pub fn encoded_len(&self, packing_mode: PackingMode) -> usize {
+        match self {
+            Header::Int(header) => header.encoded_len(),
+            Header::String(header) => header.encoded_len(packing_mode),
+            Header::Seq(header) => header.encoded_len(packing_mode),
+            Header::Map(header) => header.encoded_len(packing_mode),
+            Header::Float(header) => header.encoded_len(),
+            Header::Bytes(header) => header.encoded_len(packing_mode),
+            Header::Bool(header) => header.encoded_len(),
+            Header::Unit(header) => header.encoded_len(),
+            Header::Null(header) => header.encoded_len(),
+            Header::Ext(header) => header.encoded_len(packing_mode),
         }
     }
 }
@@ -176,14 +235,136 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn encoded_len() {
+        let compact = Header::Seq(SeqHeader::compact(3));
+        assert_eq!(compact.encoded_len(PackingMode::Optimal), 1);
+
+        let extended = Header::Seq(SeqHeader::extended(300));
+        assert_eq!(extended.encoded_len(PackingMode::Optimal), 1 + 2);
+        assert_eq!(extended.encoded_len(PackingMode::None), 1 + 8);
+
+        let bytes = Header::Bytes(BytesHeader::for_len(300));
+        assert_eq!(bytes.encoded_len(PackingMode::Optimal), 1 + 2);
+
+        let int = Header::Int(IntHeader::extended(true, 8));
+        assert_eq!(int.encoded_len(PackingMode::None), 1);
+    }
+
+    #[test]
+    fn has_len() {
+        let headers: [&dyn HasLen; 5] = [
+            &StringHeader::compact(3),
+            &SeqHeader::extended(300),
+            &MapHeader::compact(0),
+            &BytesHeader::for_len(42),
+            &ExtHeader::new(7, 8),
+        ];
+
+        let total: usize = headers.iter().map(|header| header.len()).sum();
+        assert_eq!(total, 3 + 300 + 42 + 8);
+
+        let empty: Vec<bool> = headers.iter().map(|header| header.is_empty()).collect();
+        assert_eq!(empty, vec![false, false, true, false, false]);
+    }
+
+    #[test]
+    fn constructors() {
+        use crate::value::{IntValue, Value};
+
+        /// Encodes `value`, as well as `header` (which is expected to prefix the former).
+        fn assert_prefix(header: Header, value: &Value, config: &EncoderConfig) {
+            let mut encoded_header: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::new(VecWriter::new(&mut encoded_header), config.clone());
+            encoder.encode_header(&header).unwrap();
+
+            let mut encoded_value: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::new(VecWriter::new(&mut encoded_value), config.clone());
+            encoder.encode_value(value).unwrap();
+
+            assert!(
+                encoded_value.starts_with(&encoded_header),
+                "{header:?} for {value:?}"
+            );
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded_header));
+            assert_eq!(decoder.decode_header().unwrap(), header);
+        }
+
+        let configs = [
+            EncoderConfig::default(),
+            EncoderConfig::builder().packing(PackingMode::None).build(),
+            EncoderConfig::builder()
+                .packing(PackingMode::Native)
+                .shrink_floats(true)
+                .build(),
+        ];
+
+        for config in &configs {
+            let packing_mode = config.lengths.packing;
+
+            for value in [
+                IntValue::from(5_u8),
+                IntValue::from(5_i8),
+                IntValue::from(300_u16),
+                IntValue::from(-300_i64),
+                IntValue::from(u64::MAX),
+            ] {
+                let header = IntHeader::for_value(value, config.ints.packing);
+                assert_eq!(header.is_signed(), value.is_signed());
+                assert_prefix(Header::Int(header), &Value::Int(value), config);
+            }
+
+            for value in [0.0_f32, 1.5, f32::MAX] {
+                let header = FloatHeader::for_f32(value, &config.floats);
+                assert_prefix(Header::Float(header), &Value::from(value), config);
+            }
+
+            for value in [0.0_f64, 1.5, 0.1, f64::MAX] {
+                let header = FloatHeader::for_f64(value, &config.floats);
+                assert_prefix(Header::Float(header), &Value::from(value), config);
+            }
+
+            for len in [0, 3, 300] {
+                let string = "a".repeat(len);
+                let header = StringHeader::for_len(len, packing_mode);
+                assert_prefix(Header::String(header), &Value::from(string), config);
+
+                let seq: Value = (0..len).map(|_| Value::from(true)).collect();
+                let header = SeqHeader::for_len(len, packing_mode);
+                assert_prefix(Header::Seq(header), &seq, config);
+
+                let map: Value = (0..len)
+                    .map(|i| (Value::from(i), Value::from(true)))
+                    .collect();
+                let header = MapHeader::for_len(len, packing_mode);
+                assert_prefix(Header::Map(header), &map, config);
+
+                let header = BytesHeader::for_len(len);
+                assert_prefix(Header::Bytes(header), &Value::from(vec![0_u8; len]), config);
+            }
+        }
+
+        // Shrinking narrows exactly representable values, even without packing:
+        let config = EncoderConfig::builder()
+            .packing(PackingMode::None)
+            .shrink_floats(true)
+            .build();
+        assert_eq!(FloatHeader::for_f64(1.5, &config.floats).width(), 4);
+        assert_eq!(FloatHeader::for_f64(0.1, &config.floats).width(), 8);
+    }
+
     proptest! {
         #[test]
         fn encode_decode_roundtrip(header in Header::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
             let writer = VecWriter::new(&mut encoded);
+            let packing_mode = config.lengths.packing;
             let mut encoder = Encoder::new(writer, config);
             encoder.encode_header(&header).unwrap();
 
+            prop_assert_eq!(encoded.len(), header.encoded_len(packing_mode));
+
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
             let decoded = decoder.decode_header().unwrap();
@@ -22,6 +22,12 @@ impl BoolHeader {
     pub fn value(&self) -> bool {
         self.value
     }
+
+    /// Returns the header's encoded length in bytes.
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        1
+    }
 }
 
 impl BoolHeader {
@@ -54,6 +60,7 @@ mod tests {
             encoder.encode_bool_header(&header).unwrap();
 
             prop_assert!(encoded.len() == 1);
+            prop_assert_eq!(encoded.len(), header.encoded_len());
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
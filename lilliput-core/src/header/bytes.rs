@@ -3,6 +3,8 @@ use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
 use proptest_derive::Arbitrary;
 
+use crate::{config::PackingMode, num::WithPackedBeBytes as _};
+
 /// Header representing a byte sequence.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -32,6 +34,16 @@ impl BytesHeader {
     pub fn len(&self) -> usize {
         self.len
     }
+
+    /// Returns the header's encoded length in bytes, for a given length `packing_mode`.
+    pub fn encoded_len(&self, packing_mode: PackingMode) -> usize {
+        // The bytes header only supports native packing:
+        let packing_mode = packing_mode.min(PackingMode::Native);
+
+        1 + self
+            .len
+            .with_packed_be_bytes(packing_mode, |bytes| bytes.len())
+    }
 }
 
 impl BytesHeader {
@@ -60,10 +72,12 @@ mod tests {
         fn encode_decode_roundtrip(header in BytesHeader::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
             let writer = VecWriter::new(&mut encoded);
+            let packing_mode = config.lengths.packing;
             let mut encoder = Encoder::new(writer, config);
             encoder.encode_bytes_header(&header).unwrap();
 
             prop_assert!(encoded.len() <= 1 + 8);
+            prop_assert_eq!(encoded.len(), header.encoded_len(packing_mode));
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
@@ -0,0 +1,102 @@
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+#[cfg(any(test, feature = "testing"))]
+use proptest_derive::Arbitrary;
+
+use crate::{config::PackingMode, num::WithPackedBeBytes as _};
+
+use super::IntHeader;
+
+/// Header representing an application-defined extension value.
+#[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ExtHeader {
+    tag: u64,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "super::arbitrary_len()")
+    )]
+    len: usize,
+}
+
+impl ExtHeader {
+    /// Creates a header from an extension value's `tag` and payload length.
+    #[inline]
+    pub fn new(tag: u64, len: usize) -> Self {
+        Self { tag, len }
+    }
+
+    /// Returns the associated value's application-defined tag.
+    #[inline]
+    pub fn tag(&self) -> u64 {
+        self.tag
+    }
+
+    /// Returns `true` if the associated value's payload has a length of zero bytes, otherwise `false`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the associated value's payload length.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the header's encoded length in bytes, for a given length `packing_mode`.
+    ///
+    /// The tag gets packed using the same `packing_mode` as the length.
+    pub fn encoded_len(&self, packing_mode: PackingMode) -> usize {
+        let len_width = self
+            .len
+            .with_packed_be_bytes(packing_mode, |bytes| bytes.len());
+
+        let tag_header = IntHeader::for_unsigned(self.tag, packing_mode);
+        let tag_width = tag_header.extended_width().unwrap_or_default();
+
+        1 + len_width + tag_header.encoded_len() + (tag_width as usize)
+    }
+}
+
+impl ExtHeader {
+    pub(crate) const MASK: u8 = 0b00101111;
+    pub(crate) const TYPE_BITS: u8 = 0b00101000;
+    pub(crate) const TYPE_MASK: u8 = 0b11111000;
+
+    pub(crate) const LEN_WIDTH_BITS: u8 = 0b00000111;
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::EncoderConfig,
+        decoder::Decoder,
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+    };
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn encode_decode_roundtrip(header in ExtHeader::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let packing_mode = config.lengths.packing;
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_ext_header(&header).unwrap();
+
+            prop_assert!(encoded.len() <= 1 + 8 + 1 + 8);
+            prop_assert_eq!(encoded.len(), header.encoded_len(packing_mode));
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_ext_header().unwrap();
+            prop_assert_eq!(&decoded, &header);
+        }
+    }
+}
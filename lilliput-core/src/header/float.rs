@@ -3,6 +3,8 @@ use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
 use proptest_derive::Arbitrary;
 
+use crate::{config::FloatEncoderConfig, num::WithValidatedPackedBeBytes as _};
+
 /// Header representing a floating-point number.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -23,10 +25,40 @@ impl FloatHeader {
         Self { width }
     }
 
+    /// Creates a header for a given 32-bit `value`, for a given `config`.
+    pub fn for_f32(value: f32, config: &FloatEncoderConfig) -> Self {
+        let value = config.normalized_f32(value);
+
+        value.with_validated_packed_be_bytes(config.packing, &config.validation.f32, |bytes| {
+            Self::new(bytes.len() as u8)
+        })
+    }
+
+    /// Creates a header for a given 64-bit `value`, for a given `config`.
+    ///
+    /// If float-shrinking is enabled, values exactly representable as `f32` get a header for such.
+    pub fn for_f64(value: f64, config: &FloatEncoderConfig) -> Self {
+        let value = config.normalized_f64(value);
+
+        if let Some(value) = config.shrunk_f64(value) {
+            return Self::for_f32(value, config);
+        }
+
+        value.with_validated_packed_be_bytes(config.packing, &config.validation.f64, |bytes| {
+            Self::new(bytes.len() as u8)
+        })
+    }
+
     /// Returns the associated value's byte-width.
     pub fn width(&self) -> u8 {
         self.width
     }
+
+    /// Returns the header's encoded length in bytes.
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        1
+    }
 }
 
 impl FloatHeader {
@@ -61,6 +93,7 @@ mod tests {
             encoder.encode_float_header(&header).unwrap();
 
             prop_assert!(encoded.len() == 1);
+            prop_assert_eq!(encoded.len(), header.encoded_len());
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
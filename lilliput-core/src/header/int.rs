@@ -5,7 +5,11 @@ use proptest_derive::Arbitrary;
 
 use num_traits::{Signed, Unsigned};
 
-use crate::{config::PackingMode, num::WithPackedBeBytes};
+use crate::{
+    config::PackingMode,
+    num::WithPackedBeBytes,
+    value::{IntValue, SignedIntValue, UnsignedIntValue},
+};
 
 /// Header representing an integer number.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
@@ -53,10 +57,41 @@ impl IntHeader {
         T: Unsigned + WithPackedBeBytes,
     {
         value.with_packed_be_bytes(packing_mode, |be_bytes| {
-            Self::for_int_be_bytes(true, be_bytes, packing_mode)
+            Self::for_int_be_bytes(false, be_bytes, packing_mode)
         })
     }
 
+    /// Creates a header for a given `value`, for a given `packing_mode`,
+    /// retaining its signedness.
+    pub fn for_value(value: IntValue, packing_mode: PackingMode) -> Self {
+        match value {
+            IntValue::Signed(SignedIntValue::I8(value)) => Self::for_signed(value, packing_mode),
+            IntValue::Signed(SignedIntValue::I16(value)) => Self::for_signed(value, packing_mode),
+            IntValue::Signed(SignedIntValue::I32(value)) => Self::for_signed(value, packing_mode),
+            IntValue::Signed(SignedIntValue::I64(value)) => Self::for_signed(value, packing_mode),
+            IntValue::Unsigned(UnsignedIntValue::U8(value)) => {
+                Self::for_unsigned(value, packing_mode)
+            }
+            IntValue::Unsigned(UnsignedIntValue::U16(value)) => {
+                Self::for_unsigned(value, packing_mode)
+            }
+            IntValue::Unsigned(UnsignedIntValue::U32(value)) => {
+                Self::for_unsigned(value, packing_mode)
+            }
+            IntValue::Unsigned(UnsignedIntValue::U64(value)) => {
+                Self::for_unsigned(value, packing_mode)
+            }
+        }
+    }
+
+    /// Returns whether the associated value is signed.
+    pub fn is_signed(&self) -> bool {
+        match self {
+            Self::Compact(header) => header.is_signed,
+            Self::Extended(header) => header.is_signed,
+        }
+    }
+
     /// Returns the extended byte-width, or `None` if compact.
     pub fn extended_width(&self) -> Option<u8> {
         match self {
@@ -65,6 +100,12 @@ impl IntHeader {
         }
     }
 
+    /// Returns the header's encoded length in bytes.
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        1
+    }
+
     #[inline]
     pub(crate) fn for_int_be_bytes(
         is_signed: bool,
@@ -332,6 +373,7 @@ mod tests {
             encoder.encode_int_header(&header).unwrap();
 
             prop_assert!(encoded.len() == 1);
+            prop_assert_eq!(encoded.len(), header.encoded_len());
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
@@ -0,0 +1,114 @@
+/// The byte-width of a length, as encoded in a header's extension.
+///
+/// Valid widths range from `1` to `8` bytes (i.e. up to a `u64`).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct LenWidth(u8);
+
+impl LenWidth {
+    /// The smallest valid width (`1`).
+    pub const MIN: Self = Self(1);
+
+    /// The largest valid width (`8`).
+    pub const MAX: Self = Self(8);
+
+    /// Creates a width from `width`, returning `None` if it is outside of `1..=8`.
+    pub fn new(width: u8) -> Option<Self> {
+        (Self::MIN.0..=Self::MAX.0)
+            .contains(&width)
+            .then_some(Self(width))
+    }
+
+    /// Returns the width in bytes.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl LenWidth {
+    const WIDTH_BITS: u8 = 0b00000111;
+    const EXPONENT_BITS: u8 = 0b00000011;
+
+    /// Creates a width from a header's 3-bit `width - 1` field.
+    #[inline]
+    pub(crate) fn from_width_bits(bits: u8) -> Self {
+        Self(1 + (bits & Self::WIDTH_BITS))
+    }
+
+    /// Returns the header's 3-bit `width - 1` field.
+    #[inline]
+    pub(crate) fn width_bits(self) -> u8 {
+        self.0 - 1
+    }
+
+    /// Creates a width from a header's 2-bit `log2(width)` field.
+    #[inline]
+    pub(crate) fn from_exponent_bits(bits: u8) -> Self {
+        Self(1 << (bits & Self::EXPONENT_BITS))
+    }
+
+    /// Returns the header's 2-bit `log2(width)` field,
+    /// or `None` if the width is not a power of two.
+    #[inline]
+    pub(crate) fn exponent_bits(self) -> Option<u8> {
+        self.0
+            .is_power_of_two()
+            .then(|| self.0.trailing_zeros() as u8)
+    }
+
+    /// Returns the width of packed big-endian length `bytes`.
+    #[inline]
+    pub(crate) fn of_be_bytes(bytes: &[u8]) -> Self {
+        debug_assert!((1..=8).contains(&bytes.len()));
+
+        Self::from_width_bits((bytes.len() as u8).wrapping_sub(1))
+    }
+}
+
+impl From<LenWidth> for usize {
+    fn from(width: LenWidth) -> Self {
+        width.0 as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn new() {
+        assert_eq!(LenWidth::new(0), None);
+        assert_eq!(LenWidth::new(9), None);
+        assert_eq!(LenWidth::new(u8::MAX), None);
+
+        for width in 1..=8 {
+            assert_eq!(LenWidth::new(width).map(LenWidth::get), Some(width));
+        }
+
+        assert_eq!(LenWidth::new(1), Some(LenWidth::MIN));
+        assert_eq!(LenWidth::new(8), Some(LenWidth::MAX));
+    }
+
+    #[test]
+    fn width_bits() {
+        for bits in 0..=LenWidth::WIDTH_BITS {
+            let width = LenWidth::from_width_bits(bits);
+            assert_eq!(width.get(), bits + 1);
+            assert_eq!(width.width_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn exponent_bits() {
+        for bits in 0..=LenWidth::EXPONENT_BITS {
+            let width = LenWidth::from_exponent_bits(bits);
+            assert_eq!(width.get(), 1 << bits);
+            assert_eq!(width.exponent_bits(), Some(bits));
+        }
+
+        for width in [3, 5, 6, 7] {
+            assert_eq!(LenWidth::new(width).unwrap().exponent_bits(), None);
+        }
+    }
+}
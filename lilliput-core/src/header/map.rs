@@ -3,7 +3,7 @@ use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
 use proptest_derive::Arbitrary;
 
-use crate::config::PackingMode;
+use crate::{config::PackingMode, num::WithPackedBeBytes as _};
 
 /// Header representing a map of key-value pairs.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
@@ -59,6 +59,18 @@ impl MapHeader {
         }
     }
 
+    /// Returns the header's encoded length in bytes, for a given length `packing_mode`.
+    pub fn encoded_len(&self, packing_mode: PackingMode) -> usize {
+        match self {
+            Self::Compact(_) => 1,
+            Self::Extended(extended) => {
+                1 + extended
+                    .len()
+                    .with_packed_be_bytes(packing_mode, |bytes| bytes.len())
+            }
+        }
+    }
+
     #[inline]
     fn as_compact_len(len: usize, packing_mode: PackingMode) -> Option<u8> {
         if packing_mode.is_optimal() && len <= (Self::COMPACT_MAX_LEN as usize) {
@@ -181,10 +193,12 @@ mod tests {
         fn encode_decode_roundtrip(header in MapHeader::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
             let writer = VecWriter::new(&mut encoded);
+            let packing_mode = config.lengths.packing;
             let mut encoder = Encoder::new(writer, config);
             encoder.encode_map_header(&header).unwrap();
 
             prop_assert!(encoded.len() <= 1 + 8);
+            prop_assert_eq!(encoded.len(), header.encoded_len(packing_mode));
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
@@ -14,6 +14,12 @@ impl NullHeader {
     pub fn new() -> Self {
         Self
     }
+
+    /// Returns the header's encoded length in bytes.
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        1
+    }
 }
 
 impl NullHeader {
@@ -44,6 +50,7 @@ mod tests {
             encoder.encode_null_header(&header).unwrap();
 
             prop_assert!(encoded.len() == 1);
+            prop_assert_eq!(encoded.len(), header.encoded_len());
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
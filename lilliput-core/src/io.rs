@@ -1,6 +1,7 @@
 //! I/O related stuff.
 
-use std::ops::Deref;
+use alloc::vec::Vec;
+use core::ops::Deref;
 
 use crate::error::{Error, Result};
 
@@ -39,6 +40,9 @@ pub trait Read<'r> {
     /// Returns the next byte without advancing the position.
     fn peek_one(&mut self) -> Result<u8>;
 
+    /// Returns the next `len` bytes without advancing the position.
+    fn peek(&mut self, len: usize) -> Result<&[u8]>;
+
     /// Skips the next byte, advancing the position.
     fn skip_one(&mut self) -> Result<()> {
         match self.read_one() {
@@ -94,22 +98,61 @@ pub trait Read<'r> {
 
     /// Reads the next `len` bytes into `buf`, advancing the position.
     fn read_into(&mut self, buf: &mut [u8]) -> Result<()>;
+
+    /// Returns the number of remaining unread bytes,
+    /// or `None` if the reader is unable to tell (e.g. for streaming readers).
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the remaining unread bytes without advancing the position,
+    /// or `None` if the reader doesn't hold them in memory (e.g. for streaming readers).
+    ///
+    /// This allows for scanning ahead (e.g. for skipping containers in a single jump),
+    /// without reading (or copying) any bytes.
+    fn remaining_slice(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// Moves the position to the absolute byte offset `pos`,
+    /// relative to the start of the reader's input.
+    ///
+    /// Seeking past the end of the input fails with `ErrorCode::UnexpectedEndOfFile`.
+    /// Readers unable to seek (e.g. streaming readers) fail with `ErrorCode::Uncategorized`.
+    fn seek_to(&mut self, pos: usize) -> Result<()> {
+        let _ = pos;
+
+        Err(Error::uncategorized(
+            "reader does not support seeking",
+            None,
+        ))
+    }
 }
 
+// MARK: - BorrowingRead
+
+/// A marker trait for readers which always borrow from their input,
+/// i.e. whose `Read::read` always returns a `Reference::Borrowed`.
+pub trait BorrowingRead<'r>: Read<'r> {}
+
 // MARK: - StdIoReader
 
 /// A wrapper around instances of `std::io::Read`.
+///
+/// *This type is only available if lilliput_core is built with the `"std"` feature.*
+#[cfg(feature = "std")]
 pub struct StdIoReader<R> {
     reader: R,
-    peeked: Option<u8>,
+    peeked: Vec<u8>,
 }
 
+#[cfg(feature = "std")]
 impl<R> StdIoReader<R> {
     /// Creates an instance from a `reader`.
     pub fn new(reader: R) -> Self {
         Self {
             reader,
-            peeked: None,
+            peeked: Vec::new(),
         }
     }
 
@@ -119,30 +162,40 @@ impl<R> StdIoReader<R> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<'r, R> Read<'r> for StdIoReader<R>
 where
     R: std::io::Read,
 {
     fn peek_one(&mut self) -> Result<u8> {
-        if let Some(byte) = self.peeked {
-            return Ok(byte);
-        }
-
-        let byte = self.read_one()?;
-        self.peeked = Some(byte);
-
-        Ok(byte)
+        self.peek(1).map(|bytes| bytes[0])
     }
 
-    fn read_one(&mut self) -> Result<u8> {
-        if let Some(byte) = self.peeked.take() {
-            return Ok(byte);
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        let mut filled = self.peeked.len();
+
+        if filled < len {
+            self.peeked.resize(len, 0b0);
+
+            // Unlike `read_exact` this retains any bytes read before failing,
+            // keeping them available to subsequent peeks and reads:
+            while filled < len {
+                match self.reader.read(&mut self.peeked[filled..]) {
+                    Ok(0) => {
+                        self.peeked.truncate(filled);
+                        return Err(Error::end_of_file());
+                    }
+                    Ok(read) => filled += read,
+                    Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                    Err(err) => {
+                        self.peeked.truncate(filled);
+                        return Err(Error::io(err));
+                    }
+                }
+            }
         }
 
-        let mut bytes: [u8; 1] = [0b0];
-        self.read_into(&mut bytes)?;
-
-        Ok(bytes[0])
+        Ok(&self.peeked[..len])
     }
 
     fn read<'s>(
@@ -159,9 +212,10 @@ where
             return Ok(Reference::Copied(&[]));
         }
 
-        if let Some(byte) = self.peeked.take() {
-            scratch.resize(1, byte);
-            total_read += 1;
+        if !self.peeked.is_empty() {
+            let peeked_len = len.min(self.peeked.len());
+            scratch.extend(self.peeked.drain(..peeked_len));
+            total_read += peeked_len;
         }
 
         while total_read < len {
@@ -191,12 +245,9 @@ where
             return Ok(());
         }
 
-        let offset = if let Some(byte) = self.peeked.take() {
-            buf[0] = byte;
-            1
-        } else {
-            0
-        };
+        let offset = buf.len().min(self.peeked.len());
+        buf[..offset].copy_from_slice(&self.peeked[..offset]);
+        self.peeked.drain(..offset);
 
         self.reader
             .read_exact(&mut buf[offset..])
@@ -233,12 +284,20 @@ impl<'r> Read<'r> for SliceReader<'r> {
         Ok(self.slice[self.pos])
     }
 
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        if len > self.slice.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        Ok(&self.slice[self.pos..(self.pos + len)])
+    }
+
     fn read<'s>(
         &'s mut self,
         len: usize,
         _scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'r, 's, [u8]>> {
-        if self.pos + len > self.slice.len() {
+        if len > self.slice.len() - self.pos {
             return Err(Error::end_of_file());
         }
 
@@ -251,7 +310,7 @@ impl<'r> Read<'r> for SliceReader<'r> {
     fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
         let len = buf.len();
 
-        if self.pos + len > self.slice.len() {
+        if len > self.slice.len() - self.pos {
             return Err(Error::end_of_file());
         }
 
@@ -262,6 +321,326 @@ impl<'r> Read<'r> for SliceReader<'r> {
 
         Ok(())
     }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.slice.len() - self.pos)
+    }
+
+    fn remaining_slice(&self) -> Option<&[u8]> {
+        Some(&self.slice[self.pos..])
+    }
+
+    fn seek_to(&mut self, pos: usize) -> Result<()> {
+        if pos > self.slice.len() {
+            return Err(Error::end_of_file());
+        }
+
+        self.pos = pos;
+
+        Ok(())
+    }
+}
+
+impl<'r> BorrowingRead<'r> for SliceReader<'r> {}
+
+// MARK: - BytesReader
+
+/// A wrapper around instances of `bytes::Bytes`.
+///
+/// Byte arrays can be decoded from it without copying,
+/// via `Decoder::decode_bytes_shared()`.
+///
+/// *This type is only available if lilliput_core is built with the `"bytes"` feature.*
+#[cfg(feature = "bytes")]
+pub struct BytesReader {
+    bytes: bytes::Bytes,
+    pos: usize,
+}
+
+#[cfg(feature = "bytes")]
+impl BytesReader {
+    /// Creates an instance from `bytes`.
+    pub fn new(bytes: bytes::Bytes) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Returns the current position in the buffer.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the internal buffer, consuming `self`.
+    pub fn into_bytes(self) -> bytes::Bytes {
+        self.bytes
+    }
+
+    /// Returns a slice of the internal buffer for `range`, sharing its memory.
+    pub(crate) fn slice(&self, range: core::ops::Range<usize>) -> bytes::Bytes {
+        self.bytes.slice(range)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'r> Read<'r> for BytesReader {
+    fn peek_one(&mut self) -> Result<u8> {
+        if self.pos >= self.bytes.len() {
+            return Err(Error::end_of_file());
+        }
+
+        Ok(self.bytes[self.pos])
+    }
+
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        if len > self.bytes.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        Ok(&self.bytes[self.pos..(self.pos + len)])
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        if len > self.bytes.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        self.pos += len;
+
+        Ok(())
+    }
+
+    fn read<'s>(
+        &'s mut self,
+        len: usize,
+        _scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'r, 's, [u8]>> {
+        if len > self.bytes.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        let range = self.pos..(self.pos + len);
+        self.pos += len;
+
+        Ok(Reference::Copied(&self.bytes[range]))
+    }
+
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+        let len = buf.len();
+
+        if len > self.bytes.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        let range = self.pos..(self.pos + len);
+        self.pos += len;
+
+        buf.copy_from_slice(&self.bytes[range]);
+
+        Ok(())
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.bytes.len() - self.pos)
+    }
+
+    fn remaining_slice(&self) -> Option<&[u8]> {
+        Some(&self.bytes[self.pos..])
+    }
+
+    fn seek_to(&mut self, pos: usize) -> Result<()> {
+        if pos > self.bytes.len() {
+            return Err(Error::end_of_file());
+        }
+
+        self.pos = pos;
+
+        Ok(())
+    }
+}
+
+// MARK: - TransformReader
+
+/// A wrapper around instances of `Read`, applying a streaming `transform` to all bytes read.
+///
+/// The transform gets called exactly once for each byte, in order,
+/// with chunks of consecutive bytes (e.g. for decrypting a stream cipher).
+/// It must preserve the length of its input, transforming in place.
+///
+/// As the transformed bytes can't be borrowed from the input,
+/// `Read::read` always returns a `Reference::Copied`.
+pub struct TransformReader<R, F> {
+    reader: R,
+    transform: F,
+    peeked: Vec<u8>,
+}
+
+impl<R, F> TransformReader<R, F> {
+    /// Creates an instance from a `reader` and a `transform`.
+    pub fn new(reader: R, transform: F) -> Self {
+        Self {
+            reader,
+            transform,
+            peeked: Vec::new(),
+        }
+    }
+
+    /// Returns the internal `reader`, consuming `self`.
+    ///
+    /// Any bytes peeked (and thus already read from the internal reader) get discarded.
+    pub fn into_reader(self) -> R {
+        self.reader
+    }
+}
+
+impl<'r, R, F> Read<'r> for TransformReader<R, F>
+where
+    R: Read<'r>,
+    F: FnMut(&mut [u8]),
+{
+    fn peek_one(&mut self) -> Result<u8> {
+        self.peek(1).map(|bytes| bytes[0])
+    }
+
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        let old_len = self.peeked.len();
+
+        if old_len < len {
+            self.peeked.resize(len, 0b0);
+
+            // Unlike a single `read_into` this retains (and transforms) any bytes read
+            // before failing, keeping them available to subsequent peeks and reads.
+            // Readers able to tell their remaining length get read from in bulk,
+            // all others one byte at a time:
+            let mut filled = old_len;
+
+            let result = loop {
+                if filled == len {
+                    break Ok(());
+                }
+
+                let chunk_len = match self.reader.remaining() {
+                    Some(remaining) => remaining.clamp(1, len - filled),
+                    None => 1,
+                };
+
+                match self
+                    .reader
+                    .read_into(&mut self.peeked[filled..][..chunk_len])
+                {
+                    Ok(()) => filled += chunk_len,
+                    Err(err) => break Err(err),
+                }
+            };
+
+            self.peeked.truncate(filled);
+            (self.transform)(&mut self.peeked[old_len..]);
+
+            result?;
+        }
+
+        Ok(&self.peeked[..len])
+    }
+
+    fn read<'s>(
+        &'s mut self,
+        len: usize,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'r, 's, [u8]>> {
+        // Copied from the default buffer length of `std::io::BufReader`:
+        const MAX_CHUNK_LENGTH: usize = 8192;
+
+        let mut total_read = 0;
+
+        while total_read < len {
+            let to_read = (len - total_read).min(MAX_CHUNK_LENGTH);
+
+            let old_len = scratch.len();
+            scratch.resize(old_len + to_read, 0);
+
+            self.read_into(&mut scratch[old_len..])?;
+
+            total_read += to_read;
+        }
+
+        Ok(Reference::Copied(scratch))
+    }
+
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+        let offset = buf.len().min(self.peeked.len());
+        buf[..offset].copy_from_slice(&self.peeked[..offset]);
+        self.peeked.drain(..offset);
+
+        let rest = &mut buf[offset..];
+
+        if !rest.is_empty() {
+            self.reader.read_into(rest)?;
+            (self.transform)(rest);
+        }
+
+        Ok(())
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.reader
+            .remaining()
+            .map(|remaining| remaining + self.peeked.len())
+    }
+}
+
+// MARK: - ZstdReader
+
+/// A wrapper around instances of `std::io::Read`, decompressing zstd-compressed input on the fly.
+///
+/// As the decompressed bytes can't be borrowed from the input,
+/// `Read::read` always returns a `Reference::Copied`.
+///
+/// *This type is only available if lilliput_core is built with the `"zstd"` feature.*
+#[cfg(feature = "zstd")]
+pub struct ZstdReader<R>
+where
+    R: std::io::Read,
+{
+    reader: StdIoReader<zstd::stream::read::Decoder<'static, std::io::BufReader<R>>>,
+}
+
+#[cfg(feature = "zstd")]
+impl<R> ZstdReader<R>
+where
+    R: std::io::Read,
+{
+    /// Creates an instance from a `reader` of compressed input.
+    pub fn new(reader: R) -> Result<Self> {
+        let decoder = zstd::stream::read::Decoder::new(reader).map_err(Error::io)?;
+
+        Ok(Self {
+            reader: StdIoReader::new(decoder),
+        })
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<'r, R> Read<'r> for ZstdReader<R>
+where
+    R: std::io::Read,
+{
+    fn peek_one(&mut self) -> Result<u8> {
+        self.reader.peek_one()
+    }
+
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        self.reader.peek(len)
+    }
+
+    fn read<'s>(
+        &'s mut self,
+        len: usize,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'r, 's, [u8]>> {
+        self.reader.read(len, scratch)
+    }
+
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reader.read_into(buf)
+    }
 }
 
 // MARK: - Write
@@ -276,29 +655,55 @@ pub trait Write {
     /// Flushes this output stream, ensuring that all intermediately
     /// buffered contents reach their destination.
     fn flush(&mut self) -> Result<()>;
+
+    /// Overwrites previously written bytes with `buf`, starting `distance` bytes
+    /// before the current end of output, without moving the current position.
+    ///
+    /// Rewriting beyond the bytes written so far fails with `ErrorCode::BufferOverflow`.
+    /// Writers unable to rewrite (e.g. streaming writers) fail with `ErrorCode::Uncategorized`.
+    fn rewrite(&mut self, distance: usize, buf: &[u8]) -> Result<()> {
+        let _ = (distance, buf);
+
+        Err(Error::uncategorized(
+            "writer does not support rewriting",
+            None,
+        ))
+    }
 }
 
-// MARK: - MutSliceWriter
+// MARK: - SliceWriter
 
 /// A wrapper around instances of `&mut [u8]`.
-pub struct MutSliceWriter<'w> {
+///
+/// Writes that don't fit into the remaining slice fail with `ErrorCode::BufferOverflow`,
+/// without writing any of their bytes.
+pub struct SliceWriter<'w> {
     slice: &'w mut [u8],
     pos: usize,
 }
 
-impl<'w> MutSliceWriter<'w> {
+impl<'w> SliceWriter<'w> {
     /// Creates a writer from a mutable `slice`.
-    pub fn new(slice: &'w mut Vec<u8>) -> Self {
+    pub fn new(slice: &'w mut [u8]) -> Self {
         Self { slice, pos: 0 }
     }
+
+    /// Returns the number of bytes written so far.
+    pub fn bytes_written(&self) -> usize {
+        self.pos
+    }
 }
 
-impl Write for MutSliceWriter<'_> {
+/// A wrapper around instances of `&mut [u8]`.
+#[deprecated(note = "use `SliceWriter` instead")]
+pub type MutSliceWriter<'w> = SliceWriter<'w>;
+
+impl Write for SliceWriter<'_> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
         let len = buf.len();
 
-        if self.pos + len > self.slice.len() {
-            return Err(Error::end_of_file());
+        if len > self.slice.len() - self.pos {
+            return Err(Error::buffer_overflow(Some(self.pos)));
         }
 
         let range = self.pos..(self.pos + len);
@@ -312,6 +717,17 @@ impl Write for MutSliceWriter<'_> {
     fn flush(&mut self) -> Result<()> {
         Ok(())
     }
+
+    fn rewrite(&mut self, distance: usize, buf: &[u8]) -> Result<()> {
+        if distance > self.pos || buf.len() > distance {
+            return Err(Error::buffer_overflow(Some(self.pos)));
+        }
+
+        let start = self.pos - distance;
+        self.slice[start..(start + buf.len())].copy_from_slice(buf);
+
+        Ok(())
+    }
 }
 
 // MARK: - VecWriter
@@ -342,15 +758,126 @@ impl Write for VecWriter<'_> {
     fn flush(&mut self) -> Result<()> {
         Ok(())
     }
+
+    fn rewrite(&mut self, distance: usize, buf: &[u8]) -> Result<()> {
+        let end = self.vec.len();
+
+        if distance > end || buf.len() > distance {
+            return Err(Error::buffer_overflow(Some(end)));
+        }
+
+        let start = end - distance;
+        self.vec[start..(start + buf.len())].copy_from_slice(buf);
+
+        Ok(())
+    }
+}
+
+// MARK: - CountingWriter
+
+/// A writer that discards all bytes written to it, only counting them.
+#[derive(Default)]
+pub struct CountingWriter {
+    count: usize,
+}
+
+impl CountingWriter {
+    /// Creates a writer with a count of zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.count += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn rewrite(&mut self, distance: usize, buf: &[u8]) -> Result<()> {
+        if distance > self.count || buf.len() > distance {
+            return Err(Error::buffer_overflow(Some(self.count)));
+        }
+
+        // Rewriting doesn't change the number of bytes written:
+        Ok(())
+    }
+}
+
+// MARK: - HashingWriter
+
+/// A wrapper around instances of `Write`, feeding all bytes written to a `hasher`.
+///
+/// Only bytes actually accepted by the internal writer get hashed,
+/// making the hash match its contents, without a second pass over them.
+pub struct HashingWriter<W, H> {
+    writer: W,
+    hasher: H,
+}
+
+impl<W, H> HashingWriter<W, H> {
+    /// Creates an instance from a `writer` and a `hasher`.
+    pub fn new(writer: W, hasher: H) -> Self {
+        Self { writer, hasher }
+    }
+
+    /// Returns a reference to the internal `hasher`.
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns the internal `writer` and `hasher`, consuming `self`.
+    pub fn into_parts(self) -> (W, H) {
+        (self.writer, self.hasher)
+    }
+}
+
+impl<W, H> HashingWriter<W, H>
+where
+    H: core::hash::Hasher,
+{
+    /// Returns the hash of the bytes written so far.
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+
+impl<W, H> Write for HashingWriter<W, H>
+where
+    W: Write,
+    H: core::hash::Hasher,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.hasher.write(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
 }
 
 // MARK: - StdIoBufWriter
 
 /// A wrapper around instances of `std::io::Write`.
+///
+/// *This type is only available if lilliput_core is built with the `"std"` feature.*
+#[cfg(feature = "std")]
 pub struct StdIoWriter<W> {
     writer: W,
 }
 
+#[cfg(feature = "std")]
 impl<W> StdIoWriter<W> {
     /// Creates an instance from a `reader`.
     pub fn new(writer: W) -> Self {
@@ -363,6 +890,7 @@ impl<W> StdIoWriter<W> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<W> Write for StdIoWriter<W>
 where
     W: std::io::Write,
@@ -376,15 +904,123 @@ where
     }
 }
 
+// MARK: - ZstdWriter
+
+/// A wrapper around instances of `std::io::Write`, compressing output with zstd on the fly.
+///
+/// The compressed output is only complete once `ZstdWriter::finish` has been called.
+///
+/// *This type is only available if lilliput_core is built with the `"zstd"` feature.*
+#[cfg(feature = "zstd")]
+pub struct ZstdWriter<W>
+where
+    W: std::io::Write,
+{
+    encoder: zstd::stream::write::Encoder<'static, W>,
+}
+
+#[cfg(feature = "zstd")]
+impl<W> ZstdWriter<W>
+where
+    W: std::io::Write,
+{
+    /// Creates an instance from a `writer`, compressing with the given `level`
+    /// (with `0` selecting zstd's default level).
+    pub fn new(writer: W, level: i32) -> Result<Self> {
+        let encoder = zstd::stream::write::Encoder::new(writer, level).map_err(Error::io)?;
+
+        Ok(Self { encoder })
+    }
+
+    /// Completes the compressed output, returning the internal `writer`, consuming `self`.
+    pub fn finish(self) -> Result<W> {
+        self.encoder.finish().map_err(Error::io)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<W> Write for ZstdWriter<W>
+where
+    W: std::io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        std::io::Write::write(&mut self.encoder, buf).map_err(Error::io)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        std::io::Write::flush(&mut self.encoder).map_err(Error::io)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::error::ErrorCode;
 
     use super::*;
 
+    #[cfg(feature = "std")]
     mod std_io_reader {
         use super::*;
 
+        #[test]
+        fn peek() {
+            let slice: &[u8] = &[1, 2, 3, 4, 5];
+            let mut reader = StdIoReader::new(slice);
+            let mut scratch = Vec::new();
+
+            assert_eq!(reader.peek(0).unwrap(), &[]);
+            assert_eq!(reader.peek(2).unwrap(), &[1, 2]);
+            assert_eq!(reader.peek(1).unwrap(), &[1]);
+            assert_eq!(reader.peek(3).unwrap(), &[1, 2, 3]);
+
+            assert_eq!(reader.read_one().unwrap(), 1);
+            assert_eq!(reader.peek(2).unwrap(), &[2, 3]);
+
+            assert_eq!(reader.read(3, &mut scratch).unwrap().as_ref(), &[2, 3, 4]);
+            assert_eq!(reader.peek(1).unwrap(), &[5]);
+
+            assert_eq!(
+                reader.peek(2).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+
+            assert_eq!(reader.peek(1).unwrap(), &[5]);
+        }
+
+        #[test]
+        fn peek_past_end_retains_partial() {
+            let slice: &[u8] = &[5];
+            let mut reader = StdIoReader::new(slice);
+
+            assert_eq!(
+                reader.peek(2).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+
+            assert_eq!(reader.peek_one().unwrap(), 5);
+            assert_eq!(reader.read_one().unwrap(), 5);
+        }
+
+        #[test]
+        fn peek_short_reads() {
+            /// A reader returning at most one byte per read.
+            struct Trickle<'a>(&'a [u8]);
+
+            impl std::io::Read for Trickle<'_> {
+                fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                    let len = buf.len().min(self.0.len()).min(1);
+                    buf[..len].copy_from_slice(&self.0[..len]);
+                    self.0 = &self.0[len..];
+                    Ok(len)
+                }
+            }
+
+            let mut reader = StdIoReader::new(Trickle(&[1, 2, 3]));
+
+            assert_eq!(reader.peek(3).unwrap(), &[1, 2, 3]);
+            assert_eq!(reader.read_one().unwrap(), 1);
+        }
+
         #[test]
         fn peek_one() {
             let slice: &[u8] = &[1, 2, 3, 4, 5];
@@ -526,6 +1162,55 @@ mod test {
     mod slice_reader {
         use super::*;
 
+        #[test]
+        fn peek() {
+            let slice: &[u8] = &[1, 2, 3, 4, 5];
+            let mut reader = SliceReader::new(slice);
+            let mut scratch = Vec::new();
+
+            assert_eq!(reader.peek(0).unwrap(), &[]);
+            assert_eq!(reader.peek(2).unwrap(), &[1, 2]);
+            assert_eq!(reader.peek(1).unwrap(), &[1]);
+            assert_eq!(reader.peek(3).unwrap(), &[1, 2, 3]);
+
+            assert_eq!(reader.read_one().unwrap(), 1);
+            assert_eq!(reader.peek(2).unwrap(), &[2, 3]);
+
+            assert_eq!(reader.read(3, &mut scratch).unwrap().as_ref(), &[2, 3, 4]);
+            assert_eq!(reader.peek(1).unwrap(), &[5]);
+
+            assert_eq!(
+                reader.peek(2).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+
+            assert_eq!(reader.peek(1).unwrap(), &[5]);
+        }
+
+        #[test]
+        fn peek_huge_len() {
+            let slice: &[u8] = &[1, 2, 3, 4, 5];
+            let mut reader = SliceReader::new(slice);
+            let mut scratch = Vec::new();
+
+            assert_eq!(reader.read_one().unwrap(), 1);
+
+            assert_eq!(
+                reader.peek(usize::MAX).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+            assert_eq!(
+                reader.read(usize::MAX, &mut scratch).err().unwrap().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+            assert_eq!(
+                reader.skip(usize::MAX).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+
+            assert_eq!(reader.peek(1).unwrap(), &[2]);
+        }
+
         #[test]
         fn peek_one() {
             let slice: &[u8] = &[1, 2, 3, 4, 5];
@@ -646,4 +1331,292 @@ mod test {
             );
         }
     }
+
+    #[cfg(feature = "bytes")]
+    mod bytes_reader {
+        use super::*;
+
+        #[test]
+        fn peek_huge_len() {
+            let mut reader = BytesReader::new(bytes::Bytes::from_static(&[1, 2, 3, 4, 5]));
+            let mut scratch = Vec::new();
+
+            assert_eq!(reader.read_one().unwrap(), 1);
+
+            assert_eq!(
+                reader.peek(usize::MAX).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+            assert_eq!(
+                reader.read(usize::MAX, &mut scratch).err().unwrap().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+            assert_eq!(
+                reader.skip(usize::MAX).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+
+            assert_eq!(reader.peek(1).unwrap(), &[2]);
+        }
+    }
+
+    mod slice_writer {
+        use crate::{encoder::Encoder, value::Value};
+
+        use super::*;
+
+        fn subject() -> Value {
+            [
+                Value::from("lilliput"),
+                Value::from(42_u8),
+                Value::from(true),
+            ]
+            .into_iter()
+            .collect()
+        }
+
+        fn encode_into(buf: &mut [u8]) -> (Result<()>, usize) {
+            let mut encoder = Encoder::from_writer(SliceWriter::new(buf));
+            let result = encoder.encode_value(&subject());
+            (result, encoder.into_writer().bytes_written())
+        }
+
+        fn encoded_len() -> usize {
+            let mut encoded: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+            encoder.encode_value(&subject()).unwrap();
+            encoded.len()
+        }
+
+        #[test]
+        fn write() {
+            let mut buf = [0b0; 4];
+            let mut writer = SliceWriter::new(&mut buf);
+
+            assert_eq!(writer.write(&[1, 2]).unwrap(), 2);
+            assert_eq!(writer.write(&[]).unwrap(), 0);
+            assert_eq!(writer.bytes_written(), 2);
+
+            let err = writer.write(&[3, 4, 5]).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::BufferOverflow);
+            assert_eq!(err.pos(), Some(2));
+            assert_eq!(writer.bytes_written(), 2);
+
+            assert_eq!(writer.write(&[3, 4]).unwrap(), 2);
+            assert_eq!(writer.bytes_written(), 4);
+            assert_eq!(buf, [1, 2, 3, 4]);
+        }
+
+        #[test]
+        fn rewrite() {
+            let mut buf = [0b0; 4];
+            let mut writer = SliceWriter::new(&mut buf);
+
+            writer.write(&[1, 2, 3]).unwrap();
+            writer.rewrite(2, &[7]).unwrap();
+            assert_eq!(writer.bytes_written(), 3);
+
+            let err = writer.rewrite(4, &[7]).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::BufferOverflow);
+
+            let err = writer.rewrite(1, &[7, 7]).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::BufferOverflow);
+
+            writer.write(&[4]).unwrap();
+            assert_eq!(buf, [1, 7, 3, 4]);
+        }
+
+        #[test]
+        fn exact_fit() {
+            let len = encoded_len();
+            let mut buf = vec![0b0; len];
+
+            let (result, bytes_written) = encode_into(&mut buf);
+            result.unwrap();
+            assert_eq!(bytes_written, len);
+        }
+
+        #[test]
+        fn one_byte_short() {
+            let len = encoded_len();
+            let mut buf = vec![0b0; len - 1];
+
+            let (result, bytes_written) = encode_into(&mut buf);
+            assert_eq!(result.unwrap_err().code(), ErrorCode::BufferOverflow);
+            assert!(bytes_written < len);
+        }
+
+        #[test]
+        fn one_byte_over() {
+            let len = encoded_len();
+            let mut buf = vec![0b0; len + 1];
+
+            let (result, bytes_written) = encode_into(&mut buf);
+            result.unwrap();
+            assert_eq!(bytes_written, len);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod hashing_writer {
+        use core::hash::Hasher;
+        use std::collections::hash_map::DefaultHasher;
+
+        use crate::{encoder::Encoder, value::Value};
+
+        use super::*;
+
+        #[test]
+        fn matches_separate_hash() {
+            let value: Value = [
+                Value::from("lilliput"),
+                Value::from(42_u8),
+                Value::from(vec![1_u8, 2, 3]),
+            ]
+            .into_iter()
+            .collect();
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = HashingWriter::new(VecWriter::new(&mut encoded), DefaultHasher::new());
+            let mut encoder = Encoder::from_writer(writer);
+            encoder.encode_value(&value).unwrap();
+            let hash = encoder.into_writer().finish();
+
+            let mut expected: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::from_writer(VecWriter::new(&mut expected));
+            encoder.encode_value(&value).unwrap();
+            assert_eq!(encoded, expected);
+
+            let mut hasher = DefaultHasher::new();
+            hasher.write(&expected);
+            assert_eq!(hash, hasher.finish());
+        }
+
+        #[test]
+        fn partial_write() {
+            let mut buf = [0b0; 2];
+            let mut writer = HashingWriter::new(SliceWriter::new(&mut buf), DefaultHasher::new());
+
+            assert_eq!(writer.write(&[1, 2]).unwrap(), 2);
+            assert!(writer.write(&[3]).is_err());
+
+            let mut hasher = DefaultHasher::new();
+            hasher.write(&[1, 2]);
+            assert_eq!(writer.finish(), hasher.finish());
+        }
+    }
+
+    mod transform_reader {
+        use crate::{decoder::Decoder, encoder::Encoder, value::Value};
+
+        use super::*;
+
+        /// A toy stream cipher, whose keystream depends on the position.
+        fn cipher() -> impl FnMut(&mut [u8]) {
+            let mut key: u8 = 0x5a;
+            move |bytes: &mut [u8]| {
+                for byte in bytes {
+                    *byte ^= key;
+                    key = key.wrapping_add(1);
+                }
+            }
+        }
+
+        #[test]
+        fn peek_and_read() {
+            let mut encrypted = vec![1, 2, 3, 4, 5];
+            cipher()(&mut encrypted);
+
+            let mut reader = TransformReader::new(SliceReader::new(&encrypted), cipher());
+            let mut scratch = Vec::new();
+
+            assert_eq!(reader.remaining(), Some(5));
+            assert_eq!(reader.peek(2).unwrap(), &[1, 2]);
+            assert_eq!(reader.peek(1).unwrap(), &[1]);
+            assert_eq!(reader.peek(3).unwrap(), &[1, 2, 3]);
+            assert_eq!(reader.remaining(), Some(5));
+
+            assert_eq!(reader.read_one().unwrap(), 1);
+            assert_eq!(
+                reader.read(3, &mut scratch).unwrap(),
+                Reference::Copied(&[2, 3, 4][..])
+            );
+            assert_eq!(reader.remaining(), Some(1));
+
+            assert_eq!(
+                reader.peek(2).err().unwrap().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+            assert_eq!(reader.read_one().unwrap(), 5);
+            assert_eq!(reader.remaining(), Some(0));
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn peek_past_end_retains_partial() {
+            let mut encrypted = vec![1, 2, 3];
+            cipher()(&mut encrypted);
+
+            let mut reader = TransformReader::new(StdIoReader::new(encrypted.as_slice()), cipher());
+
+            assert_eq!(
+                reader.peek(5).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+
+            assert_eq!(reader.peek(3).unwrap(), &[1, 2, 3]);
+            assert_eq!(reader.read_one().unwrap(), 1);
+            assert_eq!(reader.peek(2).unwrap(), &[2, 3]);
+        }
+
+        #[test]
+        fn roundtrip() {
+            let value: Value = [
+                Value::from("lilliput"),
+                Value::from(vec![42_u8; 100]),
+                Value::from(-1_i64),
+            ]
+            .into_iter()
+            .collect();
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+            encoder.encode_value(&value).unwrap();
+
+            let mut encrypted = encoded.clone();
+            cipher()(&mut encrypted);
+            assert_ne!(encrypted, encoded);
+
+            let reader = TransformReader::new(SliceReader::new(&encrypted), cipher());
+            let mut decoder = Decoder::from_reader(reader);
+            assert_eq!(decoder.decode_value().unwrap(), value);
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    mod zstd_reader {
+        use crate::{decoder::Decoder, encoder::Encoder, value::Value};
+
+        use super::*;
+
+        #[test]
+        fn roundtrip() {
+            let value: Value = [
+                Value::from("lilliput"),
+                Value::from(vec![42_u8; 1000]),
+                Value::from(-1_i64),
+            ]
+            .into_iter()
+            .collect();
+
+            let mut encoder = Encoder::from_writer(ZstdWriter::new(Vec::new(), 0).unwrap());
+            encoder.encode_value(&value).unwrap();
+            let compressed = encoder.into_writer().finish().unwrap();
+            assert!(compressed.len() < 1000);
+
+            let reader = ZstdReader::new(compressed.as_slice()).unwrap();
+            let mut decoder = Decoder::from_reader(reader);
+            assert_eq!(decoder.decode_value().unwrap(), value);
+        }
+    }
 }
@@ -1,5 +1,39 @@
 //! Low-level implementation of encoding/decoding logic for lilliput format.
+//!
+//! # Features
+//!
+//! Without the `"std"` feature the crate is `#![no_std]` and only depends on `core` and `alloc`,
+//! which is always required (there is no allocation-free configuration of the crate).
+//!
+//! - `"std"` (enabled by default): support for `std::io`
+//!   (`StdIoReader`, `StdIoWriter`, `Error::io`), as well as `std::error::Error` for `Error`.
+//! - `"alloc"` (deprecated): no-op, kept for compatibility, as `alloc` is always required.
+//! - `"allocator_api"`: support for decoding collections into custom allocators
+//!   (e.g. `Decoder::decode_seq_in`), via the unstable `allocator_api` (requires a nightly compiler).
+//! - `"bytes"`: support for decoding byte arrays as `bytes::Bytes`, sharing memory with the input (`BytesReader`).
+//! - `"chrono"`: support for encoding/decoding `chrono::DateTime<Utc>` timestamps.
+//! - `"digest"`: support for portable content digests of values (`Value::stable_hash`), via the `sha2` crate.
+//! - `"fuzzing"`: support for fuzz targets, checking the encoding's round-trip invariants (`fuzz_roundtrip`).
+//! - `"subtle"`: support for comparing byte values in constant time (`BytesValue::ct_eq`), via `subtle`.
+//! - `"testing"`: support for property-testing code handling values,
+//!   via `proptest::arbitrary::Arbitrary` impls (e.g. `Value::arbitrary_with(ValueArbitraryParameters)`).
+//! - `"time"`: support for encoding/decoding `time::OffsetDateTime` timestamps.
+//! - `"uuid"`: support for encoding/decoding `uuid::Uuid`s as 16-byte byte arrays.
+//! - `"zstd"`: support for transparently (de)compressing encoded values with zstd (`ZstdReader`, `ZstdWriter`).
+//!
+//! Decoding headers and borrowing strings/bytes from a `SliceReader`
+//! (e.g. via `Decoder::decode_str` or `Decoder::decode_bytes`) does not allocate,
+//! while APIs returning owned buffers (e.g. `Decoder::decode_bytes_buf`,
+//! `Decoder::decode_string`, or `Decoder::decode_value`) require an allocator.
+//! Errors are currently always heap-allocated.
+//!
+//! Likewise decoding scalars (e.g. `Decoder::decode_u32`, `Decoder::decode_i64`,
+//! `Decoder::decode_f32`, or `Decoder::decode_bool`) does not allocate,
+//! with each decoding exactly one value of the expected type (failing otherwise),
+//! which allows for hand-written decoders of fixed schemas into stack locals.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 #![warn(missing_docs)]
 
 extern crate alloc;
@@ -19,6 +53,12 @@ pub mod value;
 mod binary;
 mod sealed;
 
+#[cfg(feature = "fuzzing")]
+mod fuzzing;
+
+#[cfg(feature = "fuzzing")]
+pub use self::fuzzing::fuzz_roundtrip;
+
 #[doc(hidden)]
 pub(crate) mod num;
 
@@ -33,6 +73,23 @@ pub mod plumbing {
 }
 
 /// The crates's prelude.
+///
+/// Re-exports the commonly used types and traits (including `Read` and `Write`),
+/// for encoding and decoding values with a single import:
+///
+/// ```
+/// use lilliput_core::prelude::*;
+///
+/// let value = Value::from_iter([Value::from(42_u8), Value::from("lilliput")]);
+///
+/// let mut encoded: Vec<u8> = Vec::new();
+/// let mut encoder = Encoder::new(VecWriter::new(&mut encoded), EncoderConfig::default());
+/// encoder.encode_value(&value)?;
+///
+/// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+/// assert_eq!(decoder.decode_value()?, value);
+/// # Ok::<(), Error>(())
+/// ```
 pub mod prelude {
     pub use crate::{
         config::*, decoder::*, encoder::*, error::Error, header::*, io::*, marker::*, value::*,
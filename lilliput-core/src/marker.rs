@@ -1,10 +1,12 @@
 //! Value type markers.
 
+use alloc::{format, string::ToString};
+
 use crate::{
-    error::Expectation,
+    error::{Error, Expectation},
     header::{
-        BoolHeader, BytesHeader, FloatHeader, IntHeader, MapHeader, NullHeader, SeqHeader,
-        StringHeader, UnitHeader,
+        BoolHeader, BytesHeader, ExtHeader, FloatHeader, IntHeader, MapHeader, NullHeader,
+        SeqHeader, StringHeader, UnitHeader,
     },
 };
 
@@ -30,10 +32,12 @@ pub enum Marker {
     Unit = 0b00000001,
     /// Null values.
     Null = 0b00000000,
+    /// Extension values.
+    Ext = 0b00101000,
 }
 
-impl std::fmt::Display for Marker {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Marker {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Int => write!(f, "integer"),
             Self::String => write!(f, "string"),
@@ -44,21 +48,70 @@ impl std::fmt::Display for Marker {
             Self::Bool => write!(f, "bool"),
             Self::Null => write!(f, "null"),
             Self::Unit => write!(f, "unit"),
+            Self::Ext => write!(f, "extension"),
         }
     }
 }
 
 #[cfg(feature = "serde")]
 impl serde::de::Expected for Marker {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        std::fmt::Display::fmt(self, f)
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
     }
 }
 
 impl Marker {
+    /// All markers.
+    pub(crate) const ALL: [Marker; 10] = [
+        Marker::Int,
+        Marker::String,
+        Marker::Seq,
+        Marker::Map,
+        Marker::Float,
+        Marker::Bytes,
+        Marker::Bool,
+        Marker::Unit,
+        Marker::Null,
+        Marker::Ext,
+    ];
+
+    /// Returns all markers, as a slice.
+    #[inline]
+    pub fn all() -> &'static [Marker] {
+        &Self::ALL
+    }
+
+    /// Returns the marker's short name (e.g. `"int"`, `"seq"`).
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::String => "string",
+            Self::Seq => "seq",
+            Self::Map => "map",
+            Self::Float => "float",
+            Self::Bytes => "bytes",
+            Self::Bool => "bool",
+            Self::Unit => "unit",
+            Self::Null => "null",
+            Self::Ext => "ext",
+        }
+    }
+
+    /// Returns the marker with a given short `name`, or `None` if there is none.
+    #[inline]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|marker| marker.name() == name)
+    }
+
     /// Detects a value's type from its header byte.
     #[inline]
     pub fn detect(byte: u8) -> Self {
+        // Extension headers occupy the otherwise reserved bit of the sequence type:
+        if (byte & ExtHeader::TYPE_MASK) == ExtHeader::TYPE_BITS {
+            return Self::Ext;
+        }
+
         // Safety: The following is safe because:
         // - the value returned by `Self::repr_for(byte)` is
         //   guaranteed to contain at most a single non-zero bit.
@@ -74,7 +127,7 @@ impl Marker {
         //     8 => Self::Reserved,
         // }
         // ```
-        unsafe { std::mem::transmute_copy(&Self::repr_for(byte)) }
+        unsafe { core::mem::transmute_copy(&Self::repr_for(byte)) }
     }
 
     /// Returns a given mask's bit-mask.
@@ -91,6 +144,7 @@ impl Marker {
             Self::Bool => BoolHeader::MASK,
             Self::Unit => UnitHeader::MASK,
             Self::Null => NullHeader::MASK,
+            Self::Ext => ExtHeader::MASK,
         }
     }
 
@@ -116,15 +170,39 @@ impl Marker {
     }
 }
 
+impl From<Marker> for u8 {
+    fn from(marker: Marker) -> Self {
+        marker as u8
+    }
+}
+
+/// Parses a marker from its byte representation (i.e. `marker as u8`).
+///
+/// Unlike `Marker::detect`, which detects the marker of any header byte,
+/// this fails for bytes other than the markers' exact representations.
+impl TryFrom<u8> for Marker {
+    type Error = Error;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|marker| *marker as u8 == byte)
+            .ok_or_else(|| {
+                Error::invalid_value(format!("{byte:#010b}"), "marker byte".to_string(), None)
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::ops::RangeInclusive;
+    use core::ops::RangeInclusive;
 
     use test_log::test;
 
     use super::*;
 
-    const MARKERS: [Marker; 9] = [
+    const MARKERS: [Marker; 10] = [
+        Marker::Ext,
         Marker::Null,
         Marker::Unit,
         Marker::Bool,
@@ -136,11 +214,24 @@ mod tests {
         Marker::Int,
     ];
 
-    fn bytes_for_marker(marker: Marker) -> RangeInclusive<u8> {
-        // A byte with only the repr bit set:
-        let min_byte = marker as u8;
-        // A byte with all bits lower than the repr bit set, too:
-        let max_byte = min_byte | min_byte.saturating_sub(1);
+    fn bytes_for_marker(marker: Marker) -> Vec<u8> {
+        // Extension headers are carved out of the sequence type's bytes:
+        let ext_bytes = bytes_for_repr(Marker::Ext as u8);
+
+        bytes_for_repr(marker as u8)
+            .filter(|byte| marker == Marker::Ext || !ext_bytes.contains(byte))
+            .collect()
+    }
+
+    fn bytes_for_repr(repr: u8) -> RangeInclusive<u8> {
+        if repr == 0 {
+            return 0..=0;
+        }
+
+        // A byte with only the repr bit(s) set:
+        let min_byte = repr;
+        // A byte with all bits lower than the lowest repr bit set, too:
+        let max_byte = min_byte | ((1_u8 << min_byte.trailing_zeros()) - 1);
         min_byte..=max_byte
     }
 
@@ -154,6 +245,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn name() {
+        let names: Vec<&str> = Marker::ALL.iter().map(Marker::name).collect();
+        assert_eq!(
+            names,
+            vec!["int", "string", "seq", "map", "float", "bytes", "bool", "unit", "null", "ext"]
+        );
+
+        for marker in Marker::ALL {
+            assert_eq!(Marker::from_name(marker.name()), Some(marker));
+        }
+
+        assert_eq!(Marker::from_name("integer"), None);
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(Marker::all().len(), 10);
+
+        for marker in MARKERS {
+            assert!(Marker::all().contains(&marker));
+        }
+    }
+
+    #[test]
+    fn try_from_byte() {
+        for marker in Marker::all() {
+            let byte = u8::from(*marker);
+            assert_eq!(Marker::try_from(byte).unwrap(), *marker);
+            assert_eq!(Marker::detect(byte), *marker);
+        }
+
+        let valid_count = (u8::MIN..=u8::MAX)
+            .filter(|byte| Marker::try_from(*byte).is_ok())
+            .count();
+        assert_eq!(valid_count, Marker::all().len());
+
+        let err = Marker::try_from(0b11111111).unwrap_err();
+        assert_eq!(err.code(), crate::error::ErrorCode::InvalidValue);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Marker::Int.to_string(), "integer");
+        assert_eq!(Marker::Bytes.to_string(), "byte sequence");
+    }
+
     #[test]
     fn validate() {
         for expected in MARKERS {
@@ -1,4 +1,7 @@
-use crate::value::{IntValue, SignedIntValue, UnsignedIntValue};
+use crate::{
+    header::{BytesHeader, ExtHeader, MapHeader, SeqHeader, StringHeader},
+    value::{IntValue, SignedIntValue, UnsignedIntValue},
+};
 
 pub trait Sealed {}
 
@@ -14,3 +17,4 @@ impl_sealed!(f32, f64);
 impl_sealed!(i8, i16, i32, i64, isize);
 impl_sealed!(u8, u16, u32, u64, usize);
 impl_sealed!(SignedIntValue, UnsignedIntValue, IntValue);
+impl_sealed!(StringHeader, SeqHeader, MapHeader, BytesHeader, ExtHeader);
@@ -1,24 +1,40 @@
 //! Values.
 
+#[cfg(feature = "serde")]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
 #[cfg(any(test, feature = "testing"))]
 use proptest::{prelude::*, sample::SizeRange};
 
 mod bool;
 mod bytes;
+mod diff;
+#[cfg(feature = "digest")]
+mod digest;
+mod eq;
+mod ext;
+mod flatten;
 mod float;
 mod int;
 mod map;
+mod merge;
 mod null;
 mod seq;
+mod size;
 mod string;
+mod text;
+mod tree;
 mod unit;
 
 pub use self::{
     bool::BoolValue,
     bytes::BytesValue,
+    diff::{ValueChange, ValueDiff},
+    ext::ExtValue,
     float::FloatValue,
-    int::{IntValue, SignedIntValue, UnsignedIntValue},
+    int::{IntValue, SignedIntValue, Signedness, UnsignedIntValue},
     map::{Map, MapValue},
+    merge::{MergeStrategy, SeqMergeStrategy},
     null::NullValue,
     seq::{Seq, SeqValue},
     string::StringValue,
@@ -58,6 +74,9 @@ pub enum Value {
 
     /// Represents a null value.
     Null(NullValue),
+
+    /// Represents an application-defined extension value.
+    Ext(ExtValue),
 }
 
 impl Default for Value {
@@ -120,33 +139,129 @@ impl From<NullValue> for Value {
     }
 }
 
-impl std::fmt::Debug for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if f.alternate() {
-            match self {
-                Self::Int(value) => f.debug_tuple("Int").field(value).finish(),
-                Self::String(value) => f.debug_tuple("String").field(value).finish(),
-                Self::Seq(value) => f.debug_tuple("Seq").field(value).finish(),
-                Self::Map(value) => f.debug_tuple("Map").field(value).finish(),
-                Self::Float(value) => f.debug_tuple("Float").field(value).finish(),
-                Self::Bytes(value) => f.debug_tuple("Bytes").field(value).finish(),
-                Self::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
-                Self::Unit(value) => f.debug_tuple("Unit").field(value).finish(),
-                Self::Null(value) => f.debug_tuple("Null").field(value).finish(),
+impl From<ExtValue> for Value {
+    fn from(value: ExtValue) -> Self {
+        Self::Ext(value)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Value {
+    fn from(value: uuid::Uuid) -> Self {
+        Self::Bytes(BytesValue::from(value))
+    }
+}
+
+macro_rules! impl_value_from {
+    ($t:ty => $v:ident($w:ty)) => {
+        impl From<$t> for Value {
+            fn from(value: $t) -> Self {
+                Self::$v(<$w>::from(value))
+            }
+        }
+    };
+}
+
+impl_value_from!(i8 => Int(IntValue));
+impl_value_from!(i16 => Int(IntValue));
+impl_value_from!(i32 => Int(IntValue));
+impl_value_from!(i64 => Int(IntValue));
+impl_value_from!(isize => Int(IntValue));
+
+impl_value_from!(u8 => Int(IntValue));
+impl_value_from!(u16 => Int(IntValue));
+impl_value_from!(u32 => Int(IntValue));
+impl_value_from!(u64 => Int(IntValue));
+impl_value_from!(usize => Int(IntValue));
+
+impl_value_from!(f32 => Float(FloatValue));
+impl_value_from!(f64 => Float(FloatValue));
+
+impl_value_from!(bool => Bool(BoolValue));
+
+impl_value_from!(alloc::string::String => String(StringValue));
+
+impl_value_from!(alloc::vec::Vec<u8> => Bytes(BytesValue));
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(StringValue::from(alloc::string::String::from(value)))
+    }
+}
+
+/// Maps `None` to `Value::Null`.
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => Self::Null(NullValue),
+        }
+    }
+}
+
+/// Collects values into a `Value::Seq`.
+impl FromIterator<Value> for Value {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        Self::Seq(iter.into_iter().collect())
+    }
+}
+
+/// Collects key-value pairs into a `Value::Map`.
+impl FromIterator<(Value, Value)> for Value {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Value, Value)>,
+    {
+        Self::Map(iter.into_iter().collect())
+    }
+}
+
+/// Values get rendered transparently, with the alternate representation (`{:#?}`)
+/// rendering an indented tree, with type-suffixed numbers and hex-formatted bytes.
+impl core::fmt::Debug for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Int(value) => core::fmt::Debug::fmt(value, f),
+            Self::String(value) => core::fmt::Debug::fmt(value, f),
+            Self::Seq(value) => core::fmt::Debug::fmt(value, f),
+            Self::Map(value) => core::fmt::Debug::fmt(value, f),
+            Self::Float(value) => core::fmt::Debug::fmt(value, f),
+            Self::Bytes(value) if f.alternate() => {
+                core::fmt::Debug::fmt(&HexBytes(value.as_slice()), f)
             }
-        } else {
-            match self {
-                Self::Int(value) => std::fmt::Debug::fmt(value, f),
-                Self::String(value) => std::fmt::Debug::fmt(value, f),
-                Self::Seq(value) => std::fmt::Debug::fmt(value, f),
-                Self::Map(value) => std::fmt::Debug::fmt(value, f),
-                Self::Float(value) => std::fmt::Debug::fmt(value, f),
-                Self::Bytes(value) => std::fmt::Debug::fmt(value, f),
-                Self::Bool(value) => std::fmt::Debug::fmt(value, f),
-                Self::Unit(value) => std::fmt::Debug::fmt(value, f),
-                Self::Null(value) => std::fmt::Debug::fmt(value, f),
+            Self::Bytes(value) => core::fmt::Debug::fmt(value, f),
+            Self::Bool(value) => core::fmt::Debug::fmt(value, f),
+            Self::Unit(value) => core::fmt::Debug::fmt(value, f),
+            Self::Null(value) => core::fmt::Debug::fmt(value, f),
+            Self::Ext(value) if f.alternate() => f
+                .debug_struct("Ext")
+                .field("tag", &value.tag())
+                .field("data", &HexBytes(value.as_slice()))
+                .finish(),
+            Self::Ext(value) => core::fmt::Debug::fmt(value, f),
+        }
+    }
+}
+
+/// Renders bytes as a single-line list of hex literals (e.g. `[0x01, 0xab]`).
+struct HexBytes<'a>(&'a [u8]);
+
+impl core::fmt::Debug for HexBytes<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("[")?;
+        for (index, byte) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
             }
+            write!(f, "{byte:#04x}")?;
         }
+        f.write_str("]")
     }
 }
 
@@ -166,6 +281,7 @@ impl serde::Serialize for Value {
             Value::Bool(value) => value.serialize(serializer),
             Value::Unit(value) => value.serialize(serializer),
             Value::Null(value) => value.serialize(serializer),
+            Value::Ext(value) => value.serialize(serializer),
         }
     }
 }
@@ -407,14 +523,46 @@ impl<'de> serde::Deserialize<'de> for Value {
     }
 }
 
-#[doc(hidden)]
+/// Parameters bounding the values generated by `Value`'s `proptest::arbitrary::Arbitrary` impl,
+/// as passed to `Value::arbitrary_with`.
+///
+/// Use `Value::arbitrary()` for the default bounds.
+///
+/// *This type is only available if lilliput_core is built with the `"testing"` feature.*
 #[cfg(any(test, feature = "testing"))]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct ValueArbitraryParameters {
+    /// The maximum nesting depth of generated collections (maps and sequences),
+    /// with `0` only generating scalar values.
     pub depth: u32,
+    /// The desired total number of values within a generated value.
     pub desired_size: u32,
+    /// The expected number of elements per generated collection,
+    /// with collections always having fewer than `expected_branch_size` elements.
     pub expected_branch_size: u32,
 }
 
+#[cfg(any(test, feature = "testing"))]
+impl ValueArbitraryParameters {
+    /// Sets the maximum nesting depth of generated collections.
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the desired total number of values within a generated value.
+    pub fn with_desired_size(mut self, desired_size: u32) -> Self {
+        self.desired_size = desired_size;
+        self
+    }
+
+    /// Sets the expected number of elements per generated collection.
+    pub fn with_expected_branch_size(mut self, expected_branch_size: u32) -> Self {
+        self.expected_branch_size = expected_branch_size;
+        self
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 impl Default for ValueArbitraryParameters {
     fn default() -> Self {
@@ -451,6 +599,8 @@ impl proptest::arbitrary::Arbitrary for Value {
             BoolValue::arbitrary().prop_map(Value::Bool),
             UnitValue::arbitrary().prop_map(Value::Unit),
             NullValue::arbitrary().prop_map(Value::Null),
+            // Extension values are omitted, as they don't survive
+            // a round-trip through serde's data model.
         ];
 
         let len: SizeRange = (0..(expected_branch_size as usize)).into();
@@ -477,10 +627,7 @@ mod tests {
     fn debug() {
         // Int
         assert_eq!(format!("{:?}", Value::Int(IntValue::default())), "0");
-        assert_eq!(
-            format!("{:#?}", Value::Int(IntValue::default())),
-            "Int(\n    0_u8,\n)"
-        );
+        assert_eq!(format!("{:#?}", Value::Int(IntValue::default())), "0_u8");
 
         // String
         assert_eq!(
@@ -489,49 +636,187 @@ mod tests {
         );
         assert_eq!(
             format!("{:#?}", Value::String(StringValue::default())),
-            "String(\n    \"\",\n)"
+            "\"\""
         );
 
         // Seq
         assert_eq!(format!("{:?}", Value::Seq(SeqValue::default())), "[]");
-        assert_eq!(
-            format!("{:#?}", Value::Seq(SeqValue::default())),
-            "Seq(\n    [],\n)"
-        );
+        assert_eq!(format!("{:#?}", Value::Seq(SeqValue::default())), "[]");
 
         // Map
         assert_eq!(format!("{:?}", Value::Map(MapValue::default())), "{}");
-        assert_eq!(
-            format!("{:#?}", Value::Map(MapValue::default())),
-            "Map(\n    {},\n)"
-        );
+        assert_eq!(format!("{:#?}", Value::Map(MapValue::default())), "{}");
 
         // Float
         assert_eq!(format!("{:?}", Value::Float(FloatValue::default())), "0.0");
         assert_eq!(
             format!("{:#?}", Value::Float(FloatValue::default())),
-            "Float(\n    0.0_f32,\n)"
+            "0.0_f32"
         );
 
         // Bytes
         assert_eq!(format!("{:?}", Value::Bytes(BytesValue::default())), "[]");
-        assert_eq!(
-            format!("{:#?}", Value::Bytes(BytesValue::default())),
-            "Bytes(\n    [],\n)"
-        );
+        assert_eq!(format!("{:#?}", Value::Bytes(BytesValue::default())), "[]");
 
         // Bool
         assert_eq!(format!("{:?}", Value::Bool(BoolValue::default())), "false");
-        assert_eq!(
-            format!("{:#?}", Value::Bool(BoolValue::default())),
-            "Bool(\n    false,\n)"
-        );
+        assert_eq!(format!("{:#?}", Value::Bool(BoolValue::default())), "false");
 
         // Null
         assert_eq!(format!("{:?}", Value::Null(NullValue)), "null");
+        assert_eq!(format!("{:#?}", Value::Null(NullValue)), "null");
+    }
+
+    #[test]
+    fn from_primitives() {
+        assert_eq!(Value::from(-42_i8), Value::Int(IntValue::from(-42_i8)));
+        assert_eq!(Value::from(-42_i64), Value::Int(IntValue::from(-42_i64)));
+        assert_eq!(
+            Value::from(-42_isize),
+            Value::Int(IntValue::from(-42_isize))
+        );
+        assert_eq!(Value::from(42_u8), Value::Int(IntValue::from(42_u8)));
+        assert_eq!(Value::from(42_u64), Value::Int(IntValue::from(42_u64)));
+        assert_eq!(Value::from(42_usize), Value::Int(IntValue::from(42_usize)));
+
+        assert_eq!(
+            Value::from(4.2_f32),
+            Value::Float(FloatValue::from(4.2_f32))
+        );
+        assert_eq!(
+            Value::from(4.2_f64),
+            Value::Float(FloatValue::from(4.2_f64))
+        );
+
+        assert_eq!(Value::from(true), Value::Bool(BoolValue::from(true)));
+
+        let string = Value::String(StringValue::from("lilliput".to_owned()));
+        assert_eq!(Value::from("lilliput"), string);
+        assert_eq!(Value::from("lilliput".to_owned()), string);
+
+        // Byte vectors map to byte arrays, rather than sequences of integers:
+        assert_eq!(
+            Value::from(vec![1_u8, 2, 3]),
+            Value::Bytes(BytesValue::from(vec![1, 2, 3]))
+        );
+    }
+
+    #[test]
+    fn from_option() {
+        assert_eq!(Value::from(Some(42_u8)), Value::Int(IntValue::from(42_u8)));
+        assert_eq!(Value::from(None::<u8>), Value::Null(NullValue));
+
+        assert_eq!(
+            Value::from(Some("lilliput")),
+            Value::String(StringValue::from("lilliput".to_owned()))
+        );
+        assert_eq!(Value::from(None::<&str>), Value::Null(NullValue));
+
+        // Nested options collapse:
+        assert_eq!(Value::from(Some(None::<bool>)), Value::Null(NullValue));
+    }
+
+    #[test]
+    fn from_iter() {
+        let values: Vec<Value> = (1..=3_u8)
+            .map(|int| Value::from(IntValue::from(int)))
+            .collect();
+
+        let value: Value = values.iter().cloned().collect();
+        assert_eq!(value, Value::Seq(SeqValue::from(values.clone())));
+
+        let value: Value = values
+            .iter()
+            .cloned()
+            .map(|value| (value, Value::Null(NullValue)))
+            .collect();
+        let Value::Map(map) = value else {
+            panic!("expected map value");
+        };
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn debug_nested() {
+        let mut map = Map::default();
+        map.insert(
+            Value::String(StringValue::from("bytes".to_owned())),
+            Value::Bytes(BytesValue::from(vec![0x01, 0xab])),
+        );
+        map.insert(
+            Value::String(StringValue::from("float".to_owned())),
+            Value::Float(FloatValue::from(1.5_f64)),
+        );
+
+        let value = Value::Seq(SeqValue::from(vec![
+            Value::Int(IntValue::from(42_u8)),
+            Value::Map(MapValue::from(map)),
+            Value::Seq(SeqValue::from(vec![Value::Null(NullValue)])),
+            Value::Ext(ExtValue::new(7, vec![0xff])),
+        ]));
+
         assert_eq!(
-            format!("{:#?}", Value::Null(NullValue)),
-            "Null(\n    null,\n)"
+            format!("{value:?}"),
+            "[42, {\"bytes\": [00000001, 10101011], \"float\": 1.5}, [null], Ext { tag: 7, data: [11111111] }]"
         );
+
+        assert_eq!(
+            format!("{value:#?}"),
+            r#"[
+    42_u8,
+    {
+        "bytes": [0x01, 0xab],
+        "float": 1.5_f64,
+    },
+    [
+        null,
+    ],
+    Ext {
+        tag: 7,
+        data: [0xff],
+    },
+]"#
+        );
+    }
+
+    /// Returns the nesting depth of `value`'s collections, as well as their maximum length.
+    fn depth_and_max_len(value: &Value) -> (u32, usize) {
+        let (len, children): (usize, Vec<&Value>) = match value {
+            Value::Seq(seq) => (seq.len(), seq.0.iter().collect()),
+            Value::Map(map) => (map.len(), map.0.iter().flat_map(|(k, v)| [k, v]).collect()),
+            _ => return (0, 0),
+        };
+
+        children.iter().map(|child| depth_and_max_len(child)).fold(
+            (1, len),
+            |(depth, max_len), (child_depth, child_max_len)| {
+                (depth.max(child_depth + 1), max_len.max(child_max_len))
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_respects_default_bounds(value in Value::arbitrary()) {
+            let params = ValueArbitraryParameters::default();
+            let (depth, max_len) = depth_and_max_len(&value);
+            prop_assert!(depth <= params.depth);
+            prop_assert!(max_len < params.expected_branch_size as usize);
+        }
+
+        #[test]
+        fn arbitrary_respects_bounds(
+            value in (0..=3_u32, 1..=4_u32).prop_flat_map(|(depth, branch_size)| {
+                let params = ValueArbitraryParameters::default()
+                    .with_depth(depth)
+                    .with_expected_branch_size(branch_size);
+                (Just(params), Value::arbitrary_with(params))
+            })
+        ) {
+            let (params, value) = value;
+            let (depth, max_len) = depth_and_max_len(&value);
+            prop_assert!(depth <= params.depth);
+            prop_assert!(max_len < params.expected_branch_size as usize);
+        }
     }
 }
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 #[cfg(any(test, feature = "testing"))]
 use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
@@ -30,6 +32,21 @@ impl BytesValue {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns `true`, if `self` and `other` are equal, otherwise `false`,
+    /// comparing their bytes in constant time.
+    ///
+    /// Unlike `==` this does not return early on the first differing byte,
+    /// making it suitable for comparing secrets, such as MACs or tokens.
+    /// Differing lengths however still return early (i.e. lengths are not secret).
+    ///
+    /// *This method is only available if lilliput_core is built with the `"subtle"` feature.*
+    #[cfg(feature = "subtle")]
+    pub fn ct_eq(&self, other: &BytesValue) -> bool {
+        use subtle::ConstantTimeEq as _;
+
+        self.as_slice().ct_eq(other.as_slice()).into()
+    }
 }
 
 impl From<Vec<u8>> for BytesValue {
@@ -38,6 +55,13 @@ impl From<Vec<u8>> for BytesValue {
     }
 }
 
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for BytesValue {
+    fn from(value: uuid::Uuid) -> Self {
+        Self(value.as_bytes().to_vec())
+    }
+}
+
 impl<'a> From<&'a BytesValue> for &'a [u8] {
     fn from(value: &'a BytesValue) -> Self {
         &value.0
@@ -50,15 +74,15 @@ impl From<BytesValue> for Vec<u8> {
     }
 }
 
-impl std::fmt::Debug for BytesValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(&BytesSlice(&self.0), f)
+impl core::fmt::Debug for BytesValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&BytesSlice(&self.0), f)
     }
 }
 
-impl std::fmt::Display for BytesValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&BytesSlice(&self.0), f)
+impl core::fmt::Display for BytesValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&BytesSlice(&self.0), f)
     }
 }
 
@@ -120,7 +144,35 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn ct_eq() {
+        let tag = BytesValue::from(vec![0xde, 0xad, 0xbe, 0xef]);
+
+        let cases = [
+            BytesValue::from(vec![0xde, 0xad, 0xbe, 0xef]),
+            BytesValue::from(vec![0xde, 0xad, 0xbe, 0xee]),
+            BytesValue::from(vec![0x00, 0xad, 0xbe, 0xef]),
+            BytesValue::from(vec![0xde, 0xad, 0xbe]),
+            BytesValue::default(),
+        ];
+
+        for other in &cases {
+            assert_eq!(tag.ct_eq(other), tag == *other, "{other:?}");
+            assert_eq!(other.ct_eq(&tag), *other == tag, "{other:?}");
+        }
+
+        assert!(BytesValue::default().ct_eq(&BytesValue::default()));
+    }
+
     proptest! {
+        #[cfg(feature = "subtle")]
+        #[test]
+        fn ct_eq_matches_eq(lhs in BytesValue::arbitrary(), rhs in BytesValue::arbitrary()) {
+            prop_assert_eq!(lhs.ct_eq(&rhs), lhs == rhs);
+            prop_assert!(lhs.ct_eq(&lhs.clone()));
+        }
+
         #[test]
         fn encode_decode_roundtrip(value in BytesValue::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
@@ -0,0 +1,295 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write as _;
+
+use super::Value;
+
+/// A single change between two values, as reported by `Value::diff`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ValueChange {
+    /// A value was added at `path`.
+    Added {
+        /// The JSON-pointer-style path of the added value.
+        path: String,
+        /// The added value.
+        value: Value,
+    },
+    /// A value was removed from `path`.
+    Removed {
+        /// The JSON-pointer-style path of the removed value.
+        path: String,
+        /// The removed value.
+        value: Value,
+    },
+    /// The value at `path` was changed.
+    Changed {
+        /// The JSON-pointer-style path of the changed value.
+        path: String,
+        /// The old value.
+        old: Value,
+        /// The new value.
+        new: Value,
+    },
+}
+
+impl ValueChange {
+    /// Returns the JSON-pointer-style path of the change.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. } => path,
+            Self::Removed { path, .. } => path,
+            Self::Changed { path, .. } => path,
+        }
+    }
+}
+
+/// A structured description of the differences between two values,
+/// as returned by `Value::diff`.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct ValueDiff {
+    changes: Vec<ValueChange>,
+}
+
+impl ValueDiff {
+    /// Returns the changes.
+    pub fn changes(&self) -> &[ValueChange] {
+        &self.changes
+    }
+
+    /// Returns the changes, consuming `self`.
+    pub fn into_changes(self) -> Vec<ValueChange> {
+        self.changes
+    }
+
+    /// Returns the number of changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true`, if there are no changes, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Value {
+    /// Returns the differences between `self` and `other`.
+    ///
+    /// Changes are keyed by JSON-pointer-style paths (e.g. `"/foo/0"`), with the
+    /// empty path referring to the root value. Map keys that are not strings
+    /// get rendered into their path segment using `Value::to_text`.
+    ///
+    /// - Maps get compared key by key, reporting added and removed keys.
+    /// - Sequences get compared index by index, reporting added and removed trailing elements.
+    /// - Any other pair of unequal values gets reported as changed.
+    pub fn diff(&self, other: &Value) -> ValueDiff {
+        let mut diff = ValueDiff::default();
+        let mut path = String::new();
+
+        diff_values(self, other, &mut path, &mut diff.changes);
+
+        diff
+    }
+}
+
+fn diff_values(old: &Value, new: &Value, path: &mut String, changes: &mut Vec<ValueChange>) {
+    match (old, new) {
+        (Value::Map(old), Value::Map(new)) => {
+            let (old, new) = (old.as_map_ref(), new.as_map_ref());
+
+            for (key, old_value) in old.iter() {
+                let len = path.len();
+                push_key_segment(path, key);
+
+                match new.get(key) {
+                    Some(new_value) => diff_values(old_value, new_value, path, changes),
+                    None => changes.push(ValueChange::Removed {
+                        path: path.clone(),
+                        value: old_value.clone(),
+                    }),
+                }
+
+                path.truncate(len);
+            }
+
+            for (key, new_value) in new.iter() {
+                if old.contains_key(key) {
+                    continue;
+                }
+
+                let len = path.len();
+                push_key_segment(path, key);
+
+                changes.push(ValueChange::Added {
+                    path: path.clone(),
+                    value: new_value.clone(),
+                });
+
+                path.truncate(len);
+            }
+        }
+        (Value::Seq(old), Value::Seq(new)) => {
+            let (old, new) = (old.as_slice(), new.as_slice());
+
+            for index in 0..old.len().max(new.len()) {
+                let len = path.len();
+                write!(path, "/{index}").expect("writing to a string should not fail");
+
+                match (old.get(index), new.get(index)) {
+                    (Some(old_value), Some(new_value)) => {
+                        diff_values(old_value, new_value, path, changes)
+                    }
+                    (Some(old_value), None) => changes.push(ValueChange::Removed {
+                        path: path.clone(),
+                        value: old_value.clone(),
+                    }),
+                    (None, Some(new_value)) => changes.push(ValueChange::Added {
+                        path: path.clone(),
+                        value: new_value.clone(),
+                    }),
+                    (None, None) => unreachable!(),
+                }
+
+                path.truncate(len);
+            }
+        }
+        (old, new) if old != new => changes.push(ValueChange::Changed {
+            path: path.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn push_key_segment(path: &mut String, key: &Value) {
+    path.push('/');
+
+    let text = match key {
+        Value::String(key) => String::from(key.as_str()),
+        key => key.to_text(),
+    };
+
+    // Escape according to RFC 6901:
+    for char in text.chars() {
+        match char {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            char => path.push(char),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    fn map<const N: usize>(entries: [(&str, Value); N]) -> Value {
+        entries
+            .into_iter()
+            .map(|(key, value)| (Value::from(key), value))
+            .collect()
+    }
+
+    fn seq<const N: usize>(values: [u8; N]) -> Value {
+        values.into_iter().map(Value::from).collect()
+    }
+
+    #[test]
+    fn equal() {
+        let value = map([("a", seq([1, 2])), ("b", Value::from("foo"))]);
+
+        assert!(value.diff(&value).is_empty());
+    }
+
+    #[test]
+    fn scalar_change() {
+        let diff = Value::from(1_u8).diff(&Value::from("foo"));
+
+        assert_eq!(
+            diff.changes(),
+            &[ValueChange::Changed {
+                path: "".to_owned(),
+                old: Value::from(1_u8),
+                new: Value::from("foo"),
+            }]
+        );
+
+        let old = map([("a", Value::from(1_u8)), ("b", Value::from(true))]);
+        let new = map([("a", Value::from(2_u8)), ("b", Value::from(true))]);
+
+        assert_eq!(
+            old.diff(&new).into_changes(),
+            vec![ValueChange::Changed {
+                path: "/a".to_owned(),
+                old: Value::from(1_u8),
+                new: Value::from(2_u8),
+            }]
+        );
+    }
+
+    #[test]
+    fn key_addition_and_removal() {
+        let old = map([("a", Value::from(1_u8)), ("b/c~", Value::from(2_u8))]);
+        let new = map([("a", Value::from(1_u8)), ("d", Value::from(3_u8))]);
+
+        assert_eq!(
+            old.diff(&new).into_changes(),
+            vec![
+                ValueChange::Removed {
+                    path: "/b~1c~0".to_owned(),
+                    value: Value::from(2_u8),
+                },
+                ValueChange::Added {
+                    path: "/d".to_owned(),
+                    value: Value::from(3_u8),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn non_string_key() {
+        let old: Value = [(Value::from(42_u8), Value::from(1_u8))]
+            .into_iter()
+            .collect();
+        let new: Value = [(Value::from(42_u8), Value::from(2_u8))]
+            .into_iter()
+            .collect();
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff.changes()[0].path(), "/42");
+    }
+
+    #[test]
+    fn nested_seq_element_change() {
+        let old = map([("outer", map([("seq", seq([1, 2, 3]))]))]);
+        let new = map([("outer", map([("seq", seq([1, 4]))]))]);
+
+        assert_eq!(
+            old.diff(&new).into_changes(),
+            vec![
+                ValueChange::Changed {
+                    path: "/outer/seq/1".to_owned(),
+                    old: Value::from(2_u8),
+                    new: Value::from(4_u8),
+                },
+                ValueChange::Removed {
+                    path: "/outer/seq/2".to_owned(),
+                    value: Value::from(3_u8),
+                },
+            ]
+        );
+
+        assert_eq!(
+            new.diff(&old).changes()[1],
+            ValueChange::Added {
+                path: "/outer/seq/2".to_owned(),
+                value: Value::from(3_u8),
+            }
+        );
+    }
+}
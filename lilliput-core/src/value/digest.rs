@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    config::{EncoderConfig, IntWidth, PackingMode},
+    encoder::Encoder,
+    error::Result,
+    io::Write,
+};
+
+use super::Value;
+
+impl Value {
+    /// Returns a SHA-256 digest of the value's encoding,
+    /// which is identical across platforms (e.g. regardless of endianness).
+    ///
+    /// The value gets encoded with a fixed canonical configuration (optimal packing,
+    /// minimal integer widths, normalized and unshrunk floats) and with map entries sorted
+    /// by key (regardless of the `"preserve_order"` feature), and fed directly into the hasher,
+    /// without allocating an output buffer.
+    ///
+    /// The digest is derived from the encoded representation, so values that
+    /// compare equal, but get encoded differently (such as signed vs. unsigned
+    /// integers of the same number) produce different digests.
+    ///
+    /// *This method is only available if lilliput_core is built with the `"digest"` feature.*
+    pub fn stable_hash(&self) -> [u8; 32] {
+        let mut encoder = Encoder::new(HashWriter(Sha256::new()), canonical_config());
+        encode_sorted(&mut encoder, self).expect("writing to a hasher should not fail");
+
+        encoder.into_writer().0.finalize().into()
+    }
+}
+
+/// The configuration digests get computed with, spelled out explicitly,
+/// so that changes to `EncoderConfig::default()` don't change existing digests.
+fn canonical_config() -> EncoderConfig {
+    EncoderConfig::builder()
+        .packing(PackingMode::Optimal)
+        .int_width(IntWidth::Minimal)
+        .normalize_floats(true)
+        .shrink_floats(false)
+        .build()
+}
+
+/// Encodes `value`, like `Encoder::encode_value`, but with map entries sorted by key.
+fn encode_sorted<W>(encoder: &mut Encoder<W>, value: &Value) -> Result<()>
+where
+    W: Write,
+{
+    match value {
+        Value::Seq(seq) => {
+            encoder.encode_seq_header(&encoder.header_for_seq_len(seq.len()))?;
+
+            for value in seq.as_slice() {
+                encode_sorted(encoder, value)?;
+            }
+
+            Ok(())
+        }
+        Value::Map(map) => {
+            encoder.encode_map_header(&encoder.header_for_map_len(map.len()))?;
+
+            let mut entries: Vec<(&Value, &Value)> = map.as_map_ref().iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+
+            for (key, value) in entries {
+                encode_sorted(encoder, key)?;
+                encode_sorted(encoder, value)?;
+            }
+
+            Ok(())
+        }
+        value => encoder.encode_value(value),
+    }
+}
+
+/// Feeds the encoder's output into a `Sha256` hasher.
+struct HashWriter(Sha256);
+
+impl Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::value::{IntValue, Map, MapValue, SeqValue, StringValue};
+
+    use super::*;
+
+    fn hex(digest: impl AsRef<[u8]>) -> String {
+        digest
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    #[test]
+    fn stable_across_endianness() {
+        // Multi-byte integers, whose native byte order differs between platforms:
+        let value = Value::Seq(SeqValue::from(vec![
+            Value::Int(IntValue::from(0x0102_0304_u32)),
+            Value::Int(IntValue::from(-0x0102_0304_0506_i64)),
+            Value::String(StringValue::from("lilliput".to_owned())),
+            Value::Map(MapValue::from(Map::from_iter([(
+                Value::Int(IntValue::from(42_u8)),
+                Value::Bool(true.into()),
+            )]))),
+        ]));
+
+        // The encoding is big-endian by definition, so the digest must match
+        // the reference (computed independently from these exact bytes),
+        // regardless of the host's byte order:
+        let encoded: [u8; 26] = [
+            0x34, 0x83, 0x01, 0x02, 0x03, 0x04, 0xa5, 0x02, 0x04, 0x06, 0x08, 0x0a, 0x0b, 0x68,
+            0x6c, 0x69, 0x6c, 0x6c, 0x69, 0x70, 0x75, 0x74, 0x19, 0x80, 0x2a, 0x03,
+        ];
+        let expected = "0c324730f7d7b078de11431186522dbb0a82c183591cd22d3aba3f9a7c4dcc69";
+
+        assert_eq!(hex(Sha256::digest(encoded)), expected);
+        assert_eq!(hex(value.stable_hash()), expected);
+    }
+
+    #[test]
+    fn independent_of_map_order() {
+        let entries = [
+            (Value::from("b"), Value::from(2_u8)),
+            (Value::from("a"), Value::from(1_u8)),
+            (
+                Value::from("c"),
+                Value::Map(MapValue::from(Map::from_iter([
+                    (Value::from(2_u8), Value::from(true)),
+                    (Value::from(1_u8), Value::from(false)),
+                ]))),
+            ),
+        ];
+
+        let forward = Value::Map(MapValue::from(Map::from_iter(entries.clone())));
+        let backward = Value::Map(MapValue::from(Map::from_iter(entries.into_iter().rev())));
+
+        // The entries get hashed sorted by key, regardless of their insertion order:
+        let encoded: [u8; 14] = [
+            0x1b, 0x61, 0x61, 0xc1, 0x61, 0x62, 0xc2, 0x61, 0x63, 0x1a, 0xc1, 0x02, 0xc2, 0x03,
+        ];
+        let expected = "4207d304875b90082e9a218cd8668370cc873cfaae30439d1a71000c8d31e473";
+
+        assert_eq!(hex(Sha256::digest(encoded)), expected);
+        assert_eq!(hex(forward.stable_hash()), expected);
+        assert_eq!(hex(backward.stable_hash()), expected);
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::vec;
+
+use super::{MapValue, Value};
+
+impl Value {
+    /// Returns `true` if `self` and `other` are structurally equal,
+    /// regardless of the order of their maps' entries.
+    ///
+    /// - Maps get compared as multisets of entries, recursively.
+    /// - Sequences get compared element by element, in order, recursively.
+    /// - Any other values get compared via `PartialEq`.
+    ///
+    /// Unlike `PartialEq` (which depends on the map's backing store, see `Value::Map`),
+    /// two maps built by inserting the same entries in different orders always compare equal.
+    pub fn eq_unordered(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Map(map), Value::Map(other)) => map_eq_unordered(map, other),
+            (Value::Seq(seq), Value::Seq(other)) => {
+                seq.len() == other.len()
+                    && seq
+                        .as_slice()
+                        .iter()
+                        .zip(other.as_slice())
+                        .all(|(value, other)| value.eq_unordered(other))
+            }
+            (value, other) => value == other,
+        }
+    }
+}
+
+fn map_eq_unordered(map: &MapValue, other: &MapValue) -> bool {
+    if map.len() != other.len() {
+        return false;
+    }
+
+    // As `eq_unordered` is an equivalence relation, matching entries greedily
+    // finds a perfect matching, if there is one:
+    let mut matched = vec![false; other.len()];
+
+    map.as_map_ref().iter().all(|(key, value)| {
+        let position =
+            other
+                .as_map_ref()
+                .iter()
+                .enumerate()
+                .position(|(index, (other_key, other_value))| {
+                    !matched[index]
+                        && key.eq_unordered(other_key)
+                        && value.eq_unordered(other_value)
+                });
+
+        match position {
+            Some(index) => {
+                matched[index] = true;
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::value::{FloatValue, SeqValue};
+
+    use super::*;
+
+    fn map<const N: usize>(entries: [(Value, Value); N]) -> Value {
+        entries.into_iter().collect()
+    }
+
+    fn seq<const N: usize>(values: [Value; N]) -> Value {
+        values.into_iter().collect()
+    }
+
+    #[test]
+    fn scalars() {
+        assert!(Value::from(1_u8).eq_unordered(&Value::from(1_u8)));
+        assert!(!Value::from(1_u8).eq_unordered(&Value::from(2_u8)));
+        assert!(!Value::from(1_u8).eq_unordered(&Value::from("1")));
+
+        let nan = Value::Float(FloatValue::from(f64::NAN));
+        assert!(nan.eq_unordered(&nan));
+    }
+
+    #[test]
+    fn maps_ignore_order() {
+        let entries = [
+            (Value::from("a"), Value::from(1_u8)),
+            (Value::from("b"), Value::from(2_u8)),
+            (Value::from("c"), Value::from(3_u8)),
+        ];
+        let mut reversed = entries.clone();
+        reversed.reverse();
+
+        assert!(map(entries.clone()).eq_unordered(&map(reversed.clone())));
+
+        // Nested within sequences and maps:
+        let nested = seq([map(entries.clone())]);
+        let nested_reversed = seq([map(reversed.clone())]);
+        assert!(nested.eq_unordered(&nested_reversed));
+
+        let nested = map([(Value::from("nested"), map(entries.clone()))]);
+        let nested_reversed = map([(Value::from("nested"), map(reversed.clone()))]);
+        assert!(nested.eq_unordered(&nested_reversed));
+
+        // As keys:
+        let keyed = map([(map(entries), Value::from(true))]);
+        let keyed_reversed = map([(map(reversed), Value::from(true))]);
+        assert!(keyed.eq_unordered(&keyed_reversed));
+    }
+
+    #[test]
+    fn maps_differ() {
+        let value = map([
+            (Value::from("a"), Value::from(1_u8)),
+            (Value::from("b"), Value::from(2_u8)),
+        ]);
+
+        let other_value = map([
+            (Value::from("a"), Value::from(1_u8)),
+            (Value::from("b"), Value::from(3_u8)),
+        ]);
+        assert!(!value.eq_unordered(&other_value));
+
+        let other_key = map([
+            (Value::from("a"), Value::from(1_u8)),
+            (Value::from("c"), Value::from(2_u8)),
+        ]);
+        assert!(!value.eq_unordered(&other_key));
+
+        let fewer = map([(Value::from("a"), Value::from(1_u8))]);
+        assert!(!value.eq_unordered(&fewer));
+        assert!(!fewer.eq_unordered(&value));
+    }
+
+    #[test]
+    fn seqs_respect_order() {
+        let value = seq([Value::from(1_u8), Value::from(2_u8)]);
+        let reversed = seq([Value::from(2_u8), Value::from(1_u8)]);
+
+        assert!(value.eq_unordered(&value));
+        assert!(!value.eq_unordered(&reversed));
+        assert!(!value.eq_unordered(&seq([Value::from(1_u8)])));
+        assert!(!value.eq_unordered(&Value::Seq(SeqValue::default())));
+    }
+}
@@ -0,0 +1,168 @@
+use alloc::vec::Vec;
+
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+#[cfg(any(test, feature = "testing"))]
+use proptest_derive::Arbitrary;
+
+use crate::binary::BytesSlice;
+
+/// Represents an application-defined extension value,
+/// consisting of a tag and an opaque byte payload.
+///
+/// The tag's interpretation is left to the application,
+/// lilliput itself passes unknown tags through unchanged.
+#[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
+#[derive(Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ExtValue {
+    /// The application-defined tag.
+    pub tag: u64,
+    /// The opaque payload.
+    pub data: Vec<u8>,
+}
+
+impl ExtValue {
+    /// The tag of timestamp extension values, whose payload consists of the
+    /// big-endian seconds (`i64`) and nanoseconds (`u32`) since the unix epoch.
+    pub const TIMESTAMP_TAG: u64 = u64::MAX;
+
+    #[allow(dead_code)]
+    pub(crate) const TIMESTAMP_LEN: usize = 8 + 4;
+
+    /// Creates an extension value from a `tag` and its payload's `data`.
+    pub fn new(tag: u64, data: Vec<u8>) -> Self {
+        Self { tag, data }
+    }
+
+    /// Returns the application-defined tag.
+    pub fn tag(&self) -> u64 {
+        self.tag
+    }
+
+    /// Returns a slice, referencing the payload.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the tag and payload, consuming `self`.
+    pub fn into_parts(self) -> (u64, Vec<u8>) {
+        (self.tag, self.data)
+    }
+
+    /// Returns the length of the payload.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true`, if the payload is empty, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl core::fmt::Debug for ExtValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Ext")
+            .field("tag", &self.tag)
+            .field("data", &BytesSlice(&self.data))
+            .finish()
+    }
+}
+
+/// Extension values get represented as `(tag, bytes)` tuples in serde's data model,
+/// so round-tripping them through a serde format yields a sequence.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExtValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (self.tag, serde_bytes::Bytes::new(&self.data)).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExtValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (tag, data) = <(u64, serde_bytes::ByteBuf)>::deserialize(deserializer)?;
+
+        Ok(Self::new(tag, data.into_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::EncoderConfig,
+        decoder::Decoder,
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", ExtValue::new(42, vec![1, 2])),
+            "Ext { tag: 42, data: [00000001, 00000010] }"
+        );
+    }
+
+    #[test]
+    fn unknown_tag_passthrough() {
+        let value = Value::Ext(ExtValue::new(0xdead_beef, vec![0xde, 0xad]));
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_value(&value).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.decode_value().unwrap(), value);
+    }
+
+    proptest! {
+        #[test]
+        fn encode_decode_roundtrip(
+            tag in u64::arbitrary(),
+            data in proptest::collection::vec(u8::arbitrary(), 0..1024),
+            config in EncoderConfig::arbitrary()
+        ) {
+            let value = ExtValue::new(tag, data);
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_ext(value.tag(), value.as_slice()).unwrap();
+
+            prop_assert!(encoded.len() <= 1 + 8 + 1 + 8 + value.len());
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_ext_value().unwrap();
+            prop_assert_eq!(&decoded, &value);
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_value().unwrap();
+            let Value::Ext(decoded) = decoded else {
+                panic!("expected ext value");
+            };
+            prop_assert_eq!(&decoded, &value);
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            decoder.skip_value().unwrap();
+            prop_assert_eq!(decoder.pos(), encoded.len());
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{Map, MapValue, SeqValue, Value};
+
+impl Value {
+    /// Flattens nested maps and sequences into a single map,
+    /// whose keys are the paths of their leaf values, joined by `sep`.
+    ///
+    /// For example `{"a": {"b": 1, "c": [true, false]}}` gets flattened
+    /// into `{"a.b": 1, "a.c.0": true, "a.c.1": false}` (for a `sep` of `"."`).
+    ///
+    /// - Map keys get used verbatim if they are strings, otherwise they get
+    ///   stringified via `Value::to_text` (e.g. `1` as `"1"`).
+    /// - Sequence elements use their index as key.
+    /// - Empty maps and sequences are leaf values, as are scalars.
+    ///
+    /// A scalar `self` gets flattened into a single entry with an empty key.
+    ///
+    /// Use `Value::unflatten` for rebuilding the nested value.
+    pub fn flatten(&self, sep: &str) -> MapValue {
+        let mut map = Map::default();
+
+        self.flatten_into(None, sep, &mut map);
+
+        MapValue::from(map)
+    }
+
+    /// Rebuilds a nested value from a `map` flattened via `Value::flatten`,
+    /// splitting its keys by `sep`.
+    ///
+    /// Maps whose keys are exactly the indices `0..n` get rebuilt as sequences.
+    /// As such maps with (only) such keys can't be told apart from sequences,
+    /// and get rebuilt as the latter.
+    ///
+    /// A `map` with a single entry of empty key gets rebuilt as that entry's value,
+    /// as produced by flattening scalars (and empty containers).
+    ///
+    /// Non-string keys get stringified via `Value::to_text`. For keys conflicting
+    /// with each other (e.g. `"a"` and `"a.b"`) the latter entry in `map`'s order wins.
+    pub fn unflatten(map: &MapValue, sep: &str) -> Value {
+        let mut root = Map::default();
+
+        for (key, value) in map.as_map_ref() {
+            let key = key_segment(key);
+
+            let segments: Vec<&str> = if sep.is_empty() {
+                Vec::from([key.as_str()])
+            } else {
+                key.split(sep).collect()
+            };
+
+            insert_path(&mut root, &segments, value.clone());
+        }
+
+        if root.len() == 1 {
+            if let Some(value) = root.get(&Value::from("")) {
+                return value.clone();
+            }
+        }
+
+        sequencify(Value::Map(MapValue::from(root)))
+    }
+
+    fn flatten_into(&self, path: Option<&str>, sep: &str, map: &mut Map) {
+        let child_path = |segment: &str| match path {
+            Some(path) => format!("{path}{sep}{segment}"),
+            None => segment.to_owned(),
+        };
+
+        match self {
+            Self::Seq(seq) if !seq.is_empty() => {
+                for (index, value) in seq.as_slice().iter().enumerate() {
+                    value.flatten_into(Some(&child_path(&index.to_string())), sep, map);
+                }
+            }
+            Self::Map(entries) if !entries.is_empty() => {
+                for (key, value) in entries.as_map_ref() {
+                    value.flatten_into(Some(&child_path(&key_segment(key))), sep, map);
+                }
+            }
+            leaf => {
+                map.insert(Value::from(path.unwrap_or_default()), leaf.clone());
+            }
+        }
+    }
+}
+
+/// Returns the path segment for a map `key`.
+fn key_segment(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.as_str().to_owned(),
+        key => key.to_text(),
+    }
+}
+
+/// Inserts `value` at the path of `segments`, creating (or replacing) intermediate maps.
+fn insert_path(map: &mut Map, segments: &[&str], value: Value) {
+    let Some((segment, rest)) = segments.split_first() else {
+        return;
+    };
+
+    let key = Value::from(*segment);
+
+    if rest.is_empty() {
+        map.insert(key, value);
+        return;
+    }
+
+    let child = map
+        .entry(key)
+        .or_insert_with(|| Value::Map(MapValue::default()));
+
+    if !matches!(child, Value::Map(_)) {
+        *child = Value::Map(MapValue::default());
+    }
+
+    if let Value::Map(child) = child {
+        insert_path(&mut child.0, rest, value);
+    }
+}
+
+/// Converts maps whose keys are exactly the indices `0..n` into sequences, recursively.
+fn sequencify(value: Value) -> Value {
+    let Value::Map(map) = value else {
+        return value;
+    };
+
+    let map = map.into_map();
+
+    let indices: Option<Vec<usize>> = map
+        .keys()
+        .map(|key| match key {
+            Value::String(key) => key.as_str().parse::<usize>().ok(),
+            _ => None,
+        })
+        .collect();
+
+    if let Some(indices) = indices {
+        let is_dense =
+            !indices.is_empty() && (0..indices.len()).all(|index| indices.contains(&index));
+
+        if is_dense {
+            let mut elements: Vec<(usize, Value)> = indices
+                .into_iter()
+                .zip(map.into_values().map(sequencify))
+                .collect();
+            elements.sort_by_key(|(index, _)| *index);
+
+            return Value::Seq(SeqValue::from(
+                elements
+                    .into_iter()
+                    .map(|(_, value)| value)
+                    .collect::<Vec<_>>(),
+            ));
+        }
+    }
+
+    Value::Map(MapValue::from(
+        map.into_iter()
+            .map(|(key, value)| (key, sequencify(value)))
+            .collect::<Map>(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    fn map<const N: usize>(entries: [(&str, Value); N]) -> Value {
+        entries
+            .into_iter()
+            .map(|(key, value)| (Value::from(key), value))
+            .collect()
+    }
+
+    fn flat<const N: usize>(entries: [(&str, Value); N]) -> MapValue {
+        let Value::Map(map) = map(entries) else {
+            unreachable!()
+        };
+        map
+    }
+
+    #[test]
+    fn nested_maps() {
+        let value = map([
+            ("name", Value::from("lilliput")),
+            (
+                "server",
+                map([
+                    ("host", Value::from("localhost")),
+                    ("port", Value::from(8080_u16)),
+                ]),
+            ),
+        ]);
+
+        let flattened = value.flatten(".");
+        assert_eq!(
+            flattened,
+            flat([
+                ("name", Value::from("lilliput")),
+                ("server.host", Value::from("localhost")),
+                ("server.port", Value::from(8080_u16)),
+            ])
+        );
+
+        assert_eq!(Value::unflatten(&flattened, "."), value);
+    }
+
+    #[test]
+    fn seqs() {
+        let seq: Value = (0..12_u8).map(Value::from).collect();
+        let value = map([
+            ("seq", seq),
+            ("empty", Value::Seq(SeqValue::default())),
+            ("nested", map([("inner", Value::from(true))])),
+        ]);
+
+        let flattened = value.flatten("/");
+        assert_eq!(flattened.len(), 12 + 2);
+        assert_eq!(
+            flattened.as_map_ref().get(&Value::from("seq/0")),
+            Some(&Value::from(0_u8))
+        );
+        assert_eq!(
+            flattened.as_map_ref().get(&Value::from("seq/11")),
+            Some(&Value::from(11_u8))
+        );
+        assert_eq!(
+            flattened.as_map_ref().get(&Value::from("empty")),
+            Some(&Value::Seq(SeqValue::default()))
+        );
+        assert_eq!(
+            flattened.as_map_ref().get(&Value::from("nested/inner")),
+            Some(&Value::from(true))
+        );
+
+        assert_eq!(Value::unflatten(&flattened, "/"), value);
+    }
+
+    #[test]
+    fn non_string_keys() {
+        let value: Value = [(Value::from(1_u8), map([("a", Value::from(true))]))]
+            .into_iter()
+            .collect();
+
+        let flattened = value.flatten(".");
+        assert_eq!(flattened, flat([("1.a", Value::from(true))]));
+
+        // Non-string keys get rebuilt as strings:
+        assert_eq!(
+            Value::unflatten(&flattened, "."),
+            map([("1", map([("a", Value::from(true))]))])
+        );
+    }
+
+    #[test]
+    fn scalars() {
+        let value = Value::from(42_u8);
+        let flattened = value.flatten(".");
+        assert_eq!(flattened, flat([("", Value::from(42_u8))]));
+        assert_eq!(Value::unflatten(&flattened, "."), value);
+
+        let empty = Value::Map(MapValue::default());
+        assert_eq!(Value::unflatten(&empty.flatten("."), "."), empty);
+    }
+
+    #[test]
+    fn conflicting_keys() {
+        let flattened = flat([("a", Value::from(1_u8)), ("a.b", Value::from(2_u8))]);
+
+        assert_eq!(
+            Value::unflatten(&flattened, "."),
+            map([("a", map([("b", Value::from(2_u8))]))])
+        );
+    }
+}
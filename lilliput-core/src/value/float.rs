@@ -1,12 +1,10 @@
-use std::hash::{Hash, Hasher};
+use core::hash::{Hash, Hasher};
 
 #[cfg(any(test, feature = "testing"))]
 use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
 use proptest_derive::Arbitrary;
 
-use decorum::{constraint::IsFloat, proxy::Constrained};
-
 /// Represents a floating-point number.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
 #[derive(Copy, Clone)]
@@ -33,6 +31,22 @@ impl FloatValue {
             FloatValue::F64(value) => value,
         }
     }
+
+    /// Returns the ordering between `self` and `other`,
+    /// as defined by the `totalOrder` predicate of IEEE 754 (2008 revision).
+    ///
+    /// Values get compared as `f64`, ordering `-0.0` before `+0.0`,
+    /// and NaNs consistently, based on their sign and payload:
+    ///
+    /// ```plain
+    /// -qNaN < -sNaN < -inf < … < -0.0 < +0.0 < … < +inf < +sNaN < +qNaN
+    /// ```
+    pub fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let lhs = f64::from_bits(self.canonical_bits());
+        let rhs = f64::from_bits(other.canonical_bits());
+
+        lhs.total_cmp(&rhs)
+    }
 }
 
 impl Default for FloatValue {
@@ -69,30 +83,30 @@ impl Eq for FloatValue {}
 
 impl PartialEq for FloatValue {
     fn eq(&self, other: &Self) -> bool {
-        self.canonical_total().eq(&other.canonical_total())
+        self.canonical_bits() == other.canonical_bits()
     }
 }
 
 impl Ord for FloatValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.canonical_total().cmp(&other.canonical_total())
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.total_cmp(other)
     }
 }
 
 impl PartialOrd for FloatValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Hash for FloatValue {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.canonical_total().hash(state);
+        self.canonical_bits().hash(state);
     }
 }
 
-impl std::fmt::Debug for FloatValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for FloatValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             match self {
                 Self::F32(value) => write!(f, "{value:#?}_f32"),
@@ -100,18 +114,18 @@ impl std::fmt::Debug for FloatValue {
             }
         } else {
             match self {
-                Self::F32(value) => std::fmt::Debug::fmt(value, f),
-                Self::F64(value) => std::fmt::Debug::fmt(value, f),
+                Self::F32(value) => core::fmt::Debug::fmt(value, f),
+                Self::F64(value) => core::fmt::Debug::fmt(value, f),
             }
         }
     }
 }
 
-impl std::fmt::Display for FloatValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for FloatValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::F32(value) => std::fmt::Display::fmt(value, f),
-            Self::F64(value) => std::fmt::Display::fmt(value, f),
+            Self::F32(value) => core::fmt::Display::fmt(value, f),
+            Self::F64(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
@@ -160,8 +174,26 @@ impl<'de> serde::Deserialize<'de> for FloatValue {
 }
 
 impl FloatValue {
-    fn canonical_total(self) -> Constrained<f64, IsFloat> {
-        decorum::Total::assert(self.as_f64())
+    /// Returns the value's bits, as a `f64`.
+    ///
+    /// Unlike a native `f32` to `f64` cast this preserves the kind (signaling/quiet) of NaNs.
+    fn canonical_bits(&self) -> u64 {
+        const F32_SIGN_MASK: u64 = 0x8000_0000;
+        const F32_SIGNIFICAND_MASK: u64 = 0x007f_ffff;
+        const F64_EXPONENT_MASK: u64 = 0x7ff0_0000_0000_0000;
+
+        match *self {
+            Self::F32(value) if value.is_nan() => {
+                let bits = u64::from(value.to_bits());
+
+                let sign = (bits & F32_SIGN_MASK) << 32;
+                let significand = (bits & F32_SIGNIFICAND_MASK) << (52 - 23);
+
+                sign | F64_EXPONENT_MASK | significand
+            }
+            Self::F32(value) => f64::from(value).to_bits(),
+            Self::F64(value) => value.to_bits(),
+        }
     }
 }
 
@@ -189,6 +221,28 @@ mod tests {
         ]
     }
 
+    fn nan_f32() -> impl Strategy<Value = f32> {
+        (any::<bool>(), 1_u32..(1 << 23)).prop_map(|(is_negative, significand)| {
+            let sign = if is_negative { 1 << 31 } else { 0 };
+            f32::from_bits(sign | 0x7f80_0000 | significand)
+        })
+    }
+
+    fn nan_f64() -> impl Strategy<Value = f64> {
+        (any::<bool>(), 1_u64..(1 << 52)).prop_map(|(is_negative, significand)| {
+            let sign = if is_negative { 1 << 63 } else { 0 };
+            f64::from_bits(sign | 0x7ff0_0000_0000_0000 | significand)
+        })
+    }
+
+    fn encode_with_config(value: FloatValue, config: EncoderConfig) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::new(writer, config);
+        encoder.encode_float_value(&value).unwrap();
+        encoded
+    }
+
     fn non_normal_or_subnormal_f64() -> impl Strategy<Value = f64> {
         proptest::prop_oneof![
             proptest::num::f64::SIGNALING_NAN,
@@ -213,7 +267,119 @@ mod tests {
         assert_eq!(format!("{:#?}", FloatValue::from(4.2_f64)), "4.2_f64");
     }
 
+    #[test]
+    fn total_ordering() {
+        let signaling_nan_f32 = f32::from_bits(0x7f80_0001);
+        let signaling_nan_f64 = f64::from_bits(0x7ff0_0000_0000_0001);
+
+        let values = [
+            vec![FloatValue::F32(-f32::NAN), FloatValue::F64(-f64::NAN)],
+            vec![
+                FloatValue::F32(f32::NEG_INFINITY),
+                FloatValue::F64(f64::NEG_INFINITY),
+            ],
+            vec![FloatValue::F32(-1.0), FloatValue::F64(-1.0)],
+            vec![FloatValue::F32(-0.0), FloatValue::F64(-0.0)],
+            vec![FloatValue::F32(0.0), FloatValue::F64(0.0)],
+            vec![FloatValue::F32(1.0), FloatValue::F64(1.0)],
+            vec![
+                FloatValue::F32(f32::INFINITY),
+                FloatValue::F64(f64::INFINITY),
+            ],
+            vec![FloatValue::F64(signaling_nan_f64)],
+            vec![FloatValue::F32(signaling_nan_f32)],
+            vec![FloatValue::F32(f32::NAN), FloatValue::F64(f64::NAN)],
+        ];
+
+        for (lhs_index, lhs_values) in values.iter().enumerate() {
+            for (rhs_index, rhs_values) in values.iter().enumerate() {
+                for lhs in lhs_values {
+                    for rhs in rhs_values {
+                        let expected = lhs_index.cmp(&rhs_index);
+                        assert_eq!(lhs.total_cmp(rhs), expected, "{lhs:?} vs. {rhs:?}");
+                        assert_eq!(lhs.cmp(rhs), expected, "{lhs:?} vs. {rhs:?}");
+                        assert_eq!(lhs == rhs, expected.is_eq(), "{lhs:?} vs. {rhs:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shrink_floats() {
+        let config = EncoderConfig::default().with_packing(PackingMode::None);
+        let shrinking = config.clone().with_shrink_floats(true);
+
+        let decode = |encoded: &[u8]| {
+            let mut decoder = Decoder::from_reader(SliceReader::new(encoded));
+            decoder.decode_float_value().unwrap()
+        };
+
+        // Exactly representable values get narrowed:
+        assert_eq!(
+            encode_with_config(FloatValue::F64(3.5), config.clone()).len(),
+            1 + 8
+        );
+
+        let encoded = encode_with_config(FloatValue::F64(3.5), shrinking.clone());
+        assert_eq!(encoded.len(), 1 + 4);
+        assert!(matches!(decode(&encoded), FloatValue::F32(value) if value == 3.5));
+
+        // Others don't:
+        let encoded = encode_with_config(FloatValue::F64(0.1), shrinking.clone());
+        assert_eq!(encoded.len(), 1 + 8);
+        assert!(matches!(decode(&encoded), FloatValue::F64(value) if value == 0.1));
+
+        // Neither do NaNs whose payloads would get truncated:
+        let nan = f64::from_bits(0x7ff8_0000_0000_0001);
+        let encoded = encode_with_config(FloatValue::F64(nan), shrinking);
+        assert_eq!(encoded.len(), 1 + 8);
+    }
+
     proptest! {
+        #[test]
+        fn shrinking_is_lossless(value in prop_oneof![any::<f64>(), nan_f64()], config in EncoderConfig::arbitrary()) {
+            let config = config.with_packing(PackingMode::None).with_shrink_floats(true);
+
+            let encoded = encode_with_config(FloatValue::F64(value), config);
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            let decoded = decoder.decode_float_value().unwrap();
+            prop_assert_eq!(decoded, FloatValue::F64(value));
+        }
+
+        #[test]
+        fn normalized_nan_f32_encodes_canonically(value in nan_f32(), config in EncoderConfig::arbitrary()) {
+            let config = config.with_normalize_floats(true);
+
+            let encoded = encode_with_config(FloatValue::F32(value), config.clone());
+            let canonical = encode_with_config(FloatValue::F32(f32::NAN), config);
+            prop_assert_eq!(encoded, canonical);
+        }
+
+        #[test]
+        fn normalized_nan_f64_encodes_canonically(value in nan_f64(), config in EncoderConfig::arbitrary()) {
+            let config = config.with_normalize_floats(true);
+
+            let encoded = encode_with_config(FloatValue::F64(value), config.clone());
+            let canonical = encode_with_config(FloatValue::F64(f64::NAN), config);
+            prop_assert_eq!(encoded, canonical);
+        }
+
+        #[test]
+        fn normalized_zero_encodes_canonically(config in EncoderConfig::arbitrary()) {
+            let config = config.with_normalize_floats(true);
+
+            prop_assert_eq!(
+                encode_with_config(FloatValue::F32(-0.0), config.clone()),
+                encode_with_config(FloatValue::F32(0.0), config.clone())
+            );
+            prop_assert_eq!(
+                encode_with_config(FloatValue::F64(-0.0), config.clone()),
+                encode_with_config(FloatValue::F64(0.0), config)
+            );
+        }
+
         #[test]
         fn encode_decode_roundtrip(value in FloatValue::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
@@ -237,6 +403,33 @@ mod tests {
             prop_assert_eq!(&decoded, &value);
         }
 
+        #[test]
+        fn reencodes_f32_identically(value in prop_oneof![any::<f32>(), nan_f32()], config in EncoderConfig::arbitrary()) {
+            let encoded = encode_with_config(FloatValue::F32(value), config.clone());
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            let decoded = decoder.decode_float_value().unwrap();
+
+            // Floats of up to 4 bytes never get widened:
+            prop_assert!(matches!(decoded, FloatValue::F32(_)));
+
+            prop_assert_eq!(encode_with_config(decoded, config), encoded);
+        }
+
+        #[test]
+        fn reencodes_f64_identically(value in prop_oneof![any::<f64>(), nan_f64()], config in EncoderConfig::arbitrary()) {
+            let encoded = encode_with_config(FloatValue::F64(value), config.clone());
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            let decoded = decoder.decode_float_value().unwrap();
+
+            // Floats of up to 4 bytes decode as `f32`, wider ones as `f64`:
+            let width = encoded.len() - 1;
+            prop_assert_eq!(matches!(decoded, FloatValue::F32(_)), width <= 4);
+
+            prop_assert_eq!(encode_with_config(decoded, config), encoded);
+        }
+
         #[test]
         fn non_normal_or_subnormal_f32_encodes_optimally(value in non_normal_or_subnormal_f32()) {
             let config = EncoderConfig::default().with_packing(PackingMode::Optimal);
@@ -1,4 +1,4 @@
-use std::{
+use core::{
     hash::{Hash, Hasher},
     num::TryFromIntError,
 };
@@ -14,6 +14,10 @@ mod unsigned;
 pub use self::{signed::SignedIntValue, unsigned::UnsignedIntValue};
 
 /// Represents an integer number.
+///
+/// Decoding preserves the signedness an integer was encoded with
+/// (see `IntValue::wire_signedness`), even for values representable by either,
+/// so that re-encoding a decoded value reproduces its original signedness.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
 #[derive(Copy, Clone)]
 pub enum IntValue {
@@ -31,6 +35,26 @@ impl IntValue {
             Self::Unsigned(_) => false,
         }
     }
+
+    /// Returns the signedness `self` gets encoded with.
+    ///
+    /// For decoded values this is the signedness they were encoded with,
+    /// e.g. `5_i8` decodes as `Signed`, rather than getting normalized to `Unsigned`.
+    pub fn wire_signedness(&self) -> Signedness {
+        match self {
+            Self::Signed(_) => Signedness::Signed,
+            Self::Unsigned(_) => Signedness::Unsigned,
+        }
+    }
+}
+
+/// The signedness of an integer value.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Signedness {
+    /// Signed integer.
+    Signed,
+    /// Unsigned integer.
+    Unsigned,
 }
 
 impl Default for IntValue {
@@ -78,6 +102,40 @@ macro_rules! impl_int_value_from_size {
 impl_int_value_from_size!(isize);
 impl_int_value_from_size!(usize);
 
+macro_rules! impl_try_from_int_value {
+    ($t:ty) => {
+        impl TryFrom<IntValue> for $t {
+            type Error = TryFromIntError;
+
+            fn try_from(value: IntValue) -> Result<Self, Self::Error> {
+                <$t>::try_from(value.canonicalized())
+            }
+        }
+    };
+}
+
+impl_try_from_int_value!(i8);
+impl_try_from_int_value!(i16);
+impl_try_from_int_value!(i32);
+impl_try_from_int_value!(i64);
+impl_try_from_int_value!(isize);
+
+impl_try_from_int_value!(u8);
+impl_try_from_int_value!(u16);
+impl_try_from_int_value!(u32);
+impl_try_from_int_value!(u64);
+impl_try_from_int_value!(u128);
+impl_try_from_int_value!(usize);
+
+impl TryFrom<IntValue> for i128 {
+    type Error = TryFromIntError;
+
+    /// Converts `value` into an `i128`, which can represent any value, thus never failing.
+    fn try_from(value: IntValue) -> Result<Self, Self::Error> {
+        Ok(value.canonicalized())
+    }
+}
+
 impl PartialEq for IntValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -108,7 +166,7 @@ impl PartialEq for IntValue {
 }
 
 impl PartialOrd for IntValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -116,7 +174,7 @@ impl PartialOrd for IntValue {
 impl Eq for IntValue {}
 
 impl Ord for IntValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         match (self, other) {
             (Self::Unsigned(lhs), Self::Unsigned(rhs)) => lhs.cmp(rhs),
             (Self::Signed(lhs), Self::Signed(rhs)) => lhs.cmp(rhs),
@@ -124,7 +182,7 @@ impl Ord for IntValue {
                 let lhs = lhs.canonicalized();
                 let rhs = rhs.canonicalized();
                 if rhs.is_negative() {
-                    std::cmp::Ordering::Greater
+                    core::cmp::Ordering::Greater
                 } else {
                     lhs.cmp(&(rhs as u64))
                 }
@@ -133,7 +191,7 @@ impl Ord for IntValue {
                 let lhs = lhs.canonicalized();
                 let rhs = rhs.canonicalized();
                 if lhs.is_negative() {
-                    std::cmp::Ordering::Less
+                    core::cmp::Ordering::Less
                 } else {
                     (lhs as u64).cmp(&rhs)
                 }
@@ -144,37 +202,39 @@ impl Ord for IntValue {
 
 impl Hash for IntValue {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        // Bytes get written directly (rather than via `Hasher::write_u64`, or `[u8; N]::hash`),
+        // since those use native byte order, making the hash depend on the host's endianness:
         match *self {
             Self::Unsigned(value) => {
                 let value = value.canonicalized();
-                value.to_ne_bytes().hash(state)
+                state.write(&value.to_le_bytes())
             }
             Self::Signed(value) => {
                 let value = value.canonicalized();
                 if value.is_negative() {
-                    value.to_ne_bytes().hash(state)
+                    state.write(&value.to_le_bytes())
                 } else {
-                    (value as u64).to_ne_bytes().hash(state)
+                    state.write(&(value as u64).to_le_bytes())
                 }
             }
         }
     }
 }
 
-impl std::fmt::Debug for IntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for IntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::Signed(value) => std::fmt::Debug::fmt(&value, f),
-            Self::Unsigned(value) => std::fmt::Debug::fmt(&value, f),
+            Self::Signed(value) => core::fmt::Debug::fmt(&value, f),
+            Self::Unsigned(value) => core::fmt::Debug::fmt(&value, f),
         }
     }
 }
 
-impl std::fmt::Display for IntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for IntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::Signed(value) => std::fmt::Display::fmt(value, f),
-            Self::Unsigned(value) => std::fmt::Display::fmt(value, f),
+            Self::Signed(value) => core::fmt::Display::fmt(value, f),
+            Self::Unsigned(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
@@ -268,6 +328,121 @@ impl IntValue {
             IntValue::Unsigned(unsigned) => Ok(unsigned),
         }
     }
+
+    /// Returns the value as an `i64`, if it fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        i64::try_from(self.canonicalized()).ok()
+    }
+
+    /// Returns the value as a `u64`, if it fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::try_from(self.canonicalized()).ok()
+    }
+
+    /// Returns the value as an `i128`, if it fits.
+    ///
+    /// Since any value fits into an `i128` this always returns `Some(…)`.
+    pub fn as_i128(&self) -> Option<i128> {
+        Some(self.canonicalized())
+    }
+
+    /// Returns the value as a `u128`, if it fits.
+    pub fn as_u128(&self) -> Option<u128> {
+        u128::try_from(self.canonicalized()).ok()
+    }
+
+    /// Checked integer addition, across signed and unsigned values.
+    ///
+    /// Returns `None` if the result is neither representable as `i64`, nor as `u64`.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let result = self.canonicalized().checked_add(rhs.canonicalized())?;
+        Self::from_canonicalized(result, self.is_signed() || rhs.is_signed())
+    }
+
+    /// Checked integer subtraction, across signed and unsigned values.
+    ///
+    /// Returns `None` if the result is neither representable as `i64`, nor as `u64`.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let result = self.canonicalized().checked_sub(rhs.canonicalized())?;
+        Self::from_canonicalized(result, self.is_signed() || rhs.is_signed())
+    }
+
+    /// Checked integer multiplication, across signed and unsigned values.
+    ///
+    /// Returns `None` if the result is neither representable as `i64`, nor as `u64`.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let result = self.canonicalized().checked_mul(rhs.canonicalized())?;
+        Self::from_canonicalized(result, self.is_signed() || rhs.is_signed())
+    }
+
+    /// Saturating integer addition, across signed and unsigned values.
+    ///
+    /// Results below `i64::MIN` saturate at `i64::MIN`, results above `u64::MAX` at `u64::MAX`.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        let result = self.canonicalized() + rhs.canonicalized();
+        Self::from_canonicalized_saturating(result, self.is_signed() || rhs.is_signed())
+    }
+
+    /// Saturating integer subtraction, across signed and unsigned values.
+    ///
+    /// Results below `i64::MIN` saturate at `i64::MIN`, results above `u64::MAX` at `u64::MAX`.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        let result = self.canonicalized() - rhs.canonicalized();
+        Self::from_canonicalized_saturating(result, self.is_signed() || rhs.is_signed())
+    }
+
+    /// Returns the value as an `i64`, saturating at `i64::MAX` if it doesn't fit.
+    pub fn to_i64_saturating(&self) -> i64 {
+        let value = self.canonicalized();
+        i64::try_from(value).unwrap_or(i64::MAX)
+    }
+
+    /// Returns the value as a `u64`, saturating at `0` if negative.
+    pub fn to_u64_saturating(&self) -> u64 {
+        let value = self.canonicalized();
+        u64::try_from(value).unwrap_or(u64::MIN)
+    }
+
+    /// Returns the value as an `i64`, wrapping around (i.e. two's complement) if it doesn't fit.
+    pub fn to_i64_wrapping(&self) -> i64 {
+        self.canonicalized() as i64
+    }
+
+    /// Returns the value as a `u64`, wrapping around (i.e. two's complement) if it doesn't fit.
+    pub fn to_u64_wrapping(&self) -> u64 {
+        self.canonicalized() as u64
+    }
+
+    /// Returns the value as an `i128`, which can represent both `i64` and `u64`.
+    fn canonicalized(&self) -> i128 {
+        match self {
+            Self::Signed(value) => value.canonicalized().into(),
+            Self::Unsigned(value) => value.canonicalized().into(),
+        }
+    }
+
+    /// Converts an `i128` back into a value,
+    /// preferring a signed value if `prefer_signed` is `true`.
+    ///
+    /// Negative results are always signed.
+    fn from_canonicalized(value: i128, prefer_signed: bool) -> Option<Self> {
+        if value.is_negative() || prefer_signed {
+            if let Ok(signed) = i64::try_from(value) {
+                return Some(Self::from(signed));
+            }
+        }
+
+        u64::try_from(value).ok().map(Self::from)
+    }
+
+    /// Converts an `i128` back into a value, clamped to `i64::MIN..=u64::MAX`,
+    /// preferring a signed value if `prefer_signed` is `true`.
+    fn from_canonicalized_saturating(value: i128, prefer_signed: bool) -> Self {
+        let clamped = value.clamp(i128::from(i64::MIN), i128::from(u64::MAX));
+
+        Self::from_canonicalized(clamped, prefer_signed)
+            .expect("clamped value should be representable")
+    }
 }
 
 #[cfg(test)]
@@ -278,7 +453,7 @@ mod tests {
     use test_log::test;
 
     use crate::{
-        config::EncoderConfig,
+        config::{DecoderConfig, EncoderConfig, IntWidth},
         decoder::Decoder,
         encoder::Encoder,
         io::{SliceReader, VecWriter},
@@ -287,7 +462,246 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn as_primitive() {
+        let value = IntValue::from(u64::MAX);
+        assert_eq!(value.as_i64(), None);
+        assert_eq!(value.as_u64(), Some(u64::MAX));
+        assert_eq!(value.as_i128(), Some(u64::MAX as i128));
+        assert_eq!(value.as_u128(), Some(u64::MAX as u128));
+
+        let value = IntValue::from(i64::MIN);
+        assert_eq!(value.as_i64(), Some(i64::MIN));
+        assert_eq!(value.as_u64(), None);
+        assert_eq!(value.as_i128(), Some(i64::MIN as i128));
+        assert_eq!(value.as_u128(), None);
+
+        let value = IntValue::from(i64::MAX as u64);
+        assert_eq!(value.as_i64(), Some(i64::MAX));
+        assert_eq!(value.as_u64(), Some(i64::MAX as u64));
+
+        let value = IntValue::from(i64::MAX as u64 + 1);
+        assert_eq!(value.as_i64(), None);
+        assert_eq!(value.as_u64(), Some(i64::MAX as u64 + 1));
+
+        let value = IntValue::from(-1_i8);
+        assert_eq!(value.as_i64(), Some(-1));
+        assert_eq!(value.as_u64(), None);
+        assert_eq!(value.as_u128(), None);
+    }
+
+    #[test]
+    fn try_into_primitive() {
+        macro_rules! assert_bounds {
+            ($t:ty) => {
+                let min = IntValue::from_canonicalized(<$t>::MIN as i128, true).unwrap();
+                let max = IntValue::from_canonicalized(<$t>::MAX as i128, false).unwrap();
+                assert_eq!(<$t>::try_from(min), Ok(<$t>::MIN));
+                assert_eq!(<$t>::try_from(max), Ok(<$t>::MAX));
+
+                if let Some(below) = (<$t>::MIN as i128).checked_sub(1) {
+                    if let Some(below) = IntValue::from_canonicalized(below, true) {
+                        assert!(<$t>::try_from(below).is_err());
+                    }
+                }
+
+                if let Some(above) = (<$t>::MAX as i128).checked_add(1) {
+                    if let Some(above) = IntValue::from_canonicalized(above, false) {
+                        assert!(<$t>::try_from(above).is_err());
+                    }
+                }
+            };
+        }
+
+        assert_bounds!(i8);
+        assert_bounds!(i16);
+        assert_bounds!(i32);
+        assert_bounds!(i64);
+        assert_bounds!(isize);
+
+        assert_bounds!(u8);
+        assert_bounds!(u16);
+        assert_bounds!(u32);
+        assert_bounds!(u64);
+        assert_bounds!(usize);
+
+        assert!(u8::try_from(IntValue::from(256_u16)).is_err());
+        assert!(i8::try_from(IntValue::from(128_u8)).is_err());
+        assert!(u64::try_from(IntValue::from(-1_i8)).is_err());
+        assert!(u128::try_from(IntValue::from(-1_i8)).is_err());
+
+        // Signedness gets canonicalized:
+        assert_eq!(u8::try_from(IntValue::from(5_i64)), Ok(5));
+        assert_eq!(i8::try_from(IntValue::from(5_u64)), Ok(5));
+
+        assert_eq!(
+            i128::try_from(IntValue::from(u64::MAX)),
+            Ok(u64::MAX as i128)
+        );
+        assert_eq!(
+            i128::try_from(IntValue::from(i64::MIN)),
+            Ok(i64::MIN as i128)
+        );
+        assert_eq!(
+            u128::try_from(IntValue::from(u64::MAX)),
+            Ok(u64::MAX as u128)
+        );
+    }
+
+    #[test]
+    fn wire_signedness() {
+        assert_eq!(IntValue::from(5_i8).wire_signedness(), Signedness::Signed);
+        assert_eq!(IntValue::from(5_u8).wire_signedness(), Signedness::Unsigned);
+    }
+
+    #[test]
+    fn checked_sub_across_signedness() {
+        let lhs = IntValue::from(3_u8);
+        let rhs = IntValue::from(5_i8);
+
+        let result = lhs.checked_sub(rhs).unwrap();
+        assert!(result.is_signed());
+        assert_eq!(result, IntValue::from(-2_i64));
+
+        let result = rhs.checked_sub(lhs).unwrap();
+        assert!(result.is_signed());
+        assert_eq!(result, IntValue::from(2_u64));
+
+        let result = IntValue::from(3_u8)
+            .checked_sub(IntValue::from(5_u8))
+            .unwrap();
+        assert_eq!(result, IntValue::from(-2_i64));
+
+        assert_eq!(
+            IntValue::from(u64::MAX).checked_add(IntValue::from(1_u8)),
+            None
+        );
+        assert_eq!(
+            IntValue::from(i64::MIN).checked_sub(IntValue::from(1_u8)),
+            None
+        );
+        assert_eq!(
+            IntValue::from(u64::MAX).checked_mul(IntValue::from(-1_i8)),
+            None
+        );
+    }
+
+    #[test]
+    fn saturating_across_signedness() {
+        assert_eq!(
+            IntValue::from(u64::MAX).saturating_add(IntValue::from(1_u8)),
+            IntValue::from(u64::MAX)
+        );
+        assert_eq!(
+            IntValue::from(i64::MIN).saturating_sub(IntValue::from(1_u8)),
+            IntValue::from(i64::MIN)
+        );
+        assert_eq!(
+            IntValue::from(3_u8).saturating_sub(IntValue::from(5_u8)),
+            IntValue::from(-2_i64)
+        );
+
+        assert_eq!(IntValue::from(u64::MAX).to_i64_saturating(), i64::MAX);
+        assert_eq!(IntValue::from(-1_i8).to_u64_saturating(), 0);
+        assert_eq!(IntValue::from(u64::MAX).to_i64_wrapping(), -1);
+        assert_eq!(IntValue::from(-1_i8).to_u64_wrapping(), u64::MAX);
+    }
+
     proptest! {
+        #[test]
+        fn checked_arithmetic(lhs in IntValue::arbitrary(), rhs in IntValue::arbitrary()) {
+            let representable = |value: i128| -> Option<i128> {
+                (i128::from(i64::MIN)..=i128::from(u64::MAX)).contains(&value).then_some(value)
+            };
+
+            let lhs_i128 = lhs.canonicalized();
+            let rhs_i128 = rhs.canonicalized();
+
+            let expected = lhs_i128.checked_add(rhs_i128).and_then(representable);
+            let actual = lhs.checked_add(rhs).map(|value| value.canonicalized());
+            prop_assert_eq!(actual, expected);
+
+            let expected = lhs_i128.checked_sub(rhs_i128).and_then(representable);
+            let actual = lhs.checked_sub(rhs).map(|value| value.canonicalized());
+            prop_assert_eq!(actual, expected);
+
+            let expected = lhs_i128.checked_mul(rhs_i128).and_then(representable);
+            let actual = lhs.checked_mul(rhs).map(|value| value.canonicalized());
+            prop_assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn saturating_arithmetic(lhs in IntValue::arbitrary(), rhs in IntValue::arbitrary()) {
+            let saturate = |value: i128| -> i128 {
+                value.clamp(i128::from(i64::MIN), i128::from(u64::MAX))
+            };
+
+            let lhs_i128 = lhs.canonicalized();
+            let rhs_i128 = rhs.canonicalized();
+
+            let expected = saturate(lhs_i128.saturating_add(rhs_i128));
+            let actual = lhs.saturating_add(rhs);
+            prop_assert_eq!(actual.canonicalized(), expected);
+            if let Some(checked) = lhs.checked_add(rhs) {
+                prop_assert_eq!(actual.is_signed(), checked.is_signed());
+                prop_assert_eq!(actual, checked);
+            }
+
+            let expected = saturate(lhs_i128.saturating_sub(rhs_i128));
+            let actual = lhs.saturating_sub(rhs);
+            prop_assert_eq!(actual.canonicalized(), expected);
+            if let Some(checked) = lhs.checked_sub(rhs) {
+                prop_assert_eq!(actual.is_signed(), checked.is_signed());
+                prop_assert_eq!(actual, checked);
+            }
+        }
+
+        #[test]
+        fn saturating_and_wrapping_conversions(value in IntValue::arbitrary()) {
+            let canonicalized = value.canonicalized();
+
+            let expected = canonicalized.clamp(i128::from(i64::MIN), i128::from(i64::MAX));
+            prop_assert_eq!(i128::from(value.to_i64_saturating()), expected);
+
+            let expected = canonicalized.clamp(i128::from(u64::MIN), i128::from(u64::MAX));
+            prop_assert_eq!(i128::from(value.to_u64_saturating()), expected);
+
+            prop_assert_eq!(value.to_i64_wrapping(), canonicalized as i64);
+            prop_assert_eq!(value.to_u64_wrapping(), canonicalized as u64);
+
+            if let Some(value_i64) = value.as_i64() {
+                prop_assert_eq!(value.to_i64_saturating(), value_i64);
+                prop_assert_eq!(value.to_i64_wrapping(), value_i64);
+            }
+
+            if let Some(value_u64) = value.as_u64() {
+                prop_assert_eq!(value.to_u64_saturating(), value_u64);
+                prop_assert_eq!(value.to_u64_wrapping(), value_u64);
+            }
+        }
+
+        #[test]
+        fn try_into_primitive_roundtrip(value in IntValue::arbitrary()) {
+            let canonicalized = value.canonicalized();
+
+            prop_assert_eq!(i8::try_from(value).ok(), i8::try_from(canonicalized).ok());
+            prop_assert_eq!(i16::try_from(value).ok(), i16::try_from(canonicalized).ok());
+            prop_assert_eq!(i32::try_from(value).ok(), i32::try_from(canonicalized).ok());
+            prop_assert_eq!(i64::try_from(value).ok(), i64::try_from(canonicalized).ok());
+            prop_assert_eq!(u8::try_from(value).ok(), u8::try_from(canonicalized).ok());
+            prop_assert_eq!(u16::try_from(value).ok(), u16::try_from(canonicalized).ok());
+            prop_assert_eq!(u32::try_from(value).ok(), u32::try_from(canonicalized).ok());
+            prop_assert_eq!(u64::try_from(value).ok(), u64::try_from(canonicalized).ok());
+
+            if let Ok(signed) = i64::try_from(value) {
+                prop_assert_eq!(IntValue::from(signed), value);
+            }
+
+            if let Ok(unsigned) = u64::try_from(value) {
+                prop_assert_eq!(IntValue::from(unsigned), value);
+            }
+        }
+
         #[test]
         fn eq(signed in i8::MIN..=i8::MAX, unsigned in u8::MIN..=u8::MAX) {
             let signed_values = [
@@ -383,7 +797,7 @@ mod tests {
 
         #[test]
         fn hash(signed in i8::MIN..=i8::MAX, unsigned in u8::MIN..=u8::MAX) {
-            use std::hash::BuildHasher as _;
+            use core::hash::BuildHasher as _;
 
             let signed_values = [
                 IntValue::from(signed),
@@ -418,9 +832,53 @@ mod tests {
                     prop_assert_eq!(lhs_hash, rhs_hash);
                 }
             }
+
+            // signed vs unsigned
+            if signed >= 0 {
+                let unsigned_value = IntValue::from(signed as u8);
+                for signed_value in &signed_values {
+                    prop_assert_eq!(hash_stream(signed_value), hash_stream(&unsigned_value));
+                }
+            }
         }
     }
 
+    /// Returns the bytes fed into a hasher by `value`'s `Hash` impl.
+    fn hash_stream(value: &IntValue) -> Vec<u8> {
+        #[derive(Default)]
+        struct RecordingHasher(Vec<u8>);
+
+        impl Hasher for RecordingHasher {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.0.extend_from_slice(bytes);
+            }
+        }
+
+        let mut hasher = RecordingHasher::default();
+        value.hash(&mut hasher);
+        hasher.0
+    }
+
+    #[test]
+    fn hash_stream_is_endianness_independent() {
+        assert_eq!(
+            hash_stream(&IntValue::from(0x0102_0304_u32)),
+            [0x04, 0x03, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            hash_stream(&IntValue::from(0x0102_0304_i32)),
+            [0x04, 0x03, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            hash_stream(&IntValue::from(-2_i16)),
+            [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+    }
+
     #[test]
     fn display() {
         assert_eq!(format!("{}", IntValue::from(42_u8)), "42");
@@ -480,5 +938,78 @@ mod tests {
             };
             prop_assert_eq!(&decoded, &value);
         }
+
+        #[test]
+        fn preserves_wire_signedness(value in 0_i64..=i8::MAX as i64, config in EncoderConfig::arbitrary()) {
+            let value = IntValue::from(value);
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_int_value(&value).unwrap();
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_int_value().unwrap();
+
+            // Equality is signedness-agnostic, so check the variant explicitly:
+            prop_assert!(matches!(decoded, IntValue::Signed(_)));
+            prop_assert_eq!(decoded.wire_signedness(), Signedness::Signed);
+        }
+
+        #[test]
+        fn normalize_int_sign(value in i64::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_i64(value).unwrap();
+
+            let decoder_config = DecoderConfig::default().with_normalize_int_sign(true);
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::new(reader, decoder_config);
+            let decoded = decoder.decode_int_value().unwrap();
+
+            prop_assert_eq!(decoded, IntValue::from(value));
+            prop_assert_eq!(decoded.is_signed(), value < 0);
+
+            // Integers within values get normalized, too:
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::new(reader, decoder_config);
+            let Value::Int(decoded) = decoder.decode_value().unwrap() else {
+                panic!("expected int");
+            };
+            prop_assert_eq!(decoded.is_signed(), value < 0);
+
+            // Without the flag the wire signedness is retained:
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            prop_assert!(decoder.decode_int_value().unwrap().is_signed());
+        }
+
+        #[test]
+        fn fixed64_width(value in IntValue::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let config = config.with_int_width(IntWidth::Fixed64);
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            let header = match value {
+                IntValue::Signed(value) => encoder.header_for_signed_int(value.canonicalized()),
+                IntValue::Unsigned(value) => encoder.header_for_unsigned_int(value.canonicalized()),
+            };
+            encoder.encode_int_value(&value).unwrap();
+
+            prop_assert_eq!(encoded.len(), 1 + 8);
+            prop_assert_eq!(header.extended_width(), Some(8));
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            prop_assert_eq!(decoder.decode_int_header().unwrap(), header);
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_int_value().unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
     }
 }
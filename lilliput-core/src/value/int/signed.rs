@@ -1,4 +1,4 @@
-use std::{
+use core::{
     hash::{Hash, Hasher},
     num::TryFromIntError,
 };
@@ -50,7 +50,7 @@ impl_signed_int_value_from!(i64 => I64);
 macro_rules! impl_try_from_signed_int_value {
     ($t:ty) => {
         impl TryFrom<SignedIntValue> for $t {
-            type Error = std::num::TryFromIntError;
+            type Error = core::num::TryFromIntError;
 
             fn try_from(value: SignedIntValue) -> Result<Self, Self::Error> {
                 match value {
@@ -89,7 +89,7 @@ impl PartialEq for SignedIntValue {
 }
 
 impl PartialOrd for SignedIntValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -97,7 +97,7 @@ impl PartialOrd for SignedIntValue {
 impl Eq for SignedIntValue {}
 
 impl Ord for SignedIntValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.canonicalized().cmp(&other.canonicalized())
     }
 }
@@ -108,8 +108,8 @@ impl Hash for SignedIntValue {
     }
 }
 
-impl std::fmt::Debug for SignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for SignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             match self {
                 Self::I8(value) => write!(f, "{value:#?}_i8"),
@@ -119,22 +119,22 @@ impl std::fmt::Debug for SignedIntValue {
             }
         } else {
             match self {
-                Self::I8(value) => std::fmt::Debug::fmt(value, f),
-                Self::I16(value) => std::fmt::Debug::fmt(value, f),
-                Self::I32(value) => std::fmt::Debug::fmt(value, f),
-                Self::I64(value) => std::fmt::Debug::fmt(value, f),
+                Self::I8(value) => core::fmt::Debug::fmt(value, f),
+                Self::I16(value) => core::fmt::Debug::fmt(value, f),
+                Self::I32(value) => core::fmt::Debug::fmt(value, f),
+                Self::I64(value) => core::fmt::Debug::fmt(value, f),
             }
         }
     }
 }
 
-impl std::fmt::Display for SignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::I8(value) => std::fmt::Display::fmt(value, f),
-            Self::I16(value) => std::fmt::Display::fmt(value, f),
-            Self::I32(value) => std::fmt::Display::fmt(value, f),
-            Self::I64(value) => std::fmt::Display::fmt(value, f),
+            Self::I8(value) => core::fmt::Display::fmt(value, f),
+            Self::I16(value) => core::fmt::Display::fmt(value, f),
+            Self::I32(value) => core::fmt::Display::fmt(value, f),
+            Self::I64(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
@@ -286,7 +286,7 @@ mod tests {
 
         #[test]
         fn hash(lhs in i8::MIN..=i8::MAX) {
-            use std::hash::BuildHasher as _;
+            use core::hash::BuildHasher as _;
 
             let values = [
                 SignedIntValue::I8(lhs),
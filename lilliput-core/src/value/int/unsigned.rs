@@ -1,4 +1,4 @@
-use std::{
+use core::{
     hash::{Hash, Hasher},
     num::TryFromIntError,
 };
@@ -50,7 +50,7 @@ impl_unsigned_int_value_from!(u64 => U64);
 macro_rules! impl_try_from_unsigned_int_value {
     ($t:ty) => {
         impl TryFrom<UnsignedIntValue> for $t {
-            type Error = std::num::TryFromIntError;
+            type Error = core::num::TryFromIntError;
 
             fn try_from(value: UnsignedIntValue) -> Result<Self, Self::Error> {
                 match value {
@@ -89,7 +89,7 @@ impl PartialEq for UnsignedIntValue {
 }
 
 impl PartialOrd for UnsignedIntValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -97,7 +97,7 @@ impl PartialOrd for UnsignedIntValue {
 impl Eq for UnsignedIntValue {}
 
 impl Ord for UnsignedIntValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.canonicalized().cmp(&other.canonicalized())
     }
 }
@@ -108,8 +108,8 @@ impl Hash for UnsignedIntValue {
     }
 }
 
-impl std::fmt::Debug for UnsignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for UnsignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             match self {
                 Self::U8(value) => write!(f, "{value:#?}_u8"),
@@ -119,22 +119,22 @@ impl std::fmt::Debug for UnsignedIntValue {
             }
         } else {
             match self {
-                Self::U8(value) => std::fmt::Debug::fmt(value, f),
-                Self::U16(value) => std::fmt::Debug::fmt(value, f),
-                Self::U32(value) => std::fmt::Debug::fmt(value, f),
-                Self::U64(value) => std::fmt::Debug::fmt(value, f),
+                Self::U8(value) => core::fmt::Debug::fmt(value, f),
+                Self::U16(value) => core::fmt::Debug::fmt(value, f),
+                Self::U32(value) => core::fmt::Debug::fmt(value, f),
+                Self::U64(value) => core::fmt::Debug::fmt(value, f),
             }
         }
     }
 }
 
-impl std::fmt::Display for UnsignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for UnsignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::U8(value) => std::fmt::Display::fmt(value, f),
-            Self::U16(value) => std::fmt::Display::fmt(value, f),
-            Self::U32(value) => std::fmt::Display::fmt(value, f),
-            Self::U64(value) => std::fmt::Display::fmt(value, f),
+            Self::U8(value) => core::fmt::Display::fmt(value, f),
+            Self::U16(value) => core::fmt::Display::fmt(value, f),
+            Self::U32(value) => core::fmt::Display::fmt(value, f),
+            Self::U64(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
@@ -304,7 +304,7 @@ mod tests {
 
         #[test]
         fn hash(lhs in u8::MIN..=u8::MAX) {
-            use std::hash::BuildHasher as _;
+            use core::hash::BuildHasher as _;
 
             let values = [
                 UnsignedIntValue::U8(lhs),
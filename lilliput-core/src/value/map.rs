@@ -3,6 +3,8 @@ use proptest::{prelude::*, sample::SizeRange};
 #[cfg(any(test, feature = "testing"))]
 use proptest_derive::Arbitrary;
 
+use alloc::vec::Vec;
+
 use super::Value;
 
 /// An ordered map.
@@ -11,7 +13,7 @@ pub type Map = ordermap::OrderMap<Value, Value>;
 
 /// An unordered map.
 #[cfg(not(feature = "preserve_order"))]
-pub type Map = std::collections::BTreeMap<Value, Value>;
+pub type Map = alloc::collections::BTreeMap<Value, Value>;
 
 #[cfg(any(test, feature = "testing"))]
 pub(crate) fn arbitrary_map() -> impl Strategy<Value = Map> {
@@ -54,6 +56,22 @@ impl MapValue {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Sorts the map's entries by their keys, in place.
+    ///
+    /// Without the `"preserve_order"` feature the internal map is always sorted by key,
+    /// making this a no-op.
+    pub fn sort_keys(&mut self) {
+        #[cfg(feature = "preserve_order")]
+        self.0.sort_keys();
+    }
+
+    /// Returns the map's keys, sorted.
+    pub fn sorted_keys(&self) -> Vec<&Value> {
+        let mut keys: Vec<&Value> = self.0.keys().collect();
+        keys.sort();
+        keys
+    }
 }
 
 impl From<Map> for MapValue {
@@ -74,8 +92,26 @@ impl From<MapValue> for Map {
     }
 }
 
-impl std::fmt::Debug for MapValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl FromIterator<(Value, Value)> for MapValue {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Value, Value)>,
+    {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<(Value, Value)> for MapValue {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Value, Value)>,
+    {
+        self.0.extend(iter)
+    }
+}
+
+impl core::fmt::Debug for MapValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_map().entries(self.0.iter()).finish()
     }
 }
@@ -132,7 +168,7 @@ mod tests {
         decoder::Decoder,
         encoder::Encoder,
         io::{SliceReader, VecWriter},
-        value::{NullValue, Value},
+        value::{BoolValue, IntValue, NullValue, Value},
     };
 
     use super::*;
@@ -144,13 +180,70 @@ mod tests {
         let value = MapValue::from(map);
 
         assert_eq!(format!("{value:?}"), "{null: null}");
-        assert_eq!(
-            format!("{value:#?}"),
-            "{\n    Null(\n        null,\n    ): Null(\n        null,\n    ),\n}"
-        );
+        assert_eq!(format!("{value:#?}"), "{\n    null: null,\n}");
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let entries: Vec<(Value, Value)> = (1..=3_u8)
+            .map(|int| {
+                (
+                    Value::from(IntValue::from(int)),
+                    Value::from(BoolValue::from(int % 2 == 0)),
+                )
+            })
+            .collect();
+
+        let mut value: MapValue = entries[..2].iter().cloned().collect();
+        assert_eq!(value.len(), 2);
+
+        value.extend(entries[2..].iter().cloned());
+        assert_eq!(value.len(), 3);
+
+        for (key, expected) in &entries {
+            assert_eq!(value.as_map_ref().get(key), Some(expected));
+        }
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_map_value(&value).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.decode_map_value().unwrap(), value);
     }
 
     proptest! {
+        #[test]
+        fn sort_keys(mut value in MapValue::arbitrary()) {
+            let keys: Vec<Value> = value.sorted_keys().into_iter().cloned().collect();
+            prop_assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+
+            value.sort_keys();
+            prop_assert!(value.as_map_ref().keys().eq(keys.iter()));
+
+            let mut sorted = value.clone();
+            sorted.sort_keys();
+            prop_assert_eq!(&sorted, &value);
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::from_writer(writer);
+            encoder.encode_map_value(&value).unwrap();
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let mut decoded = decoder.decode_map_value().unwrap();
+            decoded.sort_keys();
+
+            let mut reencoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut reencoded);
+            let mut encoder = Encoder::from_writer(writer);
+            encoder.encode_map_value(&decoded).unwrap();
+            prop_assert_eq!(reencoded, encoded);
+        }
+
         #[test]
         fn encode_decode_roundtrip(value in MapValue::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
@@ -0,0 +1,223 @@
+use super::Value;
+
+/// Strategy for merging sequences.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SeqMergeStrategy {
+    /// Replace the sequence with the other one.
+    #[default]
+    Replace,
+    /// Append the other sequence's elements to the sequence.
+    Append,
+}
+
+/// Strategy used for merging values.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MergeStrategy {
+    /// Strategy for merging sequences.
+    pub seqs: SeqMergeStrategy,
+    /// Whether null values in the other map delete the corresponding keys,
+    /// rather than getting inserted as null values.
+    pub null_deletes_keys: bool,
+}
+
+impl MergeStrategy {
+    /// Sets sequence-merging strategy to `seqs`, returning `self`.
+    pub fn with_seqs(mut self, seqs: SeqMergeStrategy) -> Self {
+        self.seqs = seqs;
+        self
+    }
+
+    /// Sets null-deletes-keys to `null_deletes_keys`, returning `self`.
+    pub fn with_null_deletes_keys(mut self, null_deletes_keys: bool) -> Self {
+        self.null_deletes_keys = null_deletes_keys;
+        self
+    }
+}
+
+impl Value {
+    /// Merges `other` into `self`, using the default `MergeStrategy`.
+    ///
+    /// See `Value::merge_with` for details.
+    pub fn merge(&mut self, other: Value) {
+        self.merge_with(other, &MergeStrategy::default());
+    }
+
+    /// Merges `other` into `self`, using `strategy`.
+    ///
+    /// - Maps get merged recursively, entry by entry.
+    /// - Sequences get replaced, or appended to (see `MergeStrategy::seqs`).
+    /// - Any other combination of values results in `other` replacing `self`.
+    pub fn merge_with(&mut self, other: Value, strategy: &MergeStrategy) {
+        match (self, other) {
+            (Value::Map(map), Value::Map(other)) => {
+                for (key, value) in other.into_map() {
+                    if strategy.null_deletes_keys && matches!(value, Value::Null(_)) {
+                        map.0.remove(&key);
+                    } else if let Some(existing) = map.0.get_mut(&key) {
+                        existing.merge_with(value, strategy);
+                    } else {
+                        map.0.insert(key, value);
+                    }
+                }
+            }
+            (Value::Seq(seq), Value::Seq(other)) if strategy.seqs == SeqMergeStrategy::Append => {
+                seq.extend(other.into_vec());
+            }
+            (this, other) => {
+                *this = other;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::value::{NullValue, SeqValue};
+
+    use super::*;
+
+    fn map<const N: usize>(entries: [(&str, Value); N]) -> Value {
+        entries
+            .into_iter()
+            .map(|(key, value)| (Value::from(key), value))
+            .collect()
+    }
+
+    fn seq<const N: usize>(values: [u8; N]) -> Value {
+        values.into_iter().map(Value::from).collect()
+    }
+
+    #[test]
+    fn scalars() {
+        let mut value = Value::from(1_u8);
+        value.merge(Value::from("foo"));
+        assert_eq!(value, Value::from("foo"));
+
+        let mut value = map([("a", Value::from(1_u8))]);
+        value.merge(Value::from(2_u8));
+        assert_eq!(value, Value::from(2_u8));
+    }
+
+    #[test]
+    fn nested_maps() {
+        let mut value = map([
+            ("name", Value::from("foo")),
+            (
+                "server",
+                map([
+                    ("host", Value::from("localhost")),
+                    ("port", Value::from(80_u16)),
+                ]),
+            ),
+        ]);
+
+        value.merge(map([
+            ("debug", Value::from(true)),
+            ("server", map([("port", Value::from(8080_u16))])),
+        ]));
+
+        // Existing keys retain their position, while new ones get appended:
+        let expected = map([
+            ("name", Value::from("foo")),
+            (
+                "server",
+                map([
+                    ("host", Value::from("localhost")),
+                    ("port", Value::from(8080_u16)),
+                ]),
+            ),
+            ("debug", Value::from(true)),
+        ]);
+
+        assert_eq!(value, expected);
+    }
+
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn key_order() {
+        fn keys(value: &Value) -> Vec<&Value> {
+            let Value::Map(map) = value else {
+                panic!("expected map");
+            };
+            map.as_map_ref().keys().collect()
+        }
+
+        let mut value = map([
+            ("c", Value::from(1_u8)),
+            ("a", Value::from(2_u8)),
+            ("b", Value::from(3_u8)),
+        ]);
+        value.merge(map([("d", Value::from(4_u8)), ("a", Value::from(5_u8))]));
+        assert_eq!(
+            keys(&value),
+            [
+                &Value::from("c"),
+                &Value::from("a"),
+                &Value::from("b"),
+                &Value::from("d")
+            ]
+        );
+
+        // Deleting keys retains the order of the remaining ones:
+        let strategy = MergeStrategy::default().with_null_deletes_keys(true);
+        value.merge_with(map([("a", Value::Null(NullValue))]), &strategy);
+        assert_eq!(
+            keys(&value),
+            [&Value::from("c"), &Value::from("b"), &Value::from("d")]
+        );
+    }
+
+    #[test]
+    fn seqs() {
+        let mut value = map([("seq", seq([1, 2]))]);
+        value.merge(map([("seq", seq([3]))]));
+        assert_eq!(value, map([("seq", seq([3]))]));
+
+        let strategy = MergeStrategy::default().with_seqs(SeqMergeStrategy::Append);
+        let mut value = map([("seq", seq([1, 2]))]);
+        value.merge_with(map([("seq", seq([3]))]), &strategy);
+        assert_eq!(value, map([("seq", seq([1, 2, 3]))]));
+
+        let mut value = Value::Seq(SeqValue::default());
+        value.merge_with(seq([1]), &strategy);
+        assert_eq!(value, seq([1]));
+    }
+
+    #[test]
+    fn null_deletes_key() {
+        let original = map([
+            ("a", Value::from(1_u8)),
+            (
+                "nested",
+                map([("b", Value::from(2_u8)), ("c", Value::from(3_u8))]),
+            ),
+        ]);
+        let other = map([
+            ("a", Value::Null(NullValue)),
+            ("nested", map([("c", Value::Null(NullValue))])),
+            ("d", Value::Null(NullValue)),
+        ]);
+
+        // By default null values get merged like any other value:
+        let mut value = original.clone();
+        value.merge(other.clone());
+        assert_eq!(
+            value,
+            map([
+                ("a", Value::Null(NullValue)),
+                (
+                    "nested",
+                    map([("b", Value::from(2_u8)), ("c", Value::Null(NullValue))])
+                ),
+                ("d", Value::Null(NullValue)),
+            ])
+        );
+
+        let strategy = MergeStrategy::default().with_null_deletes_keys(true);
+        let mut value = original;
+        value.merge_with(other, &strategy);
+        assert_eq!(value, map([("nested", map([("b", Value::from(2_u8))]))]));
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 #[cfg(any(test, feature = "testing"))]
 use proptest::{prelude::*, sample::SizeRange};
 #[cfg(any(test, feature = "testing"))]
@@ -6,6 +8,10 @@ use proptest_derive::Arbitrary;
 use super::Value;
 
 /// A sequence.
+///
+/// Empty sequences do not allocate. Sequences are backed by a `Vec` rather than
+/// a small-size-optimized vector (e.g. `SmallVec<[Value; N]>`), as storing values inline
+/// would make `Value` (which contains `Seq`) a recursive type of infinite size.
 pub type Seq = Vec<Value>;
 
 #[cfg(any(test, feature = "testing"))]
@@ -68,8 +74,26 @@ impl From<SeqValue> for Seq {
     }
 }
 
-impl std::fmt::Debug for SeqValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl FromIterator<Value> for SeqValue {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Value> for SeqValue {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        self.0.extend(iter)
+    }
+}
+
+impl core::fmt::Debug for SeqValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.0.iter()).finish()
     }
 }
@@ -104,11 +128,24 @@ mod tests {
         decoder::Decoder,
         encoder::Encoder,
         io::{SliceReader, VecWriter},
-        value::{NullValue, Value},
+        value::{IntValue, NullValue, Value},
     };
 
     use super::*;
 
+    #[test]
+    fn empty_does_not_allocate() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_seq(&[]).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        let decoded = decoder.decode_seq_value().unwrap();
+        assert_eq!(decoded.into_vec().capacity(), 0);
+    }
+
     #[test]
     fn debug() {
         assert_eq!(
@@ -118,10 +155,32 @@ mod tests {
 
         assert_eq!(
             format!("{:#?}", SeqValue::from(vec![Value::Null(NullValue)])),
-            "[\n    Null(\n        null,\n    ),\n]"
+            "[\n    null,\n]"
         );
     }
 
+    #[test]
+    fn from_iter_and_extend() {
+        let values: Vec<Value> = (1..=3_u8)
+            .map(|int| Value::from(IntValue::from(int)))
+            .collect();
+
+        let mut value: SeqValue = values[..2].iter().cloned().collect();
+        assert_eq!(value.as_slice(), &values[..2]);
+
+        value.extend(values[2..].iter().cloned());
+        assert_eq!(value.as_slice(), &values[..]);
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_seq_value(&value).unwrap();
+
+        let reader = SliceReader::new(&encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        assert_eq!(decoder.decode_seq_value().unwrap(), value);
+    }
+
     proptest! {
         #[test]
         fn encode_decode_roundtrip(value in SeqValue::arbitrary(), config in EncoderConfig::arbitrary()) {
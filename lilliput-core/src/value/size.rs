@@ -0,0 +1,111 @@
+use crate::{config::EncoderConfig, encoder::Encoder, io::CountingWriter};
+
+use super::Value;
+
+impl Value {
+    /// Returns the total number of nodes in the value,
+    /// counting every scalar and container (including `self`).
+    ///
+    /// Map entries contribute both their key and their value.
+    pub fn node_count(&self) -> usize {
+        match self {
+            Self::Seq(value) => {
+                1 + value
+                    .as_slice()
+                    .iter()
+                    .map(Value::node_count)
+                    .sum::<usize>()
+            }
+            Self::Map(value) => {
+                1 + value
+                    .as_map_ref()
+                    .iter()
+                    .map(|(key, value)| key.node_count() + value.node_count())
+                    .sum::<usize>()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Returns the number of bytes the value would get encoded into, for a given `config`.
+    ///
+    /// The value gets encoded into a `CountingWriter`, without allocating an output buffer.
+    pub fn encoded_size(&self, config: &EncoderConfig) -> usize {
+        let mut encoder = Encoder::new(CountingWriter::new(), config.clone());
+        encoder
+            .encode_value(self)
+            .expect("writing to a counting writer should not fail");
+
+        encoder.into_writer().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::io::VecWriter;
+
+    use super::*;
+
+    fn encoded_len(value: &Value, config: &EncoderConfig) -> usize {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::new(writer, config.clone());
+        encoder.encode_value(value).unwrap();
+        encoded.len()
+    }
+
+    #[test]
+    fn node_count() {
+        assert_eq!(Value::from(42_u8).node_count(), 1);
+
+        let value: Value = [
+            (
+                Value::from("seq"),
+                [1_u8, 2, 3].into_iter().map(Value::from).collect(),
+            ),
+            (
+                Value::from("map"),
+                [(Value::from("inner"), Value::from(true))]
+                    .into_iter()
+                    .collect(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        // root + ("seq" + [1, 2, 3]) + ("map" + {"inner": true})
+        assert_eq!(value.node_count(), 1 + (1 + 4) + (1 + 3));
+    }
+
+    #[test]
+    fn encoded_size_nested() {
+        let value: Value = [
+            (Value::from("name"), Value::from("lilliput")),
+            (
+                Value::from("values"),
+                [
+                    Value::from(1_u8),
+                    Value::from(-1000_i32),
+                    Value::from(4.2_f64),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let config = EncoderConfig::default();
+        assert_eq!(value.encoded_size(&config), encoded_len(&value, &config));
+    }
+
+    proptest! {
+        #[test]
+        fn encoded_size(value in Value::arbitrary(), config in EncoderConfig::arbitrary()) {
+            prop_assert_eq!(value.encoded_size(&config), encoded_len(&value, &config));
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 #[cfg(any(test, feature = "testing"))]
 use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
@@ -48,8 +50,8 @@ impl From<StringValue> for String {
     }
 }
 
-impl std::fmt::Debug for StringValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for StringValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             write!(f, "{:#?}", self.0)
         } else {
@@ -58,8 +60,8 @@ impl std::fmt::Debug for StringValue {
     }
 }
 
-impl std::fmt::Display for StringValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for StringValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
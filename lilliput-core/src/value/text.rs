@@ -0,0 +1,196 @@
+use alloc::string::String;
+use core::fmt::Write as _;
+
+use crate::binary::BytesSlice;
+
+use super::{FloatValue, Value};
+
+const INDENT: &str = "  ";
+
+impl Value {
+    /// Returns a human-readable, JSON-like text representation of the value.
+    ///
+    /// Sequences and maps get rendered across multiple lines, indented by nesting depth,
+    /// with map entries in the order of their storage. Byte arrays get rendered as hex.
+    ///
+    /// The representation is meant for debugging, it is not a parseable format.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        self.write_text(&mut text, 0)
+            .expect("writing to a string should not fail");
+
+        text
+    }
+
+    fn write_text(&self, text: &mut String, depth: usize) -> core::fmt::Result {
+        match self {
+            Self::Int(value) => write!(text, "{value}"),
+            Self::String(value) => write!(text, "{:?}", value.as_str()),
+            Self::Seq(value) => {
+                if value.is_empty() {
+                    return text.write_str("[]");
+                }
+
+                text.write_str("[\n")?;
+
+                for (index, element) in value.as_slice().iter().enumerate() {
+                    if index > 0 {
+                        text.write_str(",\n")?;
+                    }
+
+                    write_indent(text, depth + 1)?;
+                    element.write_text(text, depth + 1)?;
+                }
+
+                text.write_str("\n")?;
+                write_indent(text, depth)?;
+                text.write_str("]")
+            }
+            Self::Map(value) => {
+                if value.is_empty() {
+                    return text.write_str("{}");
+                }
+
+                text.write_str("{\n")?;
+
+                for (index, (key, value)) in value.as_map_ref().iter().enumerate() {
+                    if index > 0 {
+                        text.write_str(",\n")?;
+                    }
+
+                    write_indent(text, depth + 1)?;
+                    key.write_text(text, depth + 1)?;
+                    text.write_str(": ")?;
+                    value.write_text(text, depth + 1)?;
+                }
+
+                text.write_str("\n")?;
+                write_indent(text, depth)?;
+                text.write_str("}")
+            }
+            Self::Float(value) => match value {
+                FloatValue::F32(value) => write!(text, "{value:?}"),
+                FloatValue::F64(value) => write!(text, "{value:?}"),
+            },
+            Self::Bytes(value) => write!(text, "b{}", BytesSlice(value.as_slice())),
+            Self::Bool(value) => write!(text, "{value}"),
+            Self::Unit(_) => text.write_str("()"),
+            Self::Null(_) => text.write_str("null"),
+            Self::Ext(value) => write!(
+                text,
+                "ext({}, b{})",
+                value.tag(),
+                BytesSlice(value.as_slice())
+            ),
+        }
+    }
+}
+
+fn write_indent(text: &mut String, depth: usize) -> core::fmt::Result {
+    for _ in 0..depth {
+        text.write_str(INDENT)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::value::{
+        BoolValue, BytesValue, ExtValue, IntValue, Map, MapValue, NullValue, SeqValue, StringValue,
+        UnitValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn int() {
+        assert_eq!(Value::from(IntValue::from(42_u8)).to_text(), "42");
+        assert_eq!(Value::from(IntValue::from(-42_i64)).to_text(), "-42");
+    }
+
+    #[test]
+    fn string() {
+        let value = Value::from(StringValue::from("foo \"bar\"\n".to_owned()));
+        assert_eq!(value.to_text(), r#""foo \"bar\"\n""#);
+    }
+
+    #[test]
+    fn seq() {
+        assert_eq!(Value::from(SeqValue::default()).to_text(), "[]");
+
+        let value = Value::from(SeqValue::from(vec![
+            Value::from(IntValue::from(1_u8)),
+            Value::from(SeqValue::from(vec![Value::from(BoolValue::from(true))])),
+            Value::from(SeqValue::default()),
+        ]));
+        assert_eq!(value.to_text(), "[\n  1,\n  [\n    true\n  ],\n  []\n]");
+    }
+
+    #[test]
+    fn map() {
+        assert_eq!(Value::from(MapValue::default()).to_text(), "{}");
+
+        let mut inner = Map::default();
+        inner.insert(
+            Value::from(StringValue::from("inner".to_owned())),
+            Value::from(NullValue),
+        );
+
+        let mut map = Map::default();
+        map.insert(
+            Value::from(StringValue::from("a".to_owned())),
+            Value::from(IntValue::from(1_u8)),
+        );
+        map.insert(
+            Value::from(StringValue::from("b".to_owned())),
+            Value::from(MapValue::from(inner)),
+        );
+
+        let value = Value::from(MapValue::from(map));
+        assert_eq!(
+            value.to_text(),
+            "{\n  \"a\": 1,\n  \"b\": {\n    \"inner\": null\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn float() {
+        assert_eq!(Value::from(FloatValue::from(4.2_f32)).to_text(), "4.2");
+        assert_eq!(Value::from(FloatValue::from(1.0_f64)).to_text(), "1.0");
+        assert_eq!(Value::from(FloatValue::from(f64::NAN)).to_text(), "NaN");
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(Value::from(BytesValue::default()).to_text(), "b[]");
+
+        let value = Value::from(BytesValue::from(vec![0x01, 0xab, 0xff]));
+        assert_eq!(value.to_text(), "b[01, ab, ff]");
+    }
+
+    #[test]
+    fn bool() {
+        assert_eq!(Value::from(BoolValue::from(true)).to_text(), "true");
+        assert_eq!(Value::from(BoolValue::from(false)).to_text(), "false");
+    }
+
+    #[test]
+    fn unit() {
+        assert_eq!(Value::from(UnitValue).to_text(), "()");
+    }
+
+    #[test]
+    fn null() {
+        assert_eq!(Value::from(NullValue).to_text(), "null");
+    }
+
+    #[test]
+    fn ext() {
+        let value = Value::from(ExtValue::new(42, vec![0x01, 0xab]));
+        assert_eq!(value.to_text(), "ext(42, b[01, ab])");
+    }
+}
@@ -0,0 +1,125 @@
+use super::Value;
+
+impl Value {
+    /// Returns `true` if the value is a scalar, i.e. not nesting any other values,
+    /// otherwise `false`.
+    ///
+    /// Scalars are integers, floats, booleans, strings, byte arrays,
+    /// units, nulls, as well as extension values (whose payload is opaque).
+    pub fn is_scalar(&self) -> bool {
+        !self.is_container()
+    }
+
+    /// Returns `true` if the value is a container (i.e. a sequence or map),
+    /// otherwise `false`.
+    pub fn is_container(&self) -> bool {
+        matches!(self, Self::Seq(_) | Self::Map(_))
+    }
+
+    /// Returns an iterator over the values directly nested within the value.
+    ///
+    /// - Sequences yield their elements, in order.
+    /// - Maps yield both keys and values, alternating between an entry's key and its value.
+    /// - Scalars yield nothing.
+    pub fn children(&self) -> impl Iterator<Item = &Value> + '_ {
+        let (seq, map) = match self {
+            Self::Seq(value) => (Some(value.as_slice().iter()), None),
+            Self::Map(value) => (
+                None,
+                Some(
+                    value
+                        .as_map_ref()
+                        .iter()
+                        .flat_map(|(key, value)| [key, value]),
+                ),
+            ),
+            _ => (None, None),
+        };
+
+        seq.into_iter().flatten().chain(map.into_iter().flatten())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::value::{
+        BoolValue, BytesValue, ExtValue, FloatValue, IntValue, MapValue, NullValue, SeqValue,
+        StringValue, UnitValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn classification() {
+        let scalars = [
+            Value::Int(IntValue::from(42_u8)),
+            Value::String(StringValue::from("lilliput".to_owned())),
+            Value::Float(FloatValue::from(1.5_f64)),
+            Value::Bytes(BytesValue::from(vec![1_u8, 2, 3])),
+            Value::Bool(BoolValue::from(true)),
+            Value::Unit(UnitValue),
+            Value::Null(NullValue),
+            Value::Ext(ExtValue::new(7, vec![1, 2, 3])),
+        ];
+
+        for value in &scalars {
+            assert!(value.is_scalar(), "{value:?}");
+            assert!(!value.is_container(), "{value:?}");
+            assert_eq!(value.children().count(), 0, "{value:?}");
+        }
+
+        let containers = [
+            Value::Seq(SeqValue::default()),
+            Value::Map(MapValue::default()),
+        ];
+
+        for value in &containers {
+            assert!(!value.is_scalar(), "{value:?}");
+            assert!(value.is_container(), "{value:?}");
+            assert_eq!(value.children().count(), 0, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn children() {
+        let seq: Value = [Value::from(1_u8), Value::from("a"), Value::from(true)]
+            .into_iter()
+            .collect();
+        let children: Vec<&Value> = seq.children().collect();
+        assert_eq!(
+            children,
+            [&Value::from(1_u8), &Value::from("a"), &Value::from(true)]
+        );
+
+        let map: Value = [
+            (Value::from("a"), Value::from(1_u8)),
+            (Value::from("b"), seq.clone()),
+        ]
+        .into_iter()
+        .collect();
+        let children: Vec<&Value> = map.children().collect();
+        assert_eq!(
+            children,
+            [
+                &Value::from("a"),
+                &Value::from(1_u8),
+                &Value::from("b"),
+                &seq
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn children_recursion_matches_node_count(value in Value::arbitrary()) {
+            fn count(value: &Value) -> usize {
+                1 + value.children().map(count).sum::<usize>()
+            }
+
+            prop_assert_eq!(count(&value), value.node_count());
+        }
+    }
+}
@@ -14,14 +14,14 @@ impl From<()> for UnitValue {
     }
 }
 
-impl std::fmt::Debug for UnitValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for UnitValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "unit")
     }
 }
 
-impl std::fmt::Display for UnitValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for UnitValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "unit")
     }
 }
@@ -47,7 +47,7 @@ impl<'de> serde::Deserialize<'de> for UnitValue {
         impl serde::de::Visitor<'_> for UnitValueVisitor {
             type Value = UnitValue;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 formatter.write_str("unit value")
             }
 
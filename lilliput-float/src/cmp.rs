@@ -71,6 +71,42 @@ macro_rules! impl_float_partial_eq_and_ord {
     };
 }
 
+macro_rules! impl_float_total_cmp {
+    ($t:ty => unsigned: $unsigned:ty, signed: $signed:ty) => {
+        impl $t {
+            /// Returns the ordering between `self` and `other`,
+            /// as defined by the `totalOrder` predicate of IEEE 754 (2008 revision).
+            ///
+            /// Unlike the standard partial comparison this orders `-0.0` before `+0.0`,
+            /// and NaNs consistently, based on their sign and payload.
+            pub fn total_cmp(&self, other: &Self) -> Ordering {
+                // Shift out any padding bits, moving the sign bit into the MSB:
+                let padding_bits = <$unsigned>::BITS - <$t>::BITS;
+
+                let mut lhs = (self.to_bits() << padding_bits) as $signed;
+                let mut rhs = (other.to_bits() << padding_bits) as $signed;
+
+                // Same as `f32::total_cmp`/`f64::total_cmp`: flip all bits except
+                // for the sign bit for negative values, which allows for
+                // comparing the bits as two's complement signed integers.
+                lhs ^= (((lhs >> (<$unsigned>::BITS - 1)) as $unsigned) >> 1) as $signed;
+                rhs ^= (((rhs >> (<$unsigned>::BITS - 1)) as $unsigned) >> 1) as $signed;
+
+                lhs.cmp(&rhs)
+            }
+        }
+    };
+}
+
+impl_float_total_cmp!(F8 => unsigned: u8, signed: i8);
+impl_float_total_cmp!(F16 => unsigned: u16, signed: i16);
+impl_float_total_cmp!(F24 => unsigned: u32, signed: i32);
+impl_float_total_cmp!(F32 => unsigned: u32, signed: i32);
+impl_float_total_cmp!(F40 => unsigned: u64, signed: i64);
+impl_float_total_cmp!(F48 => unsigned: u64, signed: i64);
+impl_float_total_cmp!(F56 => unsigned: u64, signed: i64);
+impl_float_total_cmp!(F64 => unsigned: u64, signed: i64);
+
 impl_float_partial_eq_and_ord!(F8 => unsigned: u8, signed: i8);
 impl_float_partial_eq_and_ord!(F16 => unsigned: u16, signed: i16);
 impl_float_partial_eq_and_ord!(F24 => unsigned: u32, signed: i32);
@@ -86,7 +122,65 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn total_cmp_order() {
+        let values = [
+            -f64::NAN,
+            f64::NEG_INFINITY,
+            -1.0,
+            -0.0,
+            0.0,
+            1.0,
+            f64::INFINITY,
+            f64::from_bits(0x7ff0_0000_0000_0001), // signaling NaN
+            f64::NAN,                              // quiet NaN
+        ];
+
+        for (lhs_index, lhs) in values.iter().enumerate() {
+            for (rhs_index, rhs) in values.iter().enumerate() {
+                let expected = lhs_index.cmp(&rhs_index);
+
+                let actual = F64::from(*lhs).total_cmp(&F64::from(*rhs));
+                assert_eq!(actual, expected, "{lhs:?} vs. {rhs:?}");
+
+                let actual = F32::from(*lhs as f32).total_cmp(&F32::from(*rhs as f32));
+                if lhs.is_nan() || rhs.is_nan() {
+                    // Converting NaNs to `f32` might not preserve their kind.
+                    continue;
+                }
+                assert_eq!(actual, expected, "{lhs:?} vs. {rhs:?}");
+            }
+        }
+    }
+
     proptest! {
+        #[test]
+        fn f32_total_cmp_matches_native_behavior(native_lhs in f32::arbitrary(), native_rhs in f32::arbitrary()) {
+            let (lhs, rhs) = (F32::from(native_lhs), F32::from(native_rhs));
+            let actual = lhs.total_cmp(&rhs);
+            let expected = native_lhs.total_cmp(&native_rhs);
+            prop_assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn f64_total_cmp_matches_native_behavior(native_lhs in f64::arbitrary(), native_rhs in f64::arbitrary()) {
+            let (lhs, rhs) = (F64::from(native_lhs), F64::from(native_rhs));
+            let actual = lhs.total_cmp(&rhs);
+            let expected = native_lhs.total_cmp(&native_rhs);
+            prop_assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn f24_total_cmp_matches_f32(lhs_bits in 0_u32..(1 << 24), rhs_bits in 0_u32..(1 << 24)) {
+            use crate::FpExtend as _;
+
+            let (lhs, rhs) = (F24(lhs_bits), F24(rhs_bits));
+            let (lhs_f32, rhs_f32): (F32, F32) = (lhs.extend(), rhs.extend());
+            let actual = lhs.total_cmp(&rhs);
+            let expected = lhs_f32.total_cmp(&rhs_f32);
+            prop_assert_eq!(actual, expected);
+        }
+
         #[test]
         fn f32_matches_native_behavior(native_lhs in f32::arbitrary(), native_rhs in f32::arbitrary()) {
             let (lhs, rhs) = (F32::from(native_lhs), F32::from(native_rhs));
@@ -0,0 +1,107 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::{Deserialize, Serialize};
+
+use lilliput_core::{decoder::Decoder, io::SliceReader};
+use lilliput_serde::{
+    de::{from_slice, from_value},
+    ser::to_vec,
+};
+
+const CRITERION_SIGNIFICANCE_LEVEL: f64 = 0.1;
+const CRITERION_SAMPLE_SIZE: usize = 100;
+
+const SAMPLES: usize = 4_096;
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    score: f64,
+    active: bool,
+    tags: Vec<String>,
+    position: Position,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+fn samples() -> Vec<Record> {
+    (0..SAMPLES)
+        .map(|index| Record {
+            id: index as u64,
+            name: format!("record #{index}"),
+            score: index as f64 / 3.0,
+            active: index % 2 == 0,
+            tags: (0..(index % 4)).map(|tag| format!("tag-{tag}")).collect(),
+            position: Position {
+                x: index as i32,
+                y: -(index as i32),
+            },
+        })
+        .collect()
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    let encoded = to_vec(&samples()).unwrap();
+
+    let mut g = c.benchmark_group("deserialize");
+
+    g.significance_level(CRITERION_SIGNIFICANCE_LEVEL);
+    g.sample_size(CRITERION_SAMPLE_SIZE);
+
+    // Deserializing straight into the target type:
+    g.bench_function("direct", |b| {
+        b.iter_custom(|iters| {
+            let mut duration = Duration::ZERO;
+
+            for _ in 0..iters {
+                let start = Instant::now();
+
+                let records: Vec<Record> = black_box(from_slice(&encoded).unwrap());
+
+                duration += start.elapsed();
+
+                drop(records);
+            }
+
+            duration
+        });
+    });
+
+    // Materializing an intermediate `Value`, then deserializing from it:
+    g.bench_function("decode_value + from_value", |b| {
+        b.iter_custom(|iters| {
+            let mut duration = Duration::ZERO;
+
+            for _ in 0..iters {
+                let reader = SliceReader::new(&encoded);
+                let mut decoder = Decoder::from_reader(reader);
+
+                let start = Instant::now();
+
+                let value = decoder.decode_value().unwrap();
+                let records: Vec<Record> = black_box(from_value(value).unwrap());
+
+                duration += start.elapsed();
+
+                drop(records);
+            }
+
+            duration
+        });
+    });
+
+    g.finish();
+}
+
+criterion_group!(deserialize, bench_deserialize);
+
+criterion_main!(deserialize);
@@ -5,10 +5,13 @@ use lilliput_core::config::EncoderConfig;
 /// The representation to serialize structs to.
 #[derive(Default, Clone, Eq, PartialEq, Debug)]
 pub enum StructRepr {
-    /// Serialize as sequence of fields.
-    #[default]
+    /// Serialize as sequence of field values, in declaration order.
+    ///
+    /// This is more compact than `Map`, but it is not self-describing by field name:
+    /// decoding breaks if fields get added, removed or reordered in between.
     Seq,
-    /// Serialize as map of fields.
+    /// Serialize as map of field names to field values.
+    #[default]
     Map,
 }
 
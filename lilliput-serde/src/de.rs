@@ -1,4 +1,8 @@
 //! Deserializers for deserializing lilliput-encoded values.
+//!
+//! Deserialization is streaming: the `Deserializer` pulls headers and bodies
+//! straight from its `Decoder`, driving the target type's `Visitor` as it goes,
+//! without materializing intermediate `Value`s.
 
 use serde::{
     de::{self, Error as _, IntoDeserializer as _},
@@ -6,6 +10,7 @@ use serde::{
 };
 
 use lilliput_core::{
+    config::DecoderConfig,
     decoder::Decoder,
     io::{Read, Reference, SliceReader, StdIoReader},
     marker::Marker,
@@ -14,6 +19,10 @@ use lilliput_core::{
 
 use crate::error::{Error, Result};
 
+mod value;
+
+pub use self::value::from_value;
+
 /// A deserializer for deserializing lilliput values.
 pub struct Deserializer<R> {
     decoder: Decoder<R>,
@@ -26,8 +35,13 @@ pub struct Deserializer<R> {
 impl<R> Deserializer<R> {
     /// Creates a deserializer from a `reader`.
     pub fn from_reader(reader: R) -> Self {
+        Self::new(reader, DecoderConfig::default())
+    }
+
+    /// Creates a deserializer from a `reader`, configured by `config`.
+    pub fn new(reader: R, config: DecoderConfig) -> Self {
         Deserializer {
-            decoder: Decoder::from_reader(reader),
+            decoder: Decoder::new(reader, config),
             scratch: Vec::new(),
             remaining_depth: 128,
             #[cfg(feature = "unbounded_depth")]
@@ -56,23 +70,70 @@ impl<R> Deserializer<R> {
 }
 
 /// Deserializes an instance of `T` from `bytes`.
+///
+/// This deserializes directly into `T`, without decoding into a `Value` first.
 pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T>
+where
+    T: 'de + Deserialize<'de>,
+{
+    from_slice_with_config(bytes, DecoderConfig::default())
+}
+
+/// Deserializes an instance of `T` from `bytes`, configured by `config`.
+pub fn from_slice_with_config<'de, T>(bytes: &'de [u8], config: DecoderConfig) -> Result<T>
+where
+    T: 'de + Deserialize<'de>,
+{
+    let reader = SliceReader::new(bytes);
+    T::deserialize(&mut Deserializer::new(reader, config))
+}
+
+/// Deserializes an instance of `T` from `bytes`, requiring it to consume all of `bytes`.
+///
+/// Fails with `ErrorCode::TrailingData` if any bytes are left over after `T`,
+/// pointing at the first of them.
+pub fn from_slice_exact<'de, T>(bytes: &'de [u8]) -> Result<T>
+where
+    T: 'de + Deserialize<'de>,
+{
+    from_slice_exact_with_config(bytes, DecoderConfig::default())
+}
+
+/// Deserializes an instance of `T` from `bytes`, requiring it to consume all of `bytes`,
+/// configured by `config`.
+pub fn from_slice_exact_with_config<'de, T>(bytes: &'de [u8], config: DecoderConfig) -> Result<T>
 where
     T: 'de + Deserialize<'de>,
 {
     let reader = SliceReader::new(bytes);
-    T::deserialize(&mut Deserializer::from_reader(reader))
+    let mut deserializer = Deserializer::new(reader, config);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    Ok(value)
 }
 
 /// Deserializes an instance of `T` from `reader`.
+///
+/// This deserializes directly into `T`, without decoding into a `Value` first.
 #[cfg(feature = "std")]
 pub fn from_reader<R, T>(reader: R) -> Result<T>
+where
+    R: std::io::Read,
+    T: de::DeserializeOwned,
+{
+    from_reader_with_config(reader, DecoderConfig::default())
+}
+
+/// Deserializes an instance of `T` from `reader`, configured by `config`.
+#[cfg(feature = "std")]
+pub fn from_reader_with_config<R, T>(reader: R, config: DecoderConfig) -> Result<T>
 where
     R: std::io::Read,
     T: de::DeserializeOwned,
 {
     let reader = StdIoReader::new(reader);
-    T::deserialize(&mut Deserializer::from_reader(reader))
+    T::deserialize(&mut Deserializer::new(reader, config))
 }
 
 #[cfg(not(feature = "unbounded_depth"))]
@@ -133,6 +194,7 @@ where
             Marker::Bool => self.deserialize_bool(visitor),
             Marker::Unit => self.deserialize_unit(visitor),
             Marker::Null => self.deserialize_option(visitor),
+            Marker::Ext => self.deserialize_ext(visitor),
         }
     }
 
@@ -339,6 +401,11 @@ where
     where
         V: de::Visitor<'de>,
     {
+        // Extension values can be deserialized as `(tag, bytes)` tuples:
+        if self.decoder.peek_marker()? == Marker::Ext {
+            return self.deserialize_ext(visitor);
+        }
+
         let header = self.decoder.decode_seq_header()?;
 
         check_depth! {
@@ -389,13 +456,27 @@ where
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        self.deserialize_map(visitor)
+        // Structs can be serialized as either maps, or sequences (see `StructRepr`):
+        match self.decoder.peek_marker()? {
+            Marker::Seq => self.deserialize_seq(visitor),
+            _ => {
+                let header = self.decoder.decode_map_header()?;
+
+                check_depth! {
+                    this: self;
+                    let access = MapAccess::with_fields(self, header.len(), fields);
+                    let value = visitor.visit_map(access)?;
+                }
+
+                Ok(value)
+            }
+        }
     }
 
     #[inline]
@@ -410,14 +491,17 @@ where
     {
         match self.decoder.peek_marker()? {
             Marker::Int => {
-                let index = self.decoder.decode_u32()? as usize;
-                visitor.visit_enum(variants[index].into_deserializer())
-            }
-            Marker::String => {
-                let mut scratch = vec![];
-                let str_ref = self.decoder.decode_str(&mut scratch)?;
-                visitor.visit_enum(str_ref.into_deserializer())
+                // Let the visitor resolve the index, so that out-of-range
+                // indices surface as errors rather than panics:
+                let index = self.decoder.decode_u32()?;
+                visitor.visit_enum(index.into_deserializer())
             }
+            Marker::String => match self.decoder.decode_str(&mut self.scratch)? {
+                Reference::Borrowed(str) => {
+                    visitor.visit_enum(de::value::BorrowedStrDeserializer::new(str))
+                }
+                Reference::Copied(str) => visitor.visit_enum(str.into_deserializer()),
+            },
             Marker::Map => {
                 let header = self.decoder.decode_map_header()?;
 
@@ -465,6 +549,14 @@ impl<'de, R> Deserializer<R>
 where
     R: Read<'de>,
 {
+    /// Ensures the deserializer's input has been fully consumed.
+    ///
+    /// Fails with `ErrorCode::TrailingData` otherwise,
+    /// pointing at the first leftover byte.
+    pub fn end(&mut self) -> Result<()> {
+        self.decoder.finish()
+    }
+
     #[inline]
     fn pos(&self) -> usize {
         self.decoder.pos()
@@ -501,6 +593,51 @@ where
             },
         }
     }
+
+    #[inline]
+    fn deserialize_ext<V>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let (tag, data) = self.decoder.decode_ext_value()?.into_parts();
+
+        visitor.visit_seq(ExtAccess {
+            tag: Some(tag),
+            data: Some(data),
+        })
+    }
+}
+
+/// Provides an extension value's tag and payload as a `(tag, bytes)` tuple.
+struct ExtAccess {
+    tag: Option<u64>,
+    data: Option<Vec<u8>>,
+}
+
+impl<'de> de::SeqAccess<'de> for ExtAccess {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if let Some(tag) = self.tag.take() {
+            return seed.deserialize(tag.into_deserializer()).map(Some);
+        }
+
+        if let Some(data) = self.data.take() {
+            let deserializer = de::value::BytesDeserializer::new(&data);
+            return seed.deserialize(deserializer).map(Some);
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::from(self.tag.is_some()) + usize::from(self.data.is_some()))
+    }
 }
 
 struct SeqAccess<'a, R> {
@@ -542,6 +679,7 @@ where
 struct MapAccess<'a, R: 'a> {
     de: &'a mut Deserializer<R>,
     remaining: usize,
+    fields: Option<&'static [&'static str]>,
 }
 
 impl<'a, R: 'a> MapAccess<'a, R> {
@@ -550,10 +688,59 @@ impl<'a, R: 'a> MapAccess<'a, R> {
         MapAccess {
             de,
             remaining: count,
+            fields: None,
+        }
+    }
+
+    #[inline]
+    fn with_fields(
+        de: &'a mut Deserializer<R>,
+        count: usize,
+        fields: &'static [&'static str],
+    ) -> Self {
+        MapAccess {
+            de,
+            remaining: count,
+            fields: Some(fields),
         }
     }
 }
 
+impl<'de, 'a, R> MapAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    fn next_field_key_seed<K>(
+        &mut self,
+        seed: K,
+        fields: &'static [&'static str],
+    ) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        // Unknown fields are left to serde (e.g. skipped via `IgnoredAny`,
+        // or rejected by `#[serde(deny_unknown_fields)]`), unless denied by the config:
+        if !self.de.decoder.config().deny_unknown_fields
+            || self.de.decoder.peek_marker()? != Marker::String
+        {
+            return seed.deserialize(&mut *self.de).map(Some);
+        }
+
+        let key = self.de.decoder.decode_str(&mut self.de.scratch)?;
+
+        let Some(field) = fields.iter().find(|&&field| field == &*key).copied() else {
+            return Err(de::Error::unknown_field(&key, fields));
+        };
+
+        let deserializer = de::value::BorrowedStrDeserializer::new(field);
+        seed.deserialize(deserializer).map(Some)
+    }
+}
+
 impl<'de, 'a, R> de::MapAccess<'de> for MapAccess<'a, R>
 where
     R: Read<'de> + 'a,
@@ -565,10 +752,16 @@ where
     where
         K: de::DeserializeSeed<'de>,
     {
+        if let Some(fields) = self.fields {
+            return self.next_field_key_seed(seed, fields);
+        }
+
         if self.remaining == 0 {
             return Ok(None);
         }
 
+        // Keys go through the same path as values, so string keys get passed to
+        // `visit_borrowed_str` (rather than getting allocated) if the reader allows it:
         seed.deserialize(&mut *self.de).map(Some)
     }
 
@@ -669,10 +862,10 @@ where
     }
 
     #[inline]
-    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        self.de.deserialize_map(visitor)
+        self.de.deserialize_struct("", fields, visitor)
     }
 }
@@ -0,0 +1,299 @@
+use serde::{
+    de::{self, IntoDeserializer as _, Unexpected},
+    forward_to_deserialize_any, Deserialize,
+};
+
+use lilliput_core::value::{FloatValue, IntValue, Map, SignedIntValue, UnsignedIntValue, Value};
+
+use crate::error::{Error, Result};
+
+/// Deserializes an instance of `T` from `value`.
+///
+/// This deserializes from the value directly, without encoding it into bytes first.
+pub fn from_value<T>(value: Value) -> Result<T>
+where
+    T: de::DeserializeOwned,
+{
+    T::deserialize(ValueDeserializer { value })
+}
+
+/// A deserializer for deserializing from `Value`s.
+struct ValueDeserializer {
+    value: Value,
+}
+
+impl ValueDeserializer {
+    fn new(value: Value) -> Self {
+        Self { value }
+    }
+}
+
+fn unexpected(value: &Value) -> Unexpected<'_> {
+    match value {
+        Value::Int(value) => match (value.as_u64(), value.as_i64()) {
+            (Some(value), _) => Unexpected::Unsigned(value),
+            (None, Some(value)) => Unexpected::Signed(value),
+            (None, None) => Unexpected::Other("integer"),
+        },
+        Value::String(value) => Unexpected::Str(value.as_str()),
+        Value::Seq(_) => Unexpected::Seq,
+        Value::Map(_) => Unexpected::Map,
+        Value::Float(value) => Unexpected::Float(value.as_f64()),
+        Value::Bytes(value) => Unexpected::Bytes(value.as_slice()),
+        Value::Bool(value) => Unexpected::Bool((*value).into()),
+        Value::Unit(_) => Unexpected::Unit,
+        Value::Null(_) => Unexpected::Option,
+        Value::Ext(_) => Unexpected::Other("extension value"),
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Value::Int(IntValue::Signed(value)) => match value {
+                SignedIntValue::I8(value) => visitor.visit_i8(value),
+                SignedIntValue::I16(value) => visitor.visit_i16(value),
+                SignedIntValue::I32(value) => visitor.visit_i32(value),
+                SignedIntValue::I64(value) => visitor.visit_i64(value),
+            },
+            Value::Int(IntValue::Unsigned(value)) => match value {
+                UnsignedIntValue::U8(value) => visitor.visit_u8(value),
+                UnsignedIntValue::U16(value) => visitor.visit_u16(value),
+                UnsignedIntValue::U32(value) => visitor.visit_u32(value),
+                UnsignedIntValue::U64(value) => visitor.visit_u64(value),
+            },
+            Value::String(value) => visitor.visit_string(value.into_string()),
+            Value::Seq(value) => visitor.visit_seq(SeqAccess {
+                iter: value.into_vec().into_iter(),
+            }),
+            Value::Map(value) => visitor.visit_map(MapAccess {
+                iter: value.into_map().into_iter(),
+                value: None,
+            }),
+            Value::Float(FloatValue::F32(value)) => visitor.visit_f32(value),
+            Value::Float(FloatValue::F64(value)) => visitor.visit_f64(value),
+            Value::Bytes(value) => visitor.visit_byte_buf(value.into_vec()),
+            Value::Bool(value) => visitor.visit_bool(value.into()),
+            Value::Unit(_) => visitor.visit_unit(),
+            Value::Null(_) => visitor.visit_none(),
+            Value::Ext(value) => {
+                // Extension values can be deserialized as `(tag, bytes)` tuples:
+                let (tag, data) = value.into_parts();
+                visitor.visit_seq(de::value::SeqDeserializer::new(
+                    [Value::Int(IntValue::from(tag)), Value::Bytes(data.into())]
+                        .into_iter()
+                        .map(ValueDeserializer::new),
+                ))
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Value::Null(_) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Value::Int(_) | Value::String(_) => visitor.visit_enum(EnumAccess {
+                variant: self.value,
+                value: None,
+            }),
+            Value::Map(value) => {
+                let mut iter = value.into_map().into_iter();
+
+                let (Some((variant, value)), None) = (iter.next(), iter.next()) else {
+                    return Err(de::Error::custom("expected map of length 1"));
+                };
+
+                visitor.visit_enum(EnumAccess {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            other => Err(de::Error::invalid_type(
+                unexpected(&other),
+                &"int, string or map",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        drop(self);
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+impl<'de> de::IntoDeserializer<'de, Error> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+struct SeqAccess {
+    iter: <Vec<Value> as IntoIterator>::IntoIter,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        self.iter
+            .next()
+            .map(|value| seed.deserialize(ValueDeserializer::new(value)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapAccess {
+    iter: <Map as IntoIterator>::IntoIter,
+    value: Option<Value>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        let Some((key, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some(value);
+
+        seed.deserialize(ValueDeserializer::new(key)).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let Some(value) = self.value.take() else {
+            return Err(de::Error::custom("map value requested before its key"));
+        };
+
+        seed.deserialize(ValueDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumAccess {
+    variant: Value,
+    value: Option<Value>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = match self.variant {
+            Value::Int(value) => {
+                let index = u32::deserialize(ValueDeserializer::new(Value::Int(value)))?;
+                seed.deserialize(index.into_deserializer())?
+            }
+            Value::String(value) => seed.deserialize(value.into_string().into_deserializer())?,
+            other => return Err(de::Error::invalid_type(unexpected(&other), &"int, string")),
+        };
+
+        Ok((variant, VariantAccess { value: self.value }))
+    }
+}
+
+struct VariantAccess {
+    value: Option<Value>,
+}
+
+impl VariantAccess {
+    fn into_deserializer(self) -> Result<ValueDeserializer> {
+        match self.value {
+            Some(value) => Ok(ValueDeserializer::new(value)),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype, tuple or struct variant",
+            )),
+        }
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            None => Ok(()),
+            Some(value) => de::Deserialize::deserialize(ValueDeserializer::new(value)),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.into_deserializer()?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_any(self.into_deserializer()?, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_any(self.into_deserializer()?, visitor)
+    }
+}
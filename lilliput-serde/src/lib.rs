@@ -1,4 +1,17 @@
 //! A serializer and deserializer of the lilliput data format, for serde.
+//!
+//! # Options and units
+//!
+//! Serde's optional and unit types map onto lilliput's null and unit values as follows:
+//!
+//! - `None` gets encoded as null.
+//! - `Some(x)` gets encoded as `x` (i.e. without any wrapper).
+//! - `()` and unit structs get encoded as unit.
+//!
+//! When deserializing an `Option<T>` a null value gets deserialized as `None`,
+//! and any other value (including unit) as `Some(…)`.
+//! As such `Option<()>` retains the difference between `None` and `Some(())`,
+//! while `Option<Option<T>>` collapses `Some(None)` into `None`, as both get encoded as null.
 
 #![warn(missing_docs)]
 
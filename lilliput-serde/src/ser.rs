@@ -10,10 +10,14 @@ use lilliput_core::{
 };
 
 use crate::{
-    config::{EnumVariantRepr, SerializerConfig},
+    config::{EnumVariantRepr, SerializerConfig, StructRepr},
     error::{Error, Result},
 };
 
+mod value;
+
+pub use self::value::{to_value, to_value_with_config};
+
 /// An serializer for serializing lilliput values.
 pub struct Serializer<W> {
     pub(crate) encoder: Encoder<W>,
@@ -265,7 +269,10 @@ where
     }
 
     fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
-        self.serialize_map(Some(len))
+        match self.config.struct_repr {
+            StructRepr::Seq => self.serialize_seq(Some(len)),
+            StructRepr::Map => self.serialize_map(Some(len)),
+        }
     }
 
     fn serialize_struct_variant(
@@ -283,8 +290,16 @@ where
             EnumVariantRepr::Name => self.serialize_str(variant)?,
         }
 
-        let inner_map_header = self.encoder.header_for_map_len(len);
-        self.encoder.encode_map_header(&inner_map_header)?;
+        match self.config.struct_repr {
+            StructRepr::Seq => {
+                let inner_seq_header = self.encoder.header_for_seq_len(len);
+                self.encoder.encode_seq_header(&inner_seq_header)?;
+            }
+            StructRepr::Map => {
+                let inner_map_header = self.encoder.header_for_map_len(len);
+                self.encoder.encode_map_header(&inner_map_header)?;
+            }
+        }
 
         Ok(self)
     }
@@ -415,7 +430,10 @@ where
     where
         T: ?Sized + Serialize,
     {
-        key.serialize(&mut **self)?;
+        if self.config.struct_repr == StructRepr::Map {
+            key.serialize(&mut **self)?;
+        }
+
         value.serialize(&mut **self)
     }
 
@@ -437,7 +455,10 @@ where
     where
         T: ?Sized + Serialize,
     {
-        key.serialize(&mut **self)?;
+        if self.config.struct_repr == StructRepr::Map {
+            key.serialize(&mut **self)?;
+        }
+
         value.serialize(&mut **self)
     }
 
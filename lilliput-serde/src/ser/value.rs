@@ -0,0 +1,454 @@
+use serde::{ser, Serialize};
+
+use lilliput_core::value::{
+    BoolValue, BytesValue, FloatValue, IntValue, Map, MapValue, NullValue, Seq, SeqValue,
+    StringValue, UnitValue, Value,
+};
+
+use crate::{
+    config::{EnumVariantRepr, SerializerConfig, StructRepr},
+    error::{Error, Result},
+};
+
+/// Serializes `value` into a `Value`.
+///
+/// This builds the value directly, without encoding it into bytes first.
+pub fn to_value<T>(value: &T) -> Result<Value>
+where
+    T: ?Sized + Serialize,
+{
+    to_value_with_config(value, SerializerConfig::default())
+}
+
+/// Serializes `value` into a `Value`, configured by `config`.
+///
+/// Only the config's struct and enum representations apply,
+/// as the resulting value is not encoded.
+pub fn to_value_with_config<T>(value: &T, config: SerializerConfig) -> Result<Value>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(&ValueSerializer { config: &config })
+}
+
+/// A serializer for serializing into `Value`s.
+struct ValueSerializer<'c> {
+    config: &'c SerializerConfig,
+}
+
+impl ValueSerializer<'_> {
+    fn variant_key(&self, variant_index: u32, variant: &'static str) -> Value {
+        match self.config.enum_variant_repr {
+            EnumVariantRepr::Index => Value::Int(IntValue::from(variant_index)),
+            EnumVariantRepr::Name => Value::String(StringValue::from(variant.to_owned())),
+        }
+    }
+}
+
+/// Wraps `value` in a single-entry map with `variant_key` as its key.
+fn variant_map(variant_key: Value, value: Value) -> Value {
+    let mut map = Map::new();
+    map.insert(variant_key, value);
+    Value::Map(MapValue::from(map))
+}
+
+impl<'c> ser::Serializer for &ValueSerializer<'c> {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeSeq<'c>;
+    type SerializeTuple = SerializeSeq<'c>;
+    type SerializeTupleStruct = SerializeSeq<'c>;
+    type SerializeTupleVariant = SerializeSeq<'c>;
+    type SerializeMap = SerializeMap<'c>;
+    type SerializeStruct = SerializeStruct<'c>;
+    type SerializeStructVariant = SerializeStruct<'c>;
+
+    fn serialize_bool(self, value: bool) -> Result<Value> {
+        Ok(Value::Bool(BoolValue::from(value)))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Value> {
+        Ok(Value::Float(FloatValue::from(value)))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Value> {
+        Ok(Value::Float(FloatValue::from(value)))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Value> {
+        Ok(Value::String(StringValue::from(value.to_string())))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Value> {
+        Ok(Value::String(StringValue::from(value.to_owned())))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Value> {
+        Ok(Value::Bytes(BytesValue::from(value.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null(NullValue::from(())))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Unit(UnitValue::from(())))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        Ok(self.variant_key(variant_index, variant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        let variant_key = self.variant_key(variant_index, variant);
+        Ok(variant_map(variant_key, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(SerializeSeq {
+            config: self.config,
+            variant_key: None,
+            seq: Seq::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(SerializeSeq {
+            config: self.config,
+            variant_key: Some(self.variant_key(variant_index, variant)),
+            seq: Seq::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(SerializeMap {
+            config: self.config,
+            map: Map::new(),
+            pending_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        Ok(SerializeStruct::new(self.config, None, len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        let variant_key = self.variant_key(variant_index, variant);
+        Ok(SerializeStruct::new(self.config, Some(variant_key), len))
+    }
+}
+
+/// Collects sequences, tuples (and tuple variants).
+struct SerializeSeq<'c> {
+    config: &'c SerializerConfig,
+    variant_key: Option<Value>,
+    seq: Seq,
+}
+
+impl SerializeSeq<'_> {
+    fn push<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = value.serialize(&ValueSerializer {
+            config: self.config,
+        })?;
+        self.seq.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value> {
+        let value = Value::Seq(SeqValue::from(self.seq));
+
+        Ok(match self.variant_key {
+            Some(variant_key) => variant_map(variant_key, value),
+            None => value,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SerializeSeq<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeSeq<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeSeq<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeSeq<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+/// Collects maps.
+struct SerializeMap<'c> {
+    config: &'c SerializerConfig,
+    map: Map,
+    pending_key: Option<Value>,
+}
+
+impl ser::SerializeMap for SerializeMap<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = key.serialize(&ValueSerializer {
+            config: self.config,
+        })?;
+        self.pending_key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let Some(key) = self.pending_key.take() else {
+            return Err(ser::Error::custom("map value serialized before its key"));
+        };
+
+        let value = value.serialize(&ValueSerializer {
+            config: self.config,
+        })?;
+        self.map.insert(key, value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Map(MapValue::from(self.map)))
+    }
+}
+
+/// Collects structs (and struct variants), as either maps or sequences (see `StructRepr`).
+struct SerializeStruct<'c> {
+    config: &'c SerializerConfig,
+    variant_key: Option<Value>,
+    fields: StructFields,
+}
+
+enum StructFields {
+    Seq(Seq),
+    Map(Map),
+}
+
+impl<'c> SerializeStruct<'c> {
+    fn new(config: &'c SerializerConfig, variant_key: Option<Value>, len: usize) -> Self {
+        let fields = match config.struct_repr {
+            StructRepr::Seq => StructFields::Seq(Seq::with_capacity(len)),
+            StructRepr::Map => StructFields::Map(Map::new()),
+        };
+
+        Self {
+            config,
+            variant_key,
+            fields,
+        }
+    }
+
+    fn push<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = value.serialize(&ValueSerializer {
+            config: self.config,
+        })?;
+
+        match &mut self.fields {
+            StructFields::Seq(seq) => {
+                seq.push(value);
+            }
+            StructFields::Map(map) => {
+                map.insert(Value::String(StringValue::from(key.to_owned())), value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value> {
+        let value = match self.fields {
+            StructFields::Seq(seq) => Value::Seq(SeqValue::from(seq)),
+            StructFields::Map(map) => Value::Map(MapValue::from(map)),
+        };
+
+        Ok(match self.variant_key {
+            Some(variant_key) => variant_map(variant_key, value),
+            None => value,
+        })
+    }
+}
+
+impl ser::SerializeStruct for SerializeStruct<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeStruct<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
@@ -152,6 +152,158 @@ mod value {
             assert_eq!(&decoded, &value);
             prop_assert_eq!(&decoded, &value);
         }
+
+        #[test]
+        fn any_from_encoder(value in Value::arbitrary()) {
+            use lilliput_core::{encoder::Encoder, io::VecWriter};
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::from_writer(writer);
+            encoder.encode_value(&value).unwrap();
+
+            let decoded: Value = from_slice(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+    }
+}
+
+mod any {
+    use lilliput_core::{encoder::Encoder, io::VecWriter};
+
+    use super::*;
+
+    /// A visitor reporting the name of the `visit_*` method it received.
+    struct VisitKind;
+
+    macro_rules! visit_kind {
+        ($($method:ident($($ty:ty)?)),* $(,)?) => {
+            $(
+                fn $method<E>(self $(, _value: $ty)?) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    Ok(stringify!($method))
+                }
+            )*
+        };
+    }
+
+    impl<'de> serde::de::Visitor<'de> for VisitKind {
+        type Value = &'static str;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("anything")
+        }
+
+        visit_kind! {
+            visit_bool(bool),
+            visit_u8(u8),
+            visit_f32(f32),
+            visit_str(&str),
+            visit_borrowed_str(&'de str),
+            visit_bytes(&[u8]),
+            visit_borrowed_bytes(&'de [u8]),
+            visit_unit(),
+            visit_none(),
+        }
+
+        fn visit_seq<A>(self, _seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            Ok("visit_seq")
+        }
+
+        fn visit_map<A>(self, _map: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::MapAccess<'de>,
+        {
+            Ok("visit_map")
+        }
+    }
+
+    fn visit_kind(value: Value) -> &'static str {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_value(&value).unwrap();
+
+        let mut deserializer =
+            crate::de::Deserializer::from_reader(lilliput_core::io::SliceReader::new(&encoded));
+        serde::Deserializer::deserialize_any(&mut deserializer, VisitKind).unwrap()
+    }
+
+    #[test]
+    fn dispatch() {
+        let cases = [
+            (Value::Int(IntValue::from(42_u8)), "visit_u8"),
+            (
+                Value::String(StringValue::from("a".to_owned())),
+                "visit_borrowed_str",
+            ),
+            (Value::Seq(SeqValue::default()), "visit_seq"),
+            (Value::Map(MapValue::default()), "visit_map"),
+            (Value::Float(FloatValue::from(1.5_f32)), "visit_f32"),
+            (
+                Value::Bytes(BytesValue::from(vec![1, 2])),
+                "visit_borrowed_bytes",
+            ),
+            (Value::Bool(BoolValue::from(true)), "visit_bool"),
+            (Value::Unit(UnitValue), "visit_unit"),
+            (Value::Null(NullValue), "visit_none"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(visit_kind(value.clone()), expected, "{value:?}");
+        }
+    }
+}
+
+mod ext {
+    use lilliput_core::{encoder::Encoder, io::VecWriter};
+
+    use super::*;
+
+    fn encode_ext(value: &ExtValue) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_ext_value(value).unwrap();
+        encoded
+    }
+
+    #[test]
+    fn as_tuple() {
+        let value = ExtValue::new(42, vec![1, 2, 3]);
+        let encoded = encode_ext(&value);
+
+        let decoded: ExtValue = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+
+        let decoded: (u64, serde_bytes::ByteBuf) = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, (42, serde_bytes::ByteBuf::from(vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn as_value() {
+        let value = ExtValue::new(42, vec![1, 2, 3]);
+        let encoded = encode_ext(&value);
+
+        let decoded: Value = from_slice(&encoded).unwrap();
+        let expected = Value::Seq(SeqValue::from(vec![
+            Value::Int(IntValue::from(42_u64)),
+            Value::Bytes(BytesValue::from(vec![1, 2, 3])),
+        ]));
+        assert_eq!(decoded, expected);
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(value in ExtValue::arbitrary()) {
+            let decoded = super::roundtrip(&value)?;
+            prop_assert_eq!(&decoded, &value);
+        }
     }
 }
 
@@ -202,6 +354,665 @@ mod bytes_repr {
     }
 }
 
+mod struct_repr {
+    use lilliput_core::marker::Marker;
+
+    use crate::{
+        config::{SerializerConfig, StructRepr},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Subject {
+        id: u32,
+        name: String,
+        flag: bool,
+    }
+
+    fn subject() -> Subject {
+        Subject {
+            id: 42,
+            name: "Bob".to_owned(),
+            flag: true,
+        }
+    }
+
+    #[test]
+    fn map() {
+        let value = subject();
+
+        let config = SerializerConfig::default().with_struct_repr(StructRepr::Map);
+        let encoded = to_vec_with_config(&value, config).unwrap();
+        assert_eq!(Marker::detect(encoded[0]), Marker::Map);
+
+        let decoded: Subject = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn seq() {
+        let value = subject();
+
+        let config = SerializerConfig::default().with_struct_repr(StructRepr::Seq);
+        let encoded = to_vec_with_config(&value, config).unwrap();
+        assert_eq!(Marker::detect(encoded[0]), Marker::Seq);
+
+        let map_encoded = to_vec(&value).unwrap();
+        assert!(encoded.len() < map_encoded.len());
+
+        let decoded: Subject = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    proptest! {
+        #[test]
+        fn struct_variant_roundtrip(
+            value in Enum::<bool>::arbitrary_struct_variant(),
+            struct_repr in prop_oneof![Just(StructRepr::Seq), Just(StructRepr::Map)]
+        ) {
+            let config = SerializerConfig::default().with_struct_repr(struct_repr);
+            let encoded = to_vec_with_config(&value, config).unwrap();
+            let decoded: Enum<bool> = from_slice(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+    }
+}
+
+mod enum_repr {
+    use lilliput_core::marker::Marker;
+
+    use crate::{
+        config::{EnumVariantRepr, SerializerConfig},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    enum External {
+        Unit,
+        Newtype(u32),
+        Tuple(u32, String),
+        Struct { id: u32, name: String },
+    }
+
+    #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    #[serde(tag = "type")]
+    enum Internal {
+        Unit,
+        Newtype(Payload),
+        Struct { id: u32, name: String },
+    }
+
+    #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    #[serde(tag = "t", content = "c")]
+    enum Adjacent {
+        Unit,
+        Newtype(u32),
+        Tuple(u32, String),
+        Struct { id: u32, name: String },
+    }
+
+    #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Payload {
+        flag: bool,
+    }
+
+    fn external_values() -> Vec<External> {
+        vec![
+            External::Unit,
+            External::Newtype(42),
+            External::Tuple(42, "Bob".to_owned()),
+            External::Struct {
+                id: 42,
+                name: "Bob".to_owned(),
+            },
+        ]
+    }
+
+    fn assert_byte_roundtrip<T>(value: &T, config: SerializerConfig)
+    where
+        T: PartialEq + std::fmt::Debug + Serialize + DeserializeOwned,
+    {
+        let encoded = to_vec_with_config(value, config.clone()).unwrap();
+        let decoded: T = from_slice(&encoded).unwrap();
+        assert_eq!(&decoded, value);
+
+        let reencoded = to_vec_with_config(&decoded, config).unwrap();
+        assert_eq!(reencoded, encoded);
+    }
+
+    #[test]
+    fn externally_tagged() {
+        for repr in [EnumVariantRepr::Index, EnumVariantRepr::Name] {
+            for value in external_values() {
+                let config = SerializerConfig::default().with_enum_variant_repr(repr.clone());
+                assert_byte_roundtrip(&value, config);
+            }
+        }
+    }
+
+    #[test]
+    fn newtype_variant_is_not_double_wrapped() {
+        let config = SerializerConfig::default().with_enum_variant_repr(EnumVariantRepr::Name);
+
+        let encoded = to_vec_with_config(&External::Newtype(42), config.clone()).unwrap();
+        let expected = to_vec_with_config(
+            &std::collections::BTreeMap::from([("Newtype", 42_u32)]),
+            config,
+        )
+        .unwrap();
+
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn unit_variant_is_bare_discriminant() {
+        let encoded = to_vec(&External::Unit).unwrap();
+        assert_eq!(Marker::detect(encoded[0]), Marker::Int);
+
+        let config = SerializerConfig::default().with_enum_variant_repr(EnumVariantRepr::Name);
+        let encoded = to_vec_with_config(&External::Unit, config).unwrap();
+        assert_eq!(Marker::detect(encoded[0]), Marker::String);
+    }
+
+    #[test]
+    fn internally_tagged() {
+        let values = [
+            Internal::Unit,
+            Internal::Newtype(Payload { flag: true }),
+            Internal::Struct {
+                id: 42,
+                name: "Bob".to_owned(),
+            },
+        ];
+
+        for value in values {
+            assert_byte_roundtrip(&value, SerializerConfig::default());
+        }
+    }
+
+    #[test]
+    fn adjacently_tagged() {
+        let values = [
+            Adjacent::Unit,
+            Adjacent::Newtype(42),
+            Adjacent::Tuple(42, "Bob".to_owned()),
+            Adjacent::Struct {
+                id: 42,
+                name: "Bob".to_owned(),
+            },
+        ];
+
+        for value in values {
+            assert_byte_roundtrip(&value, SerializerConfig::default());
+        }
+    }
+
+    #[test]
+    fn out_of_range_index_is_error() {
+        let encoded = to_vec(&7_u32).unwrap();
+        assert!(from_slice::<External>(&encoded).is_err());
+
+        let encoded = to_vec(&std::collections::BTreeMap::from([(7_u32, 42_u32)])).unwrap();
+        assert!(from_slice::<External>(&encoded).is_err());
+    }
+}
+
+mod unknown_fields {
+    use lilliput_core::config::DecoderConfig;
+
+    use crate::de::from_slice_with_config;
+
+    use super::*;
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Full {
+        id: u32,
+        extra: Vec<String>,
+        name: String,
+    }
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Partial {
+        id: u32,
+        name: String,
+    }
+
+    fn encoded() -> Vec<u8> {
+        let value = Full {
+            id: 42,
+            extra: vec!["foo".to_owned(), "bar".to_owned()],
+            name: "Bob".to_owned(),
+        };
+
+        to_vec(&value).unwrap()
+    }
+
+    #[test]
+    fn skipped_by_default() {
+        let decoded: Partial = from_slice(&encoded()).unwrap();
+
+        assert_eq!(
+            decoded,
+            Partial {
+                id: 42,
+                name: "Bob".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn denied() {
+        let config = DecoderConfig::default().with_deny_unknown_fields(true);
+        let result: Result<Partial, _> = from_slice_with_config(&encoded(), config);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn known_fields_accepted_when_denied() {
+        let config = DecoderConfig::default().with_deny_unknown_fields(true);
+        let encoded = to_vec(&Partial {
+            id: 42,
+            name: "Bob".to_owned(),
+        })
+        .unwrap();
+
+        let decoded: Partial = from_slice_with_config(&encoded, config).unwrap();
+
+        assert_eq!(decoded.id, 42);
+    }
+
+    #[test]
+    fn unknown_bool_skipped() {
+        #[derive(Serialize)]
+        struct WithBool {
+            id: u32,
+            flag: bool,
+            name: String,
+        }
+
+        let encoded = to_vec(&WithBool {
+            id: 42,
+            flag: true,
+            name: "Bob".to_owned(),
+        })
+        .unwrap();
+
+        let decoded: Partial = from_slice(&encoded).unwrap();
+
+        assert_eq!(
+            decoded,
+            Partial {
+                id: 42,
+                name: "Bob".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn denied_by_serde_attribute() {
+        #[derive(Debug, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct Strict {
+            id: u32,
+            name: String,
+        }
+
+        let result: Result<Strict, _> = from_slice(&encoded());
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deeply_nested_unknown_field() {
+        use lilliput_core::{encoder::Encoder, header::SeqHeader, io::VecWriter};
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder
+            .encode_map_header(&encoder.header_for_map_len(3))
+            .unwrap();
+        encoder.encode_str("id").unwrap();
+        encoder.encode_u32(42).unwrap();
+        encoder.encode_str("extra").unwrap();
+        for _ in 0..200_000 {
+            encoder.encode_seq_header(&SeqHeader::compact(1)).unwrap();
+        }
+        encoder.encode_null().unwrap();
+        encoder.encode_str("name").unwrap();
+        encoder.encode_str("Bob").unwrap();
+
+        // Fails with an error, rather than overflowing the stack:
+        let result: Result<Partial, _> = from_slice(&encoded);
+
+        assert!(result.is_err());
+    }
+}
+
+mod map_keys {
+    use std::collections::BTreeMap;
+
+    use lilliput_core::{
+        decoder::Decoder,
+        io::SliceReader,
+        value::{IntValue, Value},
+    };
+
+    use super::*;
+
+    #[test]
+    fn int_keys_are_encoded_natively() {
+        let map = BTreeMap::from([(-1_i64, "foo".to_owned()), (42_i64, "bar".to_owned())]);
+
+        let encoded = to_vec(&map).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let Value::Map(value) = decoder.decode_value().unwrap() else {
+            panic!("expected map");
+        };
+        let map_ref = value.as_map_ref();
+        assert!(map_ref.keys().all(|key| matches!(key, Value::Int(_))));
+        assert!(map_ref.contains_key(&Value::Int(IntValue::from(42_i64))));
+
+        let decoded: BTreeMap<i64, String> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, map);
+    }
+
+    proptest! {
+        #[test]
+        fn int_keys_roundtrip(map in prop::collection::btree_map(any::<i64>(), any::<String>(), 0..8)) {
+            let decoded = super::roundtrip(&map)?;
+            prop_assert_eq!(&decoded, &map);
+        }
+
+        #[test]
+        fn scalar_keys_roundtrip(
+            bools in prop::collection::btree_map(any::<bool>(), any::<u32>(), 0..2),
+            chars in prop::collection::btree_map(any::<char>(), any::<u32>(), 0..8),
+            units in prop::collection::btree_map(any::<u8>(), any::<()>(), 0..8),
+        ) {
+            prop_assert_eq!(&super::roundtrip(&bools)?, &bools);
+            prop_assert_eq!(&super::roundtrip(&chars)?, &chars);
+            prop_assert_eq!(&super::roundtrip(&units)?, &units);
+        }
+    }
+}
+
+mod value_bridge {
+    use lilliput_core::{decoder::Decoder, io::SliceReader};
+
+    use crate::{
+        config::{EnumVariantRepr, SerializerConfig, StructRepr},
+        de::from_value,
+        ser::{to_value, to_value_with_config, to_vec_with_config},
+    };
+
+    use super::*;
+
+    #[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Outer {
+        id: u32,
+        name: String,
+        inner: Inner,
+        items: Vec<Inner>,
+        lookup: BTreeMap<i64, Option<String>>,
+        variants: Vec<Enum<u8>>,
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        unit: UnitStruct,
+        newtype: NewtypeStruct<i16>,
+    }
+
+    #[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Inner {
+        flag: bool,
+        score: f64,
+        tuple: (u8, char),
+    }
+
+    fn outer() -> Outer {
+        let inner = Inner {
+            flag: true,
+            score: 0.5,
+            tuple: (42, 'x'),
+        };
+
+        Outer {
+            id: 42,
+            name: "Bob".to_owned(),
+            inner: inner.clone(),
+            items: vec![inner.clone(), inner],
+            lookup: BTreeMap::from([(-1, None), (1, Some("one".to_owned()))]),
+            variants: vec![
+                Enum::UnitVariant,
+                Enum::NewtypeTupleVariant(1),
+                Enum::TupleVariant(2, 3),
+                Enum::NewtypeStructVariant { a: 4 },
+                Enum::StructVariant { a: 5, b: 6 },
+            ],
+            data: vec![1, 2, 3],
+            unit: UnitStruct,
+            newtype: NewtypeStruct(-7),
+        }
+    }
+
+    fn decode_value(bytes: &[u8]) -> Value {
+        let mut decoder = Decoder::from_reader(SliceReader::new(bytes));
+        decoder.decode_value().unwrap()
+    }
+
+    #[test]
+    fn nested_roundtrip() {
+        let value = outer();
+
+        let converted = to_value(&value).unwrap();
+        let decoded: Outer = from_value(converted).unwrap();
+
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn matches_encoded() {
+        let value = outer();
+
+        for struct_repr in [StructRepr::Map, StructRepr::Seq] {
+            for enum_variant_repr in [EnumVariantRepr::Index, EnumVariantRepr::Name] {
+                let config = SerializerConfig::default()
+                    .with_struct_repr(struct_repr.clone())
+                    .with_enum_variant_repr(enum_variant_repr);
+
+                let converted = to_value_with_config(&value, config.clone()).unwrap();
+                let encoded = to_vec_with_config(&value, config).unwrap();
+                assert_eq!(converted, decode_value(&encoded));
+
+                let decoded: Outer = from_value(converted).unwrap();
+                assert_eq!(decoded, value);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_type() {
+        let value = to_value(&"not a number").unwrap();
+        assert!(from_value::<u32>(value).is_err());
+
+        let value = to_value(&7_u32).unwrap();
+        assert!(from_value::<Enum<u8>>(value).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn value_roundtrip(value in Value::arbitrary()) {
+            let converted = to_value(&value).unwrap();
+            prop_assert_eq!(&converted, &value);
+
+            let decoded: Value = from_value(converted).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+
+        #[test]
+        fn enum_roundtrip(value in Enum::<i32>::arbitrary()) {
+            let decoded: Enum<i32> = from_value(to_value(&value).unwrap()).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+    }
+}
+
+mod cow {
+    use std::borrow::Cow;
+
+    use lilliput_core::{encoder::Encoder, io::VecWriter};
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Borrowing<'a> {
+        string: Cow<'a, str>,
+        #[serde(with = "serde_bytes")]
+        bytes: Cow<'a, [u8]>,
+    }
+
+    #[test]
+    fn borrowed_matches_owned() {
+        let string = "lilliput";
+        let bytes: &[u8] = &[1, 2, 3];
+
+        let borrowed = Borrowing {
+            string: Cow::Borrowed(string),
+            bytes: Cow::Borrowed(bytes),
+        };
+        let owned = Borrowing {
+            string: Cow::Owned(string.to_owned()),
+            bytes: Cow::Owned(bytes.to_vec()),
+        };
+
+        let encoded = to_vec(&borrowed).unwrap();
+        assert_eq!(encoded, to_vec(&owned).unwrap());
+
+        // Borrowed slices get encoded directly, just like by the encoder itself:
+        let mut expected: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut expected));
+        encoder.encode_str(string).unwrap();
+        encoder.encode_bytes(bytes).unwrap();
+
+        assert_eq!(to_vec(&borrowed.string).unwrap(), expected[..9]);
+        assert_eq!(
+            to_vec(&serde_bytes::Bytes::new(&borrowed.bytes)).unwrap(),
+            expected[9..]
+        );
+    }
+}
+
+mod options_and_units {
+    use lilliput_core::{decoder::Decoder, io::SliceReader, marker::Marker};
+
+    use super::*;
+
+    fn marker_of<T>(value: &T) -> Marker
+    where
+        T: Serialize,
+    {
+        let encoded = to_vec(value).unwrap();
+        Decoder::from_reader(SliceReader::new(&encoded))
+            .peek_marker()
+            .unwrap()
+    }
+
+    #[test]
+    fn mapping() {
+        assert_eq!(marker_of(&None::<u8>), Marker::Null);
+        assert_eq!(marker_of(&Some(42_u8)), Marker::Int);
+        assert_eq!(marker_of(&()), Marker::Unit);
+        assert_eq!(marker_of(&UnitStruct), Marker::Unit);
+        assert_eq!(marker_of(&Some(())), Marker::Unit);
+        assert_eq!(marker_of(&Some(None::<u8>)), Marker::Null);
+    }
+
+    #[test]
+    fn option_of_unit() {
+        assert_eq!(roundtrip(&None::<()>).unwrap(), None);
+        assert_eq!(roundtrip(&Some(())).unwrap(), Some(()));
+
+        assert_eq!(roundtrip(&None::<UnitStruct>).unwrap(), None);
+        assert_eq!(roundtrip(&Some(UnitStruct)).unwrap(), Some(UnitStruct));
+    }
+
+    #[test]
+    fn option_of_option() {
+        assert_eq!(roundtrip(&None::<Option<u8>>).unwrap(), None);
+        assert_eq!(roundtrip(&Some(Some(42_u8))).unwrap(), Some(Some(42_u8)));
+
+        // Both `None` and `Some(None)` get encoded as null:
+        assert_eq!(
+            to_vec(&Some(None::<u8>)).unwrap(),
+            to_vec(&None::<Option<u8>>).unwrap()
+        );
+        assert_eq!(roundtrip(&Some(None::<u8>)).unwrap(), None);
+
+        // Unless the inner one is distinct from null:
+        assert_eq!(roundtrip(&Some(Some(()))).unwrap(), Some(Some(())));
+    }
+
+    #[test]
+    fn unit_is_not_none() {
+        let encoded = to_vec(&()).unwrap();
+        assert!(from_slice::<()>(&to_vec(&None::<u8>).unwrap()).is_err());
+        assert_eq!(from_slice::<Option<()>>(&encoded).unwrap(), Some(()));
+
+        assert_eq!(
+            from_slice::<Value>(&encoded).unwrap(),
+            Value::Unit(UnitValue)
+        );
+        assert_eq!(
+            from_slice::<Value>(&to_vec(&None::<u8>).unwrap()).unwrap(),
+            Value::Null(NullValue)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn option_of_option_roundtrip(value in Option::<Option<u8>>::arbitrary()) {
+            let decoded = roundtrip(&value)?;
+            prop_assert_eq!(decoded, value.flatten().map(Some));
+        }
+    }
+}
+
+mod trailing_data {
+    use lilliput_core::error::ErrorCode;
+
+    use crate::de::from_slice_exact;
+
+    use super::*;
+
+    #[test]
+    fn exact() {
+        let encoded = to_vec(&Struct { a: 1_u8, b: 2_u8 }).unwrap();
+        let decoded: Struct<u8> = from_slice_exact(&encoded).unwrap();
+        assert_eq!(decoded, Struct { a: 1, b: 2 });
+    }
+
+    #[test]
+    fn trailing_garbage() {
+        let mut encoded = to_vec(&Struct { a: 1_u8, b: 2_u8 }).unwrap();
+        let len = encoded.len();
+        encoded.extend_from_slice(&[0xFF, 0xFF]);
+
+        // Lenient decoding ignores any trailing bytes:
+        let decoded: Struct<u8> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, Struct { a: 1, b: 2 });
+
+        let err = from_slice_exact::<Struct<u8>>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TrailingData);
+        assert_eq!(err.pos(), Some(len));
+    }
+}
+
 mod zero_copy {
     use super::*;
 
@@ -227,6 +1038,84 @@ mod zero_copy {
         assert_eq!(decoded, value);
     }
 
+    #[test]
+    fn borrowed_str_points_into_input() {
+        #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+        struct Subject<'a> {
+            id: u32,
+            name: &'a str,
+        }
+
+        let value = Subject {
+            id: 42,
+            name: "lilliput",
+        };
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: Subject = from_slice(&encoded).unwrap();
+
+        assert_eq!(decoded, value);
+
+        // The decoded string must borrow from the input, rather than from a copy:
+        let input = encoded.as_ptr_range();
+        let name = decoded.name.as_bytes().as_ptr_range();
+        assert!(input.start <= name.start && name.end <= input.end);
+    }
+
+    #[test]
+    fn borrowed_cow_str() {
+        #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+        struct Subject<'a> {
+            #[serde(borrow)]
+            name: std::borrow::Cow<'a, str>,
+        }
+
+        let value = Subject {
+            name: "lilliput".into(),
+        };
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: Subject = from_slice(&encoded).unwrap();
+
+        assert_eq!(decoded, value);
+        assert!(matches!(decoded.name, std::borrow::Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn borrowed_map_keys() {
+        let value: std::collections::HashMap<&str, i32> =
+            [("foo", 1), ("bar", -2), ("baz", 3)].into_iter().collect();
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: std::collections::HashMap<&str, i32> = from_slice(&encoded).unwrap();
+
+        assert_eq!(decoded, value);
+
+        // The decoded keys must borrow from the input, rather than from a copy:
+        let input = encoded.as_ptr_range();
+        for key in decoded.keys() {
+            let key = key.as_bytes().as_ptr_range();
+            assert!(input.start <= key.start && key.end <= input.end);
+        }
+    }
+
+    #[test]
+    fn copied_str_from_reader() {
+        #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+        struct Subject {
+            name: String,
+        }
+
+        let value = Subject {
+            name: "lilliput".to_owned(),
+        };
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: Subject = crate::de::from_reader(encoded.as_slice()).unwrap();
+
+        assert_eq!(decoded, value);
+    }
+
     #[test]
     fn owned() {
         #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]